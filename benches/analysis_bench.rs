@@ -0,0 +1,37 @@
+#[macro_use]
+extern crate criterion;
+extern crate blockstack_lib;
+
+use blockstack_lib::vm::analysis::mem_type_check;
+
+use criterion::Criterion;
+
+const LET_DEPTH: usize = 50;
+const BINDINGS_PER_LET: usize = 20;
+
+/// Builds `LET_DEPTH` nested lets, each binding `BINDINGS_PER_LET` names to names bound by
+///   the enclosing let, so that every lookup walks the chain of typing contexts.
+fn nested_lets() -> String {
+    let mut program = String::new();
+    for depth in 0..LET_DEPTH {
+        let bindings: Vec<_> = (0..BINDINGS_PER_LET)
+            .map(|i| if depth == 0 {
+                format!("(x-{}-{} {})", depth, i, i)
+            } else {
+                format!("(x-{}-{} x-0-{})", depth, i, i)
+            })
+            .collect();
+        program.push_str(&format!("(let ({}) ", bindings.join(" ")));
+    }
+    program.push_str(&format!("(+ x-0-0 x-{}-0)", LET_DEPTH - 1));
+    program.push_str(&")".repeat(LET_DEPTH));
+    program
+}
+
+pub fn nested_let_benchmark(c: &mut Criterion) {
+    let program = nested_lets();
+    c.bench_function("type_check_nested_lets", |b| b.iter(|| mem_type_check(&program).unwrap()));
+}
+
+criterion_group!(benches, nested_let_benchmark);
+criterion_main!(benches);
@@ -1115,7 +1115,7 @@ pub mod test {
           (begin (var-set bar (/ x y)) (ok (var-get bar))))";
 
         let contract_runtime_error_definition = "
-        (define-data-var bar int (/ 1 0))   ;; divide-by-zero
+        (define-data-var bar int (/ 1 (to-int u0)))   ;; divide-by-zero, which analysis cannot fold
         (define-public (get-bar) (ok (var-get bar)))
         (define-public (set-bar (x int) (y int))
           (begin (var-set bar (/ x y)) (ok (var-get bar))))";
@@ -119,6 +119,14 @@ impl_array_newtype!(Sha512Trunc256Sum, u8, 32);
 impl_array_hexstring_fmt!(Sha512Trunc256Sum);
 impl_byte_array_newtype!(Sha512Trunc256Sum, u8, 32);
 
+#[derive(Serialize, Deserialize)]
+pub struct Ripemd160Sum(
+    #[serde(serialize_with = "Hash20::json_serialize", deserialize_with = "Hash20::json_deserialize")]
+    pub [u8; 20]);
+impl_array_newtype!(Ripemd160Sum, u8, 20);
+impl_array_hexstring_fmt!(Ripemd160Sum);
+impl_byte_array_newtype!(Ripemd160Sum, u8, 20);
+
 #[derive(Serialize, Deserialize)]
 pub struct DoubleSha256(
     #[serde(serialize_with = "Hash32::json_serialize", deserialize_with = "Hash32::json_deserialize")]
@@ -168,6 +176,12 @@ impl Sha512Trunc256Sum {
     }
 }
 
+impl Ripemd160Sum {
+    pub fn from_data(data: &[u8]) -> Ripemd160Sum {
+        Ripemd160Sum::from(Ripemd160::digest(data).as_slice())
+    }
+}
+
 impl MerkleHashFunc for Hash160 {
     fn empty() -> Hash160 {
         Hash160([0u8; 20])
@@ -30,7 +30,7 @@ pub fn is_big_endian() -> bool {
 macro_rules! define_named_enum {
     ($Name:ident { $($Variant:ident($VarName:literal),)* }) =>
     {
-        #[derive(Debug)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum $Name {
             $($Variant),*,
         }
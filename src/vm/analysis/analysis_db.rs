@@ -116,6 +116,12 @@ impl <'a> AnalysisDatabase <'a> {
            .cloned())
     }
 
+    pub fn has_private_function(&mut self, contract_identifier: &QualifiedContractIdentifier, function_name: &str) -> CheckResult<bool> {
+        let contract = self.load_contract(contract_identifier)
+            .ok_or(CheckErrors::NoSuchContract(contract_identifier.to_string()))?;
+        Ok(contract.get_private_function(function_name).is_some())
+    }
+
     pub fn get_defined_trait(&mut self, contract_identifier: &QualifiedContractIdentifier, trait_name: &str) -> CheckResult<Option<BTreeMap<ClarityName, FunctionSignature>>> {
         // TODO: this function loads the whole contract to obtain the function type.
         //         but it doesn't need to -- rather this information can just be 
@@ -133,6 +139,8 @@ impl <'a> AnalysisDatabase <'a> {
         Ok(contract.implemented_traits)
     }
 
+    /// Looks up the key and value types of a map defined by another, already analyzed, contract.
+    ///   Fails with `NoSuchContract` or `NoSuchMap` if either is unknown.
     pub fn get_map_type(&mut self, contract_identifier: &QualifiedContractIdentifier, map_name: &str) -> CheckResult<(TypeSignature, TypeSignature)> {
         let contract = self.load_contract(contract_identifier)
             .ok_or(CheckErrors::NoSuchContract(contract_identifier.to_string()))?;
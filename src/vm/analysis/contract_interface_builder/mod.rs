@@ -1,4 +1,4 @@
-use vm::analysis::types::ContractAnalysis;
+use vm::analysis::types::{ContractAnalysis, FunctionKind};
 use std::collections::{BTreeMap, BTreeSet};
 use vm::{ClarityName};
 use vm::types::{TypeSignature, FunctionArg, TupleTypeSignature, FunctionType, FixedFunction};
@@ -17,27 +17,34 @@ pub fn build_contract_interface(contract_analysis: &ContractAnalysis) -> Contrac
         non_fungible_tokens,
         defined_traits: _,
         implemented_traits: _,
+        print_events: _,
+        dependencies: _,
+        used_natives: _,
+        function_costs: _,
         expressions: _,
         contract_identifier: _,
         type_map: _,
         cost_track: _,
         contract_interface: _,
+        analysis_options: _,
+        diagnostics: _,
+        type_trace: _,
     } = contract_analysis;
 
     contract_interface.functions.append(
         &mut ContractInterfaceFunction::from_map(
             private_function_types, 
-            ContractInterfaceFunctionAccess::private));
+            FunctionKind::Private.into()));
 
     contract_interface.functions.append(
         &mut ContractInterfaceFunction::from_map(
             public_function_types, 
-            ContractInterfaceFunctionAccess::public));
+            FunctionKind::Public.into()));
 
     contract_interface.functions.append(
         &mut ContractInterfaceFunction::from_map(
             read_only_function_types, 
-            ContractInterfaceFunctionAccess::read_only));
+            FunctionKind::ReadOnly.into()));
 
     contract_interface.variables.append(
         &mut ContractInterfaceVariable::from_map(
@@ -68,6 +75,16 @@ pub enum ContractInterfaceFunctionAccess {
     read_only,
 }
 
+impl From<FunctionKind> for ContractInterfaceFunctionAccess {
+    fn from(kind: FunctionKind) -> ContractInterfaceFunctionAccess {
+        match kind {
+            FunctionKind::Private => ContractInterfaceFunctionAccess::private,
+            FunctionKind::Public => ContractInterfaceFunctionAccess::public,
+            FunctionKind::ReadOnly => ContractInterfaceFunctionAccess::read_only,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContractInterfaceTupleEntryType {
     pub name: String,
@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use vm::analysis::types::{ContractAnalysis, AnalysisPass, FunctionKind};
+use vm::analysis::AnalysisDatabase;
+use vm::analysis::errors::{CheckResult, CheckErrors, CheckWarnings};
+use vm::analysis::type_checker::collect_state_mutations;
+use vm::diagnostic::Diagnostic;
+use vm::representations::{SymbolicExpression, ClarityName};
+use vm::types::TypeSignature;
+use vm::functions::NativeFunctions;
+use vm::functions::iterables::Lambda;
+use vm::functions::define::DefineFunctionsParsed;
+
+#[cfg(test)]
+mod tests;
+
+/// The least cost, under `AnalysisOptions::cost_table`, of an expression whose repetition in
+///   a function body is reported by the opt-in lints. With the default table, this is more
+///   than a single read of the contract's storage.
+pub const REPEATED_EXPRESSION_MIN_COST: u64 = 25;
+
+/// Computes the static cost of each function the contract defines, weighting the natives
+///   it applies by `AnalysisOptions::cost_table`. This pass must run after the type checker,
+///   whose inferred sequence lengths bound the iterations of `map`, `filter` and `fold`.
+///   A sequence operation nested in the function of another must have such a bound, or the
+///   pass fails with `UnboundedSequenceOperation`. With `AnalysisOptions::lint`, the pass
+///   also reports costly expressions which a function body computes more than once.
+pub struct CostAnalyzer {
+    function_costs: BTreeMap<ClarityName, u64>,
+    // the defined functions which apply a sequence operation without a static bound
+    unbounded_functions: HashSet<ClarityName>,
+    // whether the function being costed applies a sequence operation without a static bound
+    found_unbounded: bool,
+    // the number of sequence operations whose function is being costed
+    iteration_depth: u32,
+}
+
+impl AnalysisPass for CostAnalyzer {
+
+    fn run_pass(contract_analysis: &mut ContractAnalysis, _analysis_db: &mut AnalysisDatabase) -> CheckResult<()> {
+        let mut command = CostAnalyzer::new();
+        command.run(contract_analysis)?;
+        Ok(())
+    }
+}
+
+impl CostAnalyzer {
+
+    fn new() -> Self {
+        Self {
+            function_costs: BTreeMap::new(),
+            unbounded_functions: HashSet::new(),
+            found_unbounded: false,
+            iteration_depth: 0,
+        }
+    }
+
+    pub fn run(&mut self, contract_analysis: &mut ContractAnalysis) -> CheckResult<()> {
+        use vm::functions::define::DefineFunctionsParsed::*;
+
+        // the definition sorter orders the top-level expressions so that a function's
+        //   callees are always defined, and so costed, before the function itself.
+        let mut diagnostics = Vec::new();
+        for exp in contract_analysis.expressions.iter() {
+            match DefineFunctionsParsed::try_parse(exp)? {
+                Some(PrivateFunction { signature, body }) | Some(PublicFunction { signature, body }) |
+                Some(ReadOnlyFunction { signature, body }) => {
+                    let function_name = signature.get(0)
+                        .ok_or(CheckErrors::DefineFunctionBadSignature)?
+                        .match_atom().ok_or(CheckErrors::BadFunctionName)?;
+                    self.found_unbounded = false;
+                    let cost = self.expression_cost(contract_analysis, body)?;
+                    self.function_costs.insert(function_name.clone(), cost);
+                    if self.found_unbounded {
+                        self.unbounded_functions.insert(function_name.clone());
+                    }
+                    if contract_analysis.analysis_options.lint {
+                        self.lint_repeated_expressions(contract_analysis, body, &mut diagnostics)?;
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        for (name, cost) in self.function_costs.iter() {
+            contract_analysis.add_function_cost(name.clone(), *cost);
+        }
+        contract_analysis.diagnostics.append(&mut diagnostics);
+        Ok(())
+    }
+
+    /// Costly expressions computed more than once in a function body could instead be bound
+    ///   once with `let`: each such expression is reported, spanning its occurrences.
+    fn lint_repeated_expressions(&mut self, contract_analysis: &ContractAnalysis, body: &SymbolicExpression,
+                                 diagnostics: &mut Vec<Diagnostic>) -> CheckResult<()> {
+        let mut occurrences = HashMap::new();
+        self.collect_costly_expressions(contract_analysis, body, &mut occurrences)?;
+        let mut repeated = Vec::new();
+        collect_repeated_expressions(body, &occurrences, &mut repeated);
+        for expr in repeated {
+            let found = &occurrences[expr];
+            let mut diagnostic = Diagnostic::warn(&CheckWarnings::RepeatedExpression(found.len()));
+            diagnostic.spans = found.iter().map(|e| e.span.clone()).collect();
+            diagnostics.push(diagnostic);
+        }
+        Ok(())
+    }
+
+    /// Collects the occurrences of each expression in `expr` which costs at least
+    ///   `REPEATED_EXPRESSION_MIN_COST` and is pure (and so could be computed once),
+    ///   keyed by its syntax.
+    fn collect_costly_expressions<'c>(&mut self, contract_analysis: &ContractAnalysis, expr: &'c SymbolicExpression,
+                                      occurrences: &mut HashMap<&'c SymbolicExpression, Vec<SymbolicExpression>>) -> CheckResult<()> {
+        if let Some(list) = expr.match_list() {
+            if is_pure_expression(contract_analysis, expr) &&
+                self.expression_cost(contract_analysis, expr)? >= REPEATED_EXPRESSION_MIN_COST {
+                occurrences.entry(expr).or_insert_with(Vec::new).push(expr.clone());
+            }
+            for sub_expr in list.iter() {
+                self.collect_costly_expressions(contract_analysis, sub_expr, occurrences)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn expression_cost(&mut self, contract_analysis: &ContractAnalysis, expr: &SymbolicExpression) -> CheckResult<u64> {
+        let expression = match expr.match_list() {
+            Some(expression) => expression,
+            None => return Ok(0)
+        };
+
+        if let Some((function_name, args)) = expression.split_first() {
+            if let Some(name) = function_name.match_atom() {
+                if let Some(native_function) = NativeFunctions::lookup_by_name(name) {
+                    return self.native_application_cost(contract_analysis, &native_function, args)
+                }
+                if let Some(cost) = self.function_costs.get(name).cloned() {
+                    self.check_bounded_function(name)?;
+                    return Ok(cost.saturating_add(self.all_expressions_cost(contract_analysis, args)?))
+                }
+            }
+        }
+
+        // binding lists, tuple entries, and the like.
+        self.all_expressions_cost(contract_analysis, expression)
+    }
+
+    fn native_application_cost(&mut self, contract_analysis: &ContractAnalysis, function: &NativeFunctions, args: &[SymbolicExpression]) -> CheckResult<u64> {
+        use vm::functions::NativeFunctions::*;
+        let weight = contract_analysis.analysis_options.cost_table.weight(function);
+        match function {
+            Map | Filter | Fold if args.len() >= 2 => {
+                let iterations = match CostAnalyzer::max_iterations(contract_analysis, function, &args[1..]) {
+                    Some(iterations) => iterations,
+                    None if self.iteration_depth > 0 => return Err(CheckErrors::UnboundedSequenceOperation.into()),
+                    None => {
+                        self.found_unbounded = true;
+                        0
+                    }
+                };
+                self.iteration_depth += 1;
+                let callback_cost = self.callback_cost(contract_analysis, &args[0]);
+                self.iteration_depth -= 1;
+                Ok(weight
+                   .saturating_add(callback_cost?.saturating_mul(iterations))
+                   .saturating_add(self.all_expressions_cost(contract_analysis, &args[1..])?))
+            },
+            _ => Ok(weight.saturating_add(self.all_expressions_cost(contract_analysis, args)?))
+        }
+    }
+
+    /// The cost of a single application of the function passed to `map`, `filter` or `fold`.
+    fn callback_cost(&mut self, contract_analysis: &ContractAnalysis, callback: &SymbolicExpression) -> CheckResult<u64> {
+        if let Some(lambda) = Lambda::parse(callback)? {
+            return self.expression_cost(contract_analysis, lambda.body)
+        }
+
+        match callback.match_atom() {
+            Some(name) => match NativeFunctions::lookup_by_name(name) {
+                Some(native_function) => Ok(contract_analysis.analysis_options.cost_table.weight(&native_function)),
+                None => {
+                    self.check_bounded_function(name)?;
+                    Ok(self.function_costs.get(name).cloned().unwrap_or(0))
+                }
+            },
+            None => Ok(0)
+        }
+    }
+
+    /// Notes an application of the defined function `name`, which may not apply an
+    ///   unbounded sequence operation if it is nested in the function of another.
+    fn check_bounded_function(&mut self, name: &str) -> CheckResult<()> {
+        if self.unbounded_functions.contains(name) {
+            if self.iteration_depth > 0 {
+                return Err(CheckErrors::UnboundedSequenceOperation.into())
+            }
+            self.found_unbounded = true;
+        }
+        Ok(())
+    }
+
+    /// The most times an iterable function may apply its callback, from the maximum lengths
+    ///   of the sequences it visits: `map` stops at the end of its shortest sequence.
+    ///   Returns `None` if the type checker inferred no maximum length for a sequence.
+    fn max_iterations(contract_analysis: &ContractAnalysis, function: &NativeFunctions, sequences: &[SymbolicExpression]) -> Option<u64> {
+        let sequences = match function {
+            NativeFunctions::Map => sequences,
+            _ => &sequences[..1]
+        };
+
+        let mut iterations = None;
+        for sequence in sequences.iter() {
+            let max_len = match contract_analysis.get_expression_type(sequence) {
+                Some(TypeSignature::ListType(list_data)) => u64::from(list_data.get_max_len()),
+                Some(TypeSignature::BufferType(buffer_length)) => u64::from(u32::from(buffer_length)),
+                _ => return None
+            };
+            iterations = Some(iterations.map_or(max_len, |current: u64| current.min(max_len)));
+        }
+        iterations
+    }
+
+    fn all_expressions_cost(&mut self, contract_analysis: &ContractAnalysis, expressions: &[SymbolicExpression]) -> CheckResult<u64> {
+        let mut total: u64 = 0;
+        for expr in expressions.iter() {
+            total = total.saturating_add(self.expression_cost(contract_analysis, expr)?);
+        }
+        Ok(total)
+    }
+}
+
+/// Whether evaluating `expr` leaves contract state unchanged: it applies no native which
+///   mutates state, no `contract-call?`, and names no defined function which is not read-only.
+fn is_pure_expression(contract_analysis: &ContractAnalysis, expr: &SymbolicExpression) -> bool {
+    let mut mutations = Vec::new();
+    collect_state_mutations(expr, &mut mutations);
+    mutations.is_empty() && !calls_impure_function(contract_analysis, expr)
+}
+
+fn calls_impure_function(contract_analysis: &ContractAnalysis, expr: &SymbolicExpression) -> bool {
+    if let Some(name) = expr.match_atom() {
+        return match NativeFunctions::lookup_by_name(name) {
+            Some(NativeFunctions::ContractCall) => true,
+            Some(_) => false,
+            None => match contract_analysis.get_function_kind(name) {
+                Some(FunctionKind::ReadOnly) | None => false,
+                Some(_) => true
+            }
+        }
+    }
+    match expr.match_list() {
+        Some(list) => list.iter().any(|sub_expr| calls_impure_function(contract_analysis, sub_expr)),
+        None => false
+    }
+}
+
+/// Collects the outermost expressions in `expr` which occur more than once: the
+///   sub-expressions of a repeated expression are necessarily repeated as well.
+fn collect_repeated_expressions<'c>(expr: &'c SymbolicExpression,
+                                    occurrences: &HashMap<&'c SymbolicExpression, Vec<SymbolicExpression>>,
+                                    repeated: &mut Vec<&'c SymbolicExpression>) {
+    if occurrences.get(expr).map_or(false, |found| found.len() > 1) {
+        if !repeated.contains(&expr) {
+            repeated.push(expr);
+        }
+    } else if let Some(list) = expr.match_list() {
+        for sub_expr in list.iter() {
+            collect_repeated_expressions(sub_expr, occurrences, repeated);
+        }
+    }
+}
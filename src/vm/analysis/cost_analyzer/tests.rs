@@ -0,0 +1,148 @@
+use vm::ast::parse;
+use vm::analysis::{mem_type_check, mem_type_check_with_options, AnalysisOptions, CostTable, ContractAnalysis};
+use vm::analysis::errors::{CheckErrors, CheckWarnings};
+use vm::analysis::cost_analyzer::CostAnalyzer;
+use vm::diagnostic::{Diagnostic, Level};
+use vm::costs::LimitedCostTracker;
+use vm::functions::NativeFunctions;
+use vm::types::QualifiedContractIdentifier;
+
+const CONTRACT: &str =
+    "(define-data-var counter int 0)
+     (define-private (double (x int)) (* x 2))
+     (define-public (bump)
+       (begin (var-set counter (+ (var-get counter) 1))
+              (ok (var-get counter))))
+     (define-read-only (doubled) (map double (list 1 2 3)))
+     (define-read-only (squares (l (list 10 int))) (map (lambda (x) (* x x)) l))
+     (define-read-only (quadruple (x int)) (double (double x)))";
+
+#[test]
+fn test_default_cost_table() {
+    let analysis = mem_type_check(CONTRACT).unwrap().1;
+
+    assert_eq!(analysis.get_function_cost("double"), Some(1));
+    // begin, +, ok, and two reads and a write of a data var.
+    assert_eq!(analysis.get_function_cost("bump"), Some(33));
+    // map and list, and one application of `double` for each of the three elements.
+    assert_eq!(analysis.get_function_cost("doubled"), Some(5));
+    assert_eq!(analysis.get_function_cost("squares"), Some(11));
+    assert_eq!(analysis.get_function_cost("quadruple"), Some(2));
+    assert_eq!(analysis.get_function_cost("counter"), None);
+}
+
+#[test]
+fn test_custom_cost_table() {
+    let mut cost_table = CostTable::empty();
+    cost_table.set_weight(NativeFunctions::Multiply, 5);
+    cost_table.set_weight(NativeFunctions::SetVar, 100);
+    cost_table.set_weight(NativeFunctions::FetchVar, 1);
+
+    let mut options = AnalysisOptions::default();
+    options.cost_table = cost_table;
+    let analysis = mem_type_check_with_options(CONTRACT, options).unwrap().1;
+
+    assert_eq!(analysis.get_function_cost("double"), Some(5));
+    assert_eq!(analysis.get_function_cost("bump"), Some(102));
+    assert_eq!(analysis.get_function_cost("doubled"), Some(15));
+    assert_eq!(analysis.get_function_cost("squares"), Some(50));
+    assert_eq!(analysis.get_function_cost("quadruple"), Some(10));
+}
+
+#[test]
+fn test_bounded_nested_sequence_operations() {
+    let contract = "(define-private (double (x int)) (* x 2))
+                    (define-private (double-all (items (list 4 int))) (map double items))
+                    (define-read-only (double-rows (rows (list 3 (list 4 int)))) (map double-all rows))
+                    (define-read-only (sum-rows (rows (list 3 (list 4 int))))
+                      (map (lambda (row) (fold + row 0)) rows))";
+    let analysis = mem_type_check(contract).unwrap().1;
+
+    // map, and `double-all` for each of the 3 rows, which maps `double` over 4 items.
+    assert_eq!(analysis.get_function_cost("double-rows"), Some(1 + 3 * (1 + 4)));
+    assert_eq!(analysis.get_function_cost("sum-rows"), Some(1 + 3 * (1 + 4)));
+}
+
+#[test]
+fn test_unbounded_nested_sequence_operations() {
+    // without the type checker's inferred types, no sequence has a static length bound.
+    fn run_untyped(contract: &str) -> Result<(), CheckErrors> {
+        let contract_identifier = QualifiedContractIdentifier::transient();
+        let expressions = parse(&contract_identifier, contract).unwrap();
+        let mut analysis = ContractAnalysis::new(contract_identifier, expressions, LimitedCostTracker::new_max_limit());
+        CostAnalyzer::new().run(&mut analysis)
+            .map_err(|e| e.err)
+    }
+
+    let good = ["(define-private (double (x int)) (* x 2))
+                 (define-read-only (double-all (items (list 4 int))) (map double items))"];
+    let bad = ["(define-private (double (x int)) (* x 2))
+                (define-private (double-all (items (list 4 int))) (map double items))
+                (define-read-only (double-rows (rows (list 3 (list 4 int)))) (map double-all rows))",
+               "(define-read-only (sum-rows (rows (list 3 (list 4 int))))
+                  (map (lambda (row) (fold + row 0)) rows))",
+               "(define-private (double (x int)) (* x 2))
+                (define-private (double-all (items (list 4 int))) (map double items))
+                (define-private (double-pair (x int)) (double-all (list x x)))
+                (define-read-only (double-pairs (items (list 3 int))) (map double-pair items))"];
+
+    for good_test in good.iter() {
+        run_untyped(good_test).unwrap();
+    }
+
+    for bad_test in bad.iter() {
+        assert_eq!(run_untyped(bad_test).unwrap_err(), CheckErrors::UnboundedSequenceOperation);
+    }
+}
+
+#[test]
+fn test_repeated_expressions() {
+    fn lint(contract: &str, cost_table: CostTable) -> Vec<Diagnostic> {
+        let options = AnalysisOptions { lint: true, cost_table, ..AnalysisOptions::default() };
+        mem_type_check_with_options(contract, options).unwrap().1.diagnostics
+    }
+
+    let test = "(define-read-only (abs (x int)) (if (< x 0) (- 0 x) x))
+                (define-read-only (spread (xs (list 10 int)))
+                  (- (fold + (map abs xs) 0)
+                     (/ (fold + (map abs xs) 0) (to-int (len xs)))))";
+    let diagnostics = lint(test, CostTable::default());
+    // only the outermost repeated expression is reported, not the repeated `map`
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, Level::Warning);
+    assert_eq!(diagnostics[0].message, Diagnostic::warn(&CheckWarnings::RepeatedExpression(2)).message);
+    assert_eq!(diagnostics[0].spans.len(), 2);
+    assert_eq!(diagnostics[0].spans[0].start_line, 3);
+    assert_eq!(diagnostics[0].spans[1].start_line, 4);
+    assert!(mem_type_check(test).unwrap().1.diagnostics.is_empty());
+
+    // the same expressions are cheap if the natives they apply weigh nothing
+    assert!(lint(test, CostTable::empty()).is_empty());
+
+    let not_reported = [
+        // cheap expressions are not worth binding
+        "(define-read-only (f (x int)) (+ (* x 2) (* x 2)))",
+        // a single fold
+        "(define-read-only (f (xs (list 10 int))) (fold + xs 0))",
+        // expressions which mutate state must be computed each time
+        "(define-data-var n int 0)
+         (define-private (bump (x int)) (begin (var-set n x) x))
+         (define-public (f (xs (list 10 int)))
+           (ok (+ (fold + (map bump xs) 0) (fold + (map bump xs) 0))))",
+        "(define-map seen ((x int)) ((count int)))
+         (define-public (f (xs (list 10 int)))
+           (ok (+ (len (filter (lambda (x) (is-none (map-set-get seen (tuple (x x)) (tuple (count 1))))) xs))
+                  (len (filter (lambda (x) (is-none (map-set-get seen (tuple (x x)) (tuple (count 1))))) xs)))))",
+        // as may any private function, even one which only computes
+        "(define-private (abs (x int)) (if (< x 0) (- 0 x) x))
+         (define-read-only (f (xs (list 10 int)))
+           (+ (fold + (map abs xs) 0) (fold + (map abs xs) 0)))",
+        // and any call of another contract
+        "(define-trait getter ((get-value (uint) (response uint uint))))
+         (define-public (f (source <getter>))
+           (ok (+ (unwrap-panic (contract-call? source get-value u1))
+                  (unwrap-panic (contract-call? source get-value u1)))))"];
+    for test in not_reported.iter() {
+        assert!(lint(test, CostTable::default()).is_empty(), "{}", test);
+    }
+}
@@ -2,6 +2,7 @@ use vm::representations::SymbolicExpression;
 use vm::diagnostic::{Diagnostic, DiagnosableError};
 use vm::types::{TypeSignature, TupleTypeSignature, Value};
 use vm::costs::{ExecutionCost, CostErrors};
+use vm::analysis::type_checker::contexts::closest_name;
 use std::error;
 use std::fmt;
 
@@ -27,11 +28,19 @@ pub enum CheckErrors {
     UnknownListConstructionFailure,
     ListTypesMustMatch,
     ConstructedListTooLarge,
+    ListDimensionTooHigh(u8),
+
+    // constant arithmetic errors
+    ArithmeticOverflow,
+    DivideByZero,
 
     // simple type expectation mismatch
     TypeError(TypeSignature, TypeSignature),
+    // the index of the first `is-eq` argument which cannot be compared to the preceding ones
+    EqualsArgumentTypeMismatch(usize, TypeSignature, TypeSignature),
     TypeLiteralError(TypeSignature, TypeSignature),
     TypeValueError(TypeSignature, Value),
+    BufferTooLargeForInt(u32),
 
     NoSuperType(TypeSignature, TypeSignature),
     InvalidTypeDescription,
@@ -43,6 +52,7 @@ pub enum CheckErrors {
 
     ExpectedLiteral,
     ExpectedOptionalType(TypeSignature),
+    ExpectedNestedOptionalType(TypeSignature),
     ExpectedResponseType(TypeSignature),
     ExpectedOptionalOrResponseType(TypeSignature),
     ExpectedOptionalValue(Value),
@@ -52,6 +62,7 @@ pub enum CheckErrors {
     CouldNotDetermineResponseErrType,
 
     CouldNotDetermineMatchTypes,
+    CouldNotDetermineType,
 
     // Checker runtime failures
     TypeAlreadyAnnotatedFailure,
@@ -98,9 +109,11 @@ pub enum CheckErrors {
     // contract-call errors
     NoSuchContract(String),
     NoSuchPublicFunction(String, String),
+    ContractCallPrivateFunction(String, String),
     PublicFunctionNotReadOnly(String, String),
     ContractAlreadyExists(String),
     ContractCallExpectName,
+    ContractCallArgumentTypeError(String, usize, TypeSignature, TypeSignature),
 
     // get-block-info? errors
     NoSuchBlockInfoProperty(String),
@@ -113,9 +126,13 @@ pub enum CheckErrors {
     ExpectedListApplication,
     ExpectedListOrBuffer(TypeSignature),
     MaxLengthOverflow,
+    UnboundedSequenceOperation,
 
     // let syntax
     BadLetSyntax,
+    BadLambdaSyntax,
+    EscapingBinding(String),
+    TooManyBindings(u32, u32),
 
     // generic binding syntax
     BadSyntaxBinding,
@@ -150,6 +167,20 @@ pub enum CheckErrors {
     AtBlockClosureMustBeReadOnly
 }
 
+/// Findings of the opt-in lints. These never fail an analysis, and are
+///   reported as warning diagnostics on the `ContractAnalysis`.
+#[derive(Debug, PartialEq)]
+pub enum CheckWarnings {
+    ConstantIfCondition(bool),
+    DegenerateArgumentType(String, TypeSignature),
+    ConstantComparison(bool),
+    MutationInCallback(Option<String>),
+    IdenticalIfArms,
+    RepeatedExpression(usize),
+    NonPositiveMintAmount,
+    NegativeAssetIdentifier(i128),
+}
+
 #[derive(Debug, PartialEq)]
 pub struct CheckError {
     pub err: CheckErrors,
@@ -167,6 +198,13 @@ impl CheckError {
         }
     }
 
+    /// A `ValueTooLarge` error, whose diagnostic describes which value is too large.
+    pub fn value_too_large(description: String) -> CheckError {
+        let mut error = CheckError::new(CheckErrors::ValueTooLarge);
+        error.diagnostic.message = description;
+        error
+    }
+
     pub fn has_expression(&self) -> bool {
         self.expressions.is_some()
     }
@@ -292,12 +330,18 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::UnknownListConstructionFailure => format!("invalid syntax for list definition"),
             CheckErrors::ListTypesMustMatch => format!("expecting elements of same type in a list"),
             CheckErrors::ConstructedListTooLarge => format!("reached limit of elements in a list"),
+            CheckErrors::ListDimensionTooHigh(dimension) => format!("constructed a list nested {} levels deep, which exceeds the maximum list dimension", dimension),
+            CheckErrors::ArithmeticOverflow => format!("arithmetic on constant operands overflows the integer bounds"),
+            CheckErrors::DivideByZero => format!("detected a division by zero on constant operands"),
             CheckErrors::TypeError(expected_type, found_type) => format!("expecting expression of type '{}', found '{}'", expected_type, found_type),
+            CheckErrors::EqualsArgumentTypeMismatch(index, found_type, expected_type) => format!("argument {} of is-eq has type '{}', which cannot be compared to the preceding arguments of type '{}'", index, found_type, expected_type),
             CheckErrors::TypeLiteralError(expected_type, found_type) => format!("expecting a literal of type '{}', found '{}'", expected_type, found_type),
             CheckErrors::TypeValueError(expected_type, found_value) => format!("expecting expression of type '{}', found '{}'", expected_type, found_value),
+            CheckErrors::BufferTooLargeForInt(found_len) => format!("expecting a buffer of at most 16 bytes to convert to an integer, found a buffer of {} bytes", found_len),
             CheckErrors::UnionTypeError(expected_types, found_type) => format!("expecting expression of type {}, found '{}'", formatted_expected_types(expected_types), found_type),
             CheckErrors::UnionTypeValueError(expected_types, found_type) => format!("expecting expression of type {}, found '{}'", formatted_expected_types(expected_types), found_type),
             CheckErrors::ExpectedOptionalType(found_type) => format!("expecting expression of type 'optional', found '{}'", found_type),
+            CheckErrors::ExpectedNestedOptionalType(found_type) => format!("expecting expression of type '(optional (optional ...))', found '{}'", found_type),
             CheckErrors::ExpectedOptionalOrResponseType(found_type) => format!("expecting expression of type 'optional' or 'response', found '{}'", found_type),
             CheckErrors::ExpectedOptionalOrResponseValue(found_type) =>  format!("expecting expression of type 'optional' or 'response', found '{}'", found_type),
             CheckErrors::ExpectedResponseType(found_type) => format!("expecting expression of type 'response', found '{}'", found_type),
@@ -305,6 +349,7 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::ExpectedResponseValue(found_type) => format!("expecting expression of type 'response', found '{}'", found_type),
             CheckErrors::CouldNotDetermineResponseOkType => format!("attempted to obtain 'ok' value from response, but 'ok' type is indeterminate"),
             CheckErrors::CouldNotDetermineResponseErrType => format!("attempted to obtain 'err' value from response, but 'err' type is indeterminate"),
+            CheckErrors::CouldNotDetermineType => "could not determine the type of the expression, annotate it (e.g., with a typed binding, or a `u` prefix on an unsigned integer literal)".into(),
             CheckErrors::CouldNotDetermineMatchTypes => format!("attempted to match on an (optional) or (response) type where either the some, ok, or err type is indeterminate. you may wish to use unwrap-panic or unwrap-err-panic instead."),
             CheckErrors::BadTupleFieldName => format!("invalid tuple field name"),
             CheckErrors::ExpectedTuple(type_signature) => format!("expecting tuple, found '{}'", type_signature),
@@ -326,18 +371,24 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::ReturnTypesMustMatch(type_1, type_2) => format!("detected two execution paths, returning two different expression types (got '{}' and '{}')", type_1, type_2),
             CheckErrors::NoSuchContract(contract_identifier) => format!("use of unresolved contract '{}'", contract_identifier),
             CheckErrors::NoSuchPublicFunction(contract_identifier, function_name) => format!("contract '{}' has no public function '{}'", contract_identifier, function_name),
+            CheckErrors::ContractCallPrivateFunction(contract_identifier, function_name) => format!("function '{}' in contract '{}' is private, and cannot be called from another contract", function_name, contract_identifier),
             CheckErrors::PublicFunctionNotReadOnly(contract_identifier, function_name) => format!("function '{}' in '{}' is not read-only", contract_identifier, function_name),
             CheckErrors::ContractAlreadyExists(contract_identifier) => format!("contract name '{}' conflicts with existing contract", contract_identifier),
             CheckErrors::ContractCallExpectName => format!("missing contract name for call"),
+            CheckErrors::ContractCallArgumentTypeError(function_name, position, expected_type, found_type) => format!("argument {} of call to '{}' expects type '{}', found '{}'", position, function_name, expected_type, found_type),
             CheckErrors::NoSuchBlockInfoProperty(property_name) => format!("use of block unknown property '{}'", property_name),
             CheckErrors::GetBlockInfoExpectPropertyName => format!("missing property name for block info introspection"),
             CheckErrors::NameAlreadyUsed(name) => format!("defining '{}' conflicts with previous value", name),
             CheckErrors::NonFunctionApplication => format!("expecting expression of type function"),
             CheckErrors::ExpectedListApplication => format!("expecting expression of type list"),
             CheckErrors::ExpectedListOrBuffer(found_type) => format!("expecting expression of type 'list' or 'buff', found '{}'", found_type),
+            CheckErrors::UnboundedSequenceOperation => format!("'map', 'filter' or 'fold' is nested in the function of another, but iterates over a sequence with no maximum length"),
             CheckErrors::MaxLengthOverflow => format!("expecting a value <= {}", u32::max_value()),
             CheckErrors::BadLetSyntax => format!("invalid syntax of 'let'"),
-            CheckErrors::CircularReference(function_names) => format!("detected interdependent functions ({})", function_names.join(", ")),
+            CheckErrors::BadLambdaSyntax => format!("invalid syntax of 'lambda'"),
+            CheckErrors::EscapingBinding(name) => format!("binding '{}' escapes its 'let' through a returned lambda", name),
+            CheckErrors::TooManyBindings(limit, found) => format!("'let' may bind at most {} names, found {}", limit, found),
+            CheckErrors::CircularReference(function_names) => format!("detected interdependent functions ({}): recursion is not allowed, since a cycle of calls may never return", function_names.join(", ")),
             CheckErrors::BadSyntaxBinding => format!("invalid syntax binding"),
             CheckErrors::MaxContextDepthReached => format!("reached depth limit"),
             CheckErrors::UndefinedVariable(var_name) => format!("use of unresolved variable '{}'", var_name),
@@ -376,8 +427,39 @@ impl DiagnosableError for CheckErrors {
         match &self {
             CheckErrors::BadSyntaxBinding => Some(format!("binding syntax example: ((supply int) (ttl int))")),
             CheckErrors::BadLetSyntax => Some(format!("'let' syntax example: (let ((supply 1000) (ttl 60)) <next-expression>)")),
+            CheckErrors::BadLambdaSyntax => Some(format!("'lambda' syntax example: (map (lambda (x) (+ x 1)) (list 1 2 3))")),
             CheckErrors::TraitReferenceUnknown(_) => Some(format!("traits should be either defined, with define-trait, or imported, with use-trait.")),
             CheckErrors::NoSuchBlockInfoProperty(_) => Some(format!("properties available: time, header-hash, burnchain-header-hash, vrf-seed")),
+            CheckErrors::ExpectedTuple(TypeSignature::ResponseType(_)) => Some(format!("a response must be unwrapped before its tuple can be read, e.g., with unwrap! or match")),
+            CheckErrors::NoSuchTupleField(field_name, tuple_signature) => closest_name(field_name, tuple_signature.get_type_map().keys())
+                .map(|closest| format!("did you mean '{}'?", closest)),
+            _ => None
+        }
+    }
+}
+
+impl DiagnosableError for CheckWarnings {
+
+    fn message(&self) -> String {
+        match &self {
+            CheckWarnings::ConstantIfCondition(condition) => format!("'if' condition is always {}, the {} branch is unreachable",
+                                                                     condition, if *condition { "else" } else { "then" }),
+            CheckWarnings::DegenerateArgumentType(arg_name, arg_type) => format!("argument '{}' is declared as '{}', which contains a zero-length sequence and cannot receive meaningful input", arg_name, arg_type),
+            CheckWarnings::ConstantComparison(outcome) => format!("comparison of constant operands is always {}", outcome),
+            CheckWarnings::MutationInCallback(Some(function_name)) => format!("function '{}' is applied by 'map', 'filter' or 'fold', but mutates contract state", function_name),
+            CheckWarnings::MutationInCallback(None) => format!("the lambda applied by 'map', 'filter' or 'fold' mutates contract state"),
+            CheckWarnings::IdenticalIfArms => format!("both arms of 'if' are identical, so its condition has no effect"),
+            CheckWarnings::RepeatedExpression(count) => format!("this expression is computed {} times", count),
+            CheckWarnings::NonPositiveMintAmount => format!("'ft-mint?' of a zero amount always returns (err u1)"),
+            CheckWarnings::NegativeAssetIdentifier(id) => format!("'nft-mint?' of the negative asset identifier {}", id),
+        }
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        match &self {
+            CheckWarnings::IdenticalIfArms => Some(format!("check whether one of the arms is a mistake, or replace the 'if' with its arm")),
+            CheckWarnings::RepeatedExpression(_) => Some(format!("bind its value once with 'let'")),
+            CheckWarnings::NegativeAssetIdentifier(_) => Some(format!("check the sign of the identifier, or define the asset with a 'uint' identifier type")),
             _ => None
         }
     }
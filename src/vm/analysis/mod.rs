@@ -5,12 +5,14 @@ pub mod type_checker;
 pub mod read_only_checker;
 pub mod analysis_db;
 pub mod contract_interface_builder;
+pub mod cost_analyzer;
 
-pub use self::types::{ContractAnalysis, AnalysisPass};
+pub use self::types::{ContractAnalysis, AnalysisPass, AnalysisOptions, CustomNatives, BlockInfoRegistry, TypeTrace, FunctionKind, CostTable};
 use vm::representations::{SymbolicExpression};
 use vm::types::{TypeSignature, QualifiedContractIdentifier};
 use vm::costs::LimitedCostTracker;
 use vm::database::STORE_CONTRACT_SRC_INTERFACE;
+use vm::diagnostic::Diagnostic;
 
 pub use self::errors::{CheckResult, CheckError, CheckErrors};
 pub use self::analysis_db::{AnalysisDatabase};
@@ -19,15 +21,22 @@ use self::read_only_checker::ReadOnlyChecker;
 use self::trait_checker::TraitChecker;
 use self::type_checker::TypeChecker;
 use self::contract_interface_builder::build_contract_interface;
+use self::cost_analyzer::CostAnalyzer;
 
 pub fn mem_type_check(snippet: &str) -> CheckResult<(Option<TypeSignature>, ContractAnalysis)> {
+    mem_type_check_with_options(snippet, AnalysisOptions::default())
+}
+
+pub fn mem_type_check_with_options(snippet: &str, options: AnalysisOptions) -> CheckResult<(Option<TypeSignature>, ContractAnalysis)> {
     use vm::database::MemoryBackingStore;
     use vm::ast::parse;
     let contract_identifier = QualifiedContractIdentifier::transient();
     let mut contract = parse(&contract_identifier, snippet).unwrap();
     let mut marf = MemoryBackingStore::new();
     let mut analysis_db = marf.as_analysis_db();
-    type_check(&QualifiedContractIdentifier::transient(), &mut contract, &mut analysis_db, false)
+    run_analysis_with_options(&contract_identifier, &mut contract, &mut analysis_db, false,
+                              LimitedCostTracker::new_max_limit(), options)
+        .map_err(|(e, _cost_tracker)| e)
         .map(|x| {
              // return the first type result of the type checker
              let first_type = x.type_map.as_ref().unwrap()
@@ -50,26 +59,64 @@ pub fn run_analysis(contract_identifier: &QualifiedContractIdentifier,
                     analysis_db: &mut AnalysisDatabase, 
                     save_contract: bool,
                     cost_tracker: LimitedCostTracker) -> Result<ContractAnalysis, (CheckError, LimitedCostTracker)> {
+    run_analysis_with_options(contract_identifier, expressions, analysis_db, save_contract, cost_tracker,
+                              AnalysisOptions::default())
+}
+
+pub fn run_analysis_with_options(contract_identifier: &QualifiedContractIdentifier, 
+                                 expressions: &mut [SymbolicExpression],
+                                 analysis_db: &mut AnalysisDatabase, 
+                                 save_contract: bool,
+                                 cost_tracker: LimitedCostTracker,
+                                 options: AnalysisOptions) -> Result<ContractAnalysis, (CheckError, LimitedCostTracker)> {
     let mut contract_analysis = ContractAnalysis::new(contract_identifier.clone(), expressions.to_vec(), cost_tracker);
-    let result = analysis_db.execute(|db| {
-        ReadOnlyChecker::run_pass(&mut contract_analysis, db)?;
-        TypeChecker::run_pass(&mut contract_analysis, db)?;
-        TraitChecker::run_pass(&mut contract_analysis, db)?;
-        if STORE_CONTRACT_SRC_INTERFACE {
-            let interface = build_contract_interface(&contract_analysis);
-            contract_analysis.contract_interface = Some(interface);
-        }
-        if save_contract {
-            db.insert_contract(&contract_identifier, &contract_analysis)?;
-        }
-        Ok(())
-    });
+    contract_analysis.analysis_options = options;
+    let result = analysis_db.execute(|db| run_passes(contract_identifier, &mut contract_analysis, db, save_contract));
     match result {
         Ok(_) => Ok(contract_analysis),
         Err(e) => Err((e, contract_analysis.take_contract_cost_tracker()))
     }
 }
 
+/// Runs the analysis passes without saving the contract, and returns every diagnostic
+///   reported: the warnings found before any error, followed by that error, which
+///   halted the analysis.
+pub fn run_analysis_diagnostics(contract_identifier: &QualifiedContractIdentifier,
+                                expressions: &mut [SymbolicExpression],
+                                analysis_db: &mut AnalysisDatabase,
+                                cost_tracker: LimitedCostTracker,
+                                options: AnalysisOptions) -> Vec<Diagnostic> {
+    let mut contract_analysis = ContractAnalysis::new(contract_identifier.clone(), expressions.to_vec(), cost_tracker);
+    contract_analysis.analysis_options = options;
+    let result = analysis_db.execute(|db| run_passes(contract_identifier, &mut contract_analysis, db, false));
+    let mut diagnostics = contract_analysis.diagnostics;
+    if let Err(e) = result {
+        // with `continue_on_error`, the halting error has already been reported.
+        if !diagnostics.contains(&e.diagnostic) {
+            diagnostics.push(e.diagnostic);
+        }
+    }
+    diagnostics
+}
+
+fn run_passes(contract_identifier: &QualifiedContractIdentifier,
+              contract_analysis: &mut ContractAnalysis,
+              db: &mut AnalysisDatabase,
+              save_contract: bool) -> CheckResult<()> {
+    ReadOnlyChecker::run_pass(contract_analysis, db)?;
+    TypeChecker::run_pass(contract_analysis, db)?;
+    TraitChecker::run_pass(contract_analysis, db)?;
+    CostAnalyzer::run_pass(contract_analysis, db)?;
+    if STORE_CONTRACT_SRC_INTERFACE {
+        let interface = build_contract_interface(contract_analysis);
+        contract_analysis.contract_interface = Some(interface);
+    }
+    if save_contract {
+        db.insert_contract(contract_identifier, contract_analysis)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -2,6 +2,7 @@ use vm::representations::{SymbolicExpressionType, SymbolicExpression, ClarityNam
 use vm::representations::SymbolicExpressionType::{AtomValue, Atom, List, LiteralValue, TraitReference, Field};
 use vm::types::{TypeSignature, TupleTypeSignature, Value, PrincipalData, parse_name_type_pairs};
 use vm::functions::NativeFunctions;
+use vm::functions::iterables::Lambda;
 use vm::functions::define::DefineFunctionsParsed;
 use vm::functions::tuples;
 use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
@@ -41,6 +42,12 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
 
     pub fn run(& mut self, contract_analysis: &mut ContractAnalysis) -> CheckResult<()> {
 
+        // custom natives are only checked by the type checker: their registration
+        //   states whether they are read-only.
+        for (name, read_only) in contract_analysis.analysis_options.custom_natives.read_only_flags() {
+            self.defined_functions.insert(name.clone(), read_only);
+        }
+
         for exp in contract_analysis.expressions.iter() {
             let mut result = self.check_reads_only_valid(&exp);
             if let Err(ref mut error) = result {
@@ -148,6 +155,13 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
         Ok(true)
     }
 
+    /// An inline lambda is read-only if its body and the sequence arguments it is applied over are.
+    fn is_lambda_application_read_only(&mut self, lambda: &Lambda, args: &[SymbolicExpression]) -> CheckResult<bool> {
+        let body_read_only = self.check_read_only(lambda.body)?;
+        let args_read_only = self.check_all_read_only(args)?;
+        Ok(body_read_only && args_read_only)
+    }
+
     fn try_native_function_check(&mut self, function: &str, args: &[SymbolicExpression]) -> Option<CheckResult<bool>> {
         NativeFunctions::lookup_by_name(function).map(|function| {
             self.check_native_function(&function, args)
@@ -158,15 +172,30 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
         use vm::functions::NativeFunctions::*;
 
         match function {
-            Add | Subtract | Divide | Multiply | CmpGeq | CmpLeq | CmpLess | CmpGreater |
-            Modulo | Power | BitwiseXOR | And | Or | Not | Hash160 | Sha256 | Keccak256 | Equals | If |
-            Sha512 | Sha512Trunc256 |
-            ConsSome | ConsOkay | ConsError | DefaultTo | UnwrapRet | UnwrapErrRet | IsOkay | IsNone | Asserts |
+            Add | Subtract | Divide | Multiply | CheckedAdd | CheckedSub | CheckedMul | CmpGeq | CmpLeq | CmpLess | CmpGreater |
+            Modulo | Power | BitwiseXOR | And | Or | Not | Hash160 | Sha256 | Keccak256 | Ripemd160 | Equals | If |
+            Sha512 | Sha512Trunc256 | HashPair | Secp256k1Recover | Secp256k1Verify | ToConsensusBuff | PrincipalDestruct | PrincipalConstruct |
+            ConsSome | ConsOkay | ConsError | DefaultTo | UnwrapRet | UnwrapErrRet | IsOkay | IsNone | GetOk | GetErr | Flatten | Asserts |
             Unwrap | UnwrapErr | Match | IsErr | IsSome | TryRet |
-            ToUInt | ToInt | Append | Concat | AsMaxLen |
-            ListCons | GetBlockInfo | TupleGet | Len | Print | AsContract | Begin | FetchVar | GetTokenBalance | GetAssetOwner => {
+            ToUInt | ToInt | BuffToUIntLe | BuffToUIntBe | Append | Concat | AsMaxLen |
+            ListCons | GetBlockInfo | TupleGet | TupleGetAt | Len | Print | TestAssert | AsContract | Begin | FetchVar | GetTokenBalance | GetTokenSupply | GetAssetOwner | StxGetAccount => {
                 self.check_all_read_only(args)
             },
+            PrintEvent => {
+                check_argument_count(2, args)?;
+                // the first argument is the topic, a literal.
+                self.check_read_only(&args[1])
+            },
+            FromConsensusBuff => {
+                check_argument_count(2, args)?;
+                // the first argument is a type, not an expression.
+                self.check_read_only(&args[1])
+            },
+            TupleGetIn => {
+                check_argument_count(2, args)?;
+                // the second argument is a path of field names, not an expression.
+                self.check_read_only(&args[0])
+            },
             AtBlock => {
                 check_argument_count(2, args)?;
                 
@@ -190,8 +219,21 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
                 };
                 res
             },
+            FetchContractEntry => {
+                check_argument_count(3, args)?;
+
+                let res = match tuples::get_definition_type_of_tuple_argument(&args[2]) {
+                    Implicit(ref tuple_expr) => {
+                        self.is_implicit_tuple_definition_read_only(tuple_expr)
+                    },
+                    Explicit => {
+                        self.check_read_only(&args[2])
+                    }
+                };
+                res
+            },
             StxTransfer | StxBurn |
-            SetEntry | DeleteEntry | InsertEntry | SetVar | MintAsset | MintToken | TransferAsset | TransferToken => {
+            SetEntry | DeleteEntry | InsertEntry | SetGetEntry | SetVar | MintAsset | MintToken | TransferAsset | TransferToken => {
                 Ok(false)
             },
             Let => {
@@ -216,6 +258,10 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
             },
             Map | Filter => {
                 check_argument_count(2, args)?;
+
+                if let Some(lambda) = Lambda::parse(&args[0])? {
+                    return self.is_lambda_application_read_only(&lambda, &args[1..])
+                }
     
                 // note -- we do _not_ check here to make sure we're not mapping on
                 //      a special function. that check is performed by the type checker.
@@ -227,6 +273,10 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
             },
             Fold => {
                 check_argument_count(3, args)?;
+
+                if let Some(lambda) = Lambda::parse(&args[0])? {
+                    return self.is_lambda_application_read_only(&lambda, &args[1..])
+                }
     
                 // note -- we do _not_ check here to make sure we're not folding on
                 //      a special function. that check is performed by the type checker.
@@ -240,11 +290,12 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
                 for pair in args.iter() {
                     let pair_expression = pair.match_list()
                         .ok_or(CheckErrors::TupleExpectsPairs)?;
-                    if pair_expression.len() != 2 {
+                    // a punned field, `(a)`, is its own value
+                    if pair_expression.len() != 1 && pair_expression.len() != 2 {
                         return Err(CheckErrors::TupleExpectsPairs.into())
                     }
 
-                    if !self.check_read_only(&pair_expression[1])? {
+                    if !self.check_read_only(&pair_expression[pair_expression.len() - 1])? {
                         return Ok(false)
                     }
                 }
@@ -25,6 +25,13 @@ fn test_type_error() {
     assert!(format!("{}", err.diagnostic).contains("expecting expression of type 'int', found 'bool'"));
 }
 
+#[test]
+fn test_equals_argument_index() {
+    let snippet = "(is-eq 1 2 true)";
+    let err = mem_type_check(snippet).unwrap_err();
+    assert!(format!("{}", err.diagnostic).contains("argument 2 of is-eq has type 'bool'"));
+}
+
 #[test]
 fn test_union_type_error() {
     let snippet = "(hash160 true)";
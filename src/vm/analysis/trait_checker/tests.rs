@@ -298,6 +298,51 @@ fn test_cycle_in_traits_1_contract() {
     }
 }
 
+#[test]
+fn test_define_trait_duplicate_method() {
+    let contract_src =
+        "(define-trait trait-1 (
+            (get-1 (uint) (response uint uint))
+            (get-1 (int) (response int uint))))";
+
+    let contract_id = QualifiedContractIdentifier::local("trait-contract").unwrap();
+    let mut contract = parse(&contract_id, contract_src).unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let err = db.execute(|db| type_check(&contract_id, &mut contract, db, true)).unwrap_err();
+    assert_eq!(err.err, CheckErrors::NameAlreadyUsed("get-1".to_string()));
+}
+
+#[test]
+fn test_define_trait_malformed_signatures() {
+    let bad = [
+        // a method's arguments must be a list of types
+        "(define-trait trait-1 ((get-1 uint (response uint uint))))",
+        // a method must return a response
+        "(define-trait trait-1 ((get-1 (uint) uint)))",
+        // a method's name must be an atom
+        "(define-trait trait-1 ((u1 (uint) (response uint uint))))",
+    ];
+
+    let contract_id = QualifiedContractIdentifier::local("trait-contract").unwrap();
+    for contract_src in bad.iter() {
+        let mut contract = parse(&contract_id, contract_src).unwrap();
+        let mut marf = MemoryBackingStore::new();
+        let mut db = marf.as_analysis_db();
+
+        let err = db.execute(|db| type_check(&contract_id, &mut contract, db, true)).unwrap_err();
+        assert_eq!(err.err, CheckErrors::DefineTraitBadSignature, "{}", contract_src);
+    }
+
+    // argument types which fail to parse report the type error
+    let mut contract = parse(&contract_id, "(define-trait trait-1 ((get-1 (not-a-type) (response uint uint))))").unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let err = db.execute(|db| type_check(&contract_id, &mut contract, db, true)).unwrap_err();
+    assert_eq!(err.err, CheckErrors::UnknownTypeName("not-a-type".to_string()));
+}
+
 #[test]
 fn test_cycle_in_traits_2_contracts() {
     let dispatching_contract_src =
@@ -667,7 +712,7 @@ fn test_dynamic_dispatch_mismatched_args() {
         type_check(&target_contract_id, &mut target_contract, db, true)
     }).unwrap_err();
     match err.err {
-        CheckErrors::TypeError(_, _) => {},
+        CheckErrors::ContractCallArgumentTypeError(_, 1, TypeSignature::IntType, TypeSignature::UIntType) => {},
         _ => {
             panic!("{:?}", err)
         }
@@ -1,12 +1,13 @@
-use std::collections::{HashMap, BTreeMap, HashSet};
+use std::collections::{HashMap, BTreeMap, BTreeSet, HashSet};
 use vm::representations::{SymbolicExpression, ClarityName};
-use vm::types::{TypeSignature, FunctionType, TraitIdentifier};
+use vm::types::{TypeSignature, FunctionType, TraitIdentifier, QualifiedContractIdentifier};
 use vm::types::signatures::{FunctionSignature};
 
 use vm::contexts::MAX_CONTEXT_DEPTH;
 
 use vm::analysis::errors::{CheckResult, CheckError, CheckErrors};
-use vm::analysis::types::{ContractAnalysis};
+use vm::analysis::types::{ContractAnalysis, FunctionKind};
+use vm::functions::NativeFunctions;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct TypeMap {
@@ -31,6 +32,41 @@ pub struct ContractContext {
     non_fungible_tokens: HashMap<ClarityName, TypeSignature>,
     traits: HashMap<ClarityName, BTreeMap<ClarityName, FunctionSignature>>,
     pub implemented_traits: HashSet<TraitIdentifier>,
+    print_events: Vec<(String, TypeSignature)>,
+    dependencies: BTreeSet<QualifiedContractIdentifier>,
+    used_natives: HashSet<NativeFunctions>,
+}
+
+/// The number of single-character insertions, deletions or substitutions needed
+///   to turn one name into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.bytes().enumerate() {
+        let mut current_row = Vec::with_capacity(b.len() + 1);
+        current_row.push(i + 1);
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + if a_char == *b_char { 0 } else { 1 };
+            let deletion = previous_row[j + 1] + 1;
+            let insertion = current_row[j] + 1;
+            current_row.push(substitution.min(deletion).min(insertion));
+        }
+        previous_row = current_row;
+    }
+    previous_row[b.len()]
+}
+
+/// Finds the candidate closest to a misspelled name, if any is close enough to be the
+///   intended one. Ties are broken alphabetically, so the suggestion is deterministic.
+pub fn closest_name<'a, I: Iterator<Item = &'a ClarityName>>(name: &str, candidates: I) -> Option<&'a ClarityName> {
+    let max_distance = (name.len() / 3).max(1);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by(|(distance_a, name_a), (distance_b, name_b)| {
+            distance_a.cmp(distance_b).then_with(|| name_a.as_str().cmp(name_b.as_str()))
+        })
+        .map(|(_, candidate)| candidate)
 }
 
 impl TypeMap {
@@ -64,14 +100,59 @@ impl ContractContext {
             non_fungible_tokens: HashMap::new(),
             traits: HashMap::new(),
             implemented_traits: HashSet::new(),
+            print_events: Vec::new(),
+            dependencies: BTreeSet::new(),
+            used_natives: HashSet::new(),
+        }
+    }
+
+    /// Rebuilds the context of a contract from its completed analysis, the inverse
+    ///  of `into_contract_analysis`.
+    pub fn from_contract_analysis(contract_analysis: &ContractAnalysis) -> ContractContext {
+        ContractContext {
+            variable_types: contract_analysis.variable_types.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            private_function_types: contract_analysis.private_function_types.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            public_function_types: contract_analysis.public_function_types.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            read_only_function_types: contract_analysis.read_only_function_types.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            map_types: contract_analysis.map_types.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            persisted_variable_types: contract_analysis.persisted_variable_types.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            fungible_tokens: contract_analysis.fungible_tokens.iter().cloned().collect(),
+            non_fungible_tokens: contract_analysis.non_fungible_tokens.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            traits: contract_analysis.defined_traits.iter()
+                .map(|(name, t)| (name.clone(), t.clone())).collect(),
+            implemented_traits: contract_analysis.implemented_traits.iter().cloned().collect(),
+            print_events: contract_analysis.print_events.clone(),
+            dependencies: contract_analysis.dependencies.clone(),
+            used_natives: contract_analysis.used_natives.clone(),
         }
     }
 
+    /// Forgets any definition of `name`, so that it may be defined again.
+    pub fn remove_definition(&mut self, name: &str) {
+        self.variable_types.remove(name);
+        self.private_function_types.remove(name);
+        self.public_function_types.remove(name);
+        self.read_only_function_types.remove(name);
+        self.map_types.remove(name);
+        self.persisted_variable_types.remove(name);
+        self.fungible_tokens.remove(name);
+        self.non_fungible_tokens.remove(name);
+        self.traits.remove(name);
+    }
+
     pub fn check_name_used(&self, name: &str) -> CheckResult<()> {
         if self.variable_types.contains_key(name) ||
             self.persisted_variable_types.contains_key(name) ||
             self.private_function_types.contains_key(name) ||
             self.public_function_types.contains_key(name) ||
+            self.read_only_function_types.contains_key(name) ||
             self.fungible_tokens.contains(name) ||
             self.non_fungible_tokens.contains_key(name) ||
             self.traits.contains_key(name) ||
@@ -153,6 +234,18 @@ impl ContractContext {
         Ok(())
     }
 
+    pub fn add_print_event(&mut self, topic: String, value_type: TypeSignature) {
+        self.print_events.push((topic, value_type));
+    }
+
+    pub fn add_dependency(&mut self, contract_identifier: QualifiedContractIdentifier) {
+        self.dependencies.insert(contract_identifier);
+    }
+
+    pub fn add_used_native(&mut self, native_function: NativeFunctions) {
+        self.used_natives.insert(native_function);
+    }
+
     pub fn get_trait(&self, trait_name: &str) -> Option<&BTreeMap<ClarityName, FunctionSignature>> {
         self.traits.get(trait_name)
     }
@@ -169,13 +262,24 @@ impl ContractContext {
         self.persisted_variable_types.get(name)
     }
 
+    /// Returns the declared data variable whose name is closest to `name`.
+    pub fn closest_persisted_variable(&self, name: &str) -> Option<&ClarityName> {
+        closest_name(name, self.persisted_variable_types.keys())
+    }
+
     pub fn get_function_type(&self, name: &str) -> Option<&FunctionType> {
+        self.get_function(name).map(|(_, f_type)| f_type)
+    }
+
+    /// Returns the kind and type of the function defined as `name`, whichever kind it is.
+    pub fn get_function(&self, name: &str) -> Option<(FunctionKind, &FunctionType)> {
         if let Some(f_type) = self.public_function_types.get(name) {
-            Some(f_type)
+            Some((FunctionKind::Public, f_type))
         } else if let Some(f_type) =  self.private_function_types.get(name){
-            Some(f_type)
+            Some((FunctionKind::Private, f_type))
         } else {
             self.read_only_function_types.get(name)
+                .map(|f_type| (FunctionKind::ReadOnly, f_type))
         }
     }
 
@@ -222,6 +326,18 @@ impl ContractContext {
         for trait_identifier in self.implemented_traits.drain() {
             contract_analysis.add_implemented_trait(trait_identifier);
         }
+
+        for (topic, value_type) in self.print_events.drain(..) {
+            contract_analysis.add_print_event(topic, value_type);
+        }
+
+        for contract_identifier in self.dependencies.into_iter() {
+            contract_analysis.add_dependency(contract_identifier);
+        }
+
+        for native_function in self.used_natives.into_iter() {
+            contract_analysis.add_used_native(native_function);
+        }
     }
 }
 
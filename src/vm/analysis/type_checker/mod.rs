@@ -3,26 +3,27 @@ pub mod contexts;
 pub mod natives;
 
 use std::convert::TryInto;
-use std::collections::{HashMap, BTreeMap};
+use std::collections::{HashMap, HashSet, BTreeMap};
 use vm::representations::{SymbolicExpression, ClarityName, depth_traverse};
 use vm::representations::SymbolicExpressionType::{AtomValue, Atom, List, LiteralValue, TraitReference, Field};
 use vm::types::{TypeSignature, TupleTypeSignature, FunctionArg,
                 FunctionType, FixedFunction, parse_name_type_pairs, Value, PrincipalData};
 use vm::types::signatures::{FunctionSignature};
 use vm::functions::NativeFunctions;
-use vm::functions::define::DefineFunctionsParsed;
+use vm::functions::define::{DefineFunctions, DefineFunctionsParsed};
 use vm::variables::NativeVariables;
+use vm::diagnostic::Diagnostic;
 use vm::costs::{CostTracker, ExecutionCost, LimitedCostTracker, CostErrors,
                 cost_functions, analysis_typecheck_cost, CostOverflowingMath};
 
 use super::AnalysisDatabase;
-pub use super::types::{ContractAnalysis, AnalysisPass};
+pub use super::types::{ContractAnalysis, AnalysisPass, AnalysisOptions, TypeTrace};
 
 use self::contexts::{TypeMap, TypingContext, ContractContext};
 
-pub use self::natives::{TypedNativeFunction, SimpleNativeFunction};
+pub use self::natives::{TypedNativeFunction, SimpleNativeFunction, SpecialNativeFunction};
 
-pub use super::errors::{CheckResult, CheckError, CheckErrors, check_argument_count,
+pub use super::errors::{CheckResult, CheckError, CheckErrors, CheckWarnings, check_argument_count,
                         check_arguments_at_least};
 
 
@@ -51,6 +52,24 @@ pub struct TypeChecker <'a, 'b> {
     function_return_tracker: Option<Option<TypeSignature>>,
     db: &'a mut AnalysisDatabase<'b>,
     pub cost_track: LimitedCostTracker,
+    options: AnalysisOptions,
+    diagnostics: Vec<Diagnostic>,
+    type_trace: Vec<TypeTrace>,
+    // the input of an early return which is allowed to always diverge, see `type_check_arm`
+    diverging_input: Option<u64>,
+    // the defined functions passed to `map`, `filter` or `fold`, see `lint_callback_mutations`
+    iterable_callbacks: HashSet<ClarityName>,
+    // the values of constant-folded native applications by expression id, see `fold_native_application`
+    constant_values: HashMap<u64, Value>,
+    // the types of the data variables looked up so far, see `get_persisted_variable_type`
+    persisted_variable_types: HashMap<ClarityName, TypeSignature>,
+}
+
+/// The result of re-checking a single top-level expression of a contract,
+///   see `TypeChecker::check_definition`.
+pub struct DefinitionAnalysis {
+    pub type_map: TypeMap,
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl CostTracker for TypeChecker<'_, '_> {
@@ -71,7 +90,7 @@ impl CostTracker for TypeChecker<'_, '_> {
 impl AnalysisPass for TypeChecker <'_, '_> {
     fn run_pass(contract_analysis: &mut ContractAnalysis, analysis_db: &mut AnalysisDatabase) -> CheckResult<()> {
         let cost_track = contract_analysis.take_contract_cost_tracker();
-        let mut command = TypeChecker::new(analysis_db, cost_track);
+        let mut command = TypeChecker::new(analysis_db, cost_track, contract_analysis.analysis_options.clone());
         // run the analysis, and replace the cost tracker whether or not the
         //   analysis succeeded.
         match command.run(contract_analysis) {
@@ -81,8 +100,10 @@ impl AnalysisPass for TypeChecker <'_, '_> {
                 Ok(())
             },
             err => {
-                let TypeChecker { cost_track, .. } = command;
+                // the warnings found before the error are still reported.
+                let TypeChecker { cost_track, mut diagnostics, .. } = command;
                 contract_analysis.replace_contract_cost_tracker(cost_track);
+                contract_analysis.diagnostics.append(&mut diagnostics);
                 err
             },
         }
@@ -93,12 +114,18 @@ pub type TypeResult = CheckResult<TypeSignature>;
 
 impl FunctionType {
     pub fn check_args<T: CostTracker>(&self, accounting: &mut T, args: &[TypeSignature]) -> CheckResult<TypeSignature> {
+        self.check_args_with(accounting, args, false)
+    }
+
+    /// Like `check_args`, admitting the arguments as `TypeSignature::admits_type_with`.
+    pub fn check_args_with<T: CostTracker>(&self, accounting: &mut T, args: &[TypeSignature],
+                                           tuple_width_subtyping: bool) -> CheckResult<TypeSignature> {
         match self {
             FunctionType::Variadic(expected_type, return_type) => {
                 check_arguments_at_least(1, args)?;
                 for found_type in args.iter() {
                     analysis_typecheck_cost(accounting, expected_type, found_type)?;
-                    if !expected_type.admits_type(found_type) {
+                    if !expected_type.admits_type_with(found_type, tuple_width_subtyping) {
                         return Err(CheckErrors::TypeError(
                             expected_type.clone(), found_type.clone()).into())
                     }
@@ -109,9 +136,23 @@ impl FunctionType {
                 check_argument_count(arg_types.len(), args)?;
                 for (expected_type, found_type) in arg_types.iter().map(|x| &x.signature).zip(args) {
                     analysis_typecheck_cost(accounting, expected_type, found_type)?;
-                    if !expected_type.admits_type(found_type) {
-                        return Err(CheckErrors::TypeError(
-                            expected_type.clone(), found_type.clone()).into())
+                    if !expected_type.admits_type_with(found_type, tuple_width_subtyping) {
+                        return Err(admission_error(expected_type, found_type.clone()))
+                    }
+                }
+                Ok(returns.clone())
+            },
+            FunctionType::FixedWithOptional(FixedFunction { args: required_types, returns }, optional_types) => {
+                check_arguments_at_least(required_types.len(), args)?;
+                let max_args = required_types.len() + optional_types.len();
+                if args.len() > max_args {
+                    return Err(CheckErrors::IncorrectArgumentCount(max_args, args.len()).into())
+                }
+                // omitted optionals are defaulted, so only the supplied arguments are checked
+                for (expected_type, found_type) in required_types.iter().chain(optional_types).map(|x| &x.signature).zip(args) {
+                    analysis_typecheck_cost(accounting, expected_type, found_type)?;
+                    if !expected_type.admits_type_with(found_type, tuple_width_subtyping) {
+                        return Err(admission_error(expected_type, found_type.clone()))
                     }
                 }
                 Ok(returns.clone())
@@ -121,7 +162,7 @@ impl FunctionType {
                 let found_type = &args[0];
                 for expected_type in arg_types.iter() {
                     analysis_typecheck_cost(accounting, expected_type, found_type)?;
-                    if expected_type.admits_type(found_type) {
+                    if expected_type.admits_type_with(found_type, tuple_width_subtyping) {
                         return  Ok(return_type.clone())
                     }
                 }
@@ -197,34 +238,278 @@ fn type_reserved_variable(variable_name: &str) -> Option<TypeSignature> {
     }
 }
 
+/// Type checks a standalone `(contract-call? ...)` expression against the contracts
+///   stored in `db`, outside of any contract (e.g., for read-only query tooling).
+pub fn type_check_contract_call_expr(db: &mut AnalysisDatabase, expr: &SymbolicExpression) -> TypeResult {
+    let (function_name, args) = expr.match_list()
+        .and_then(|expression| expression.split_first())
+        .ok_or(CheckErrors::NonFunctionApplication)?;
+    let function_name = function_name.match_atom()
+        .ok_or(CheckErrors::NonFunctionApplication)?;
+    match NativeFunctions::lookup_by_name(function_name) {
+        Some(NativeFunctions::ContractCall) => {},
+        _ => return Err(CheckErrors::IllegalOrUnknownFunctionApplication(function_name.to_string()).into())
+    }
+
+    let mut checker = TypeChecker::new(db, LimitedCostTracker::new_max_limit(), AnalysisOptions::default());
+    natives::check_contract_call(&mut checker, args, &TypingContext::new())
+}
+
+/// Type checks a standalone expression against an expected type, outside of any contract
+///   (e.g., for REPLs and test harnesses). The expression may not reference any local bindings.
+pub fn type_check_expr_expects(db: &mut AnalysisDatabase, expr: &SymbolicExpression, expected_type: &TypeSignature) -> CheckResult<()> {
+    let mut checker = TypeChecker::new(db, LimitedCostTracker::new_max_limit(), AnalysisOptions::default());
+    checker.type_check_expects(expr, &TypingContext::new(), expected_type)?;
+    Ok(())
+}
+
+fn is_cost_error(err: &CheckErrors) -> bool {
+    match err {
+        CheckErrors::CostOverflow | CheckErrors::CostBalanceExceeded(_, _) | CheckErrors::MemoryBalanceExceeded(_, _) => true,
+        _ => false
+    }
+}
+
+/// The name bound by a top-level `define-*` expression, if it binds one.
+fn defined_name(expr: &SymbolicExpression) -> Option<&ClarityName> {
+    let definition = expr.match_list()?;
+    if DefineFunctions::lookup_by_name(definition.get(0)?.match_atom()?).is_none() {
+        return None
+    }
+    let signature = definition.get(1)?;
+    signature.match_atom()
+        .or_else(|| signature.match_list()
+                 .and_then(|signature| signature.get(0))
+                 .and_then(|name| name.match_atom()))
+}
+
+/// Collects the applications in `expr` of natives which mutate contract state.
+pub fn collect_state_mutations<'c>(expr: &'c SymbolicExpression, mutations: &mut Vec<&'c SymbolicExpression>) {
+    use vm::functions::NativeFunctions::*;
+
+    if let Some(list) = expr.match_list() {
+        if let Some(function) = list.get(0).and_then(|name| name.match_atom())
+            .and_then(|name| NativeFunctions::lookup_by_name(name)) {
+            match function {
+                SetVar | SetEntry | InsertEntry | SetGetEntry | DeleteEntry | MintAsset | MintToken |
+                TransferAsset | TransferToken | StxTransfer | StxBurn => mutations.push(expr),
+                _ => {}
+            }
+        }
+        for sub_expr in list.iter() {
+            collect_state_mutations(sub_expr, mutations);
+        }
+    }
+}
+
+fn references_any(expr: &SymbolicExpression, names: &HashSet<ClarityName>) -> bool {
+    if let Some(name) = expr.match_atom() {
+        return names.contains(name)
+    }
+    match expr.match_list() {
+        Some(list) => list.iter().any(|sub_expr| references_any(sub_expr, names)),
+        None => false
+    }
+}
+
+/// Constructs the error for `expected_type` not admitting `found_type`. A buffer which only
+///   fails to be admitted because it is too long (possibly as a field of a tuple) is reported
+///   as `ValueTooLarge`, naming its length and the declared maximum, rather than as a
+///   generic type error.
+pub fn admission_error(expected_type: &TypeSignature, found_type: TypeSignature) -> CheckError {
+    match find_oversized_buffer(expected_type, &found_type) {
+        Some((max_len, found_len)) => CheckError::value_too_large(
+            format!("expecting a buffer of at most {} bytes, found a buffer of {} bytes", max_len, found_len)),
+        None => CheckErrors::TypeError(expected_type.clone(), found_type).into()
+    }
+}
+
+/// A `ValueTooLarge` error for a map whose key or value type, as named by `part`, is
+///   greater than the maximum value size.
+fn map_type_too_large(map_name: &str, part: &str) -> CheckError {
+    CheckError::value_too_large(
+        format!("the {} type of map '{}' is greater than the maximum allowed value size", part, map_name))
+}
+
+fn find_oversized_buffer(expected_type: &TypeSignature, found_type: &TypeSignature) -> Option<(u32, u32)> {
+    match (expected_type, found_type) {
+        (TypeSignature::BufferType(max_len), TypeSignature::BufferType(found_len)) => {
+            let (max_len, found_len) = (u32::from(max_len), u32::from(found_len));
+            if found_len > max_len {
+                Some((max_len, found_len))
+            } else {
+                None
+            }
+        },
+        (TypeSignature::TupleType(expected_tuple), TypeSignature::TupleType(found_tuple)) => {
+            let expected_fields = expected_tuple.get_type_map();
+            let found_fields = found_tuple.get_type_map();
+            if expected_fields.len() != found_fields.len() {
+                return None
+            }
+            let mut oversized = None;
+            for (name, expected_field) in expected_fields.iter() {
+                let found_field = found_fields.get(name)?;
+                if !expected_field.admits_type(found_field) {
+                    // every mismatched field must be an oversized buffer
+                    let field_oversized = find_oversized_buffer(expected_field, found_field)?;
+                    oversized.get_or_insert(field_oversized);
+                }
+            }
+            oversized
+        },
+        _ => None
+    }
+}
+
 pub fn no_type() -> TypeSignature {
     TypeSignature::NoType
 }
 
 impl <'a, 'b> TypeChecker <'a, 'b> {
-    fn new(db: &'a mut AnalysisDatabase<'b>, cost_track: LimitedCostTracker) -> TypeChecker<'a, 'b> {
+    fn new(db: &'a mut AnalysisDatabase<'b>, cost_track: LimitedCostTracker, options: AnalysisOptions) -> TypeChecker<'a, 'b> {
         Self {
-            db, cost_track,
+            db, cost_track, options,
             contract_context: ContractContext::new(),
             function_return_tracker: None,
             type_map: TypeMap::new(),
+            diagnostics: Vec::new(),
+            type_trace: Vec::new(),
+            diverging_input: None,
+            iterable_callbacks: HashSet::new(),
+            constant_values: HashMap::new(),
+            persisted_variable_types: HashMap::new(),
         }
     }
 
-    fn into_contract_analysis(self, contract_analysis: &mut ContractAnalysis) -> LimitedCostTracker {
+    fn into_contract_analysis(mut self, contract_analysis: &mut ContractAnalysis) -> LimitedCostTracker {
         self.contract_context.into_contract_analysis(contract_analysis);
         contract_analysis.type_map = Some(self.type_map);
+        contract_analysis.diagnostics.append(&mut self.diagnostics);
+        contract_analysis.type_trace.append(&mut self.type_trace);
         self.cost_track
     }
 
+    /// Type checks a single top-level expression of a contract against the definitions
+    ///  of that contract's completed analysis, without re-checking the rest of the contract.
+    ///  This is intended for tooling (e.g., editors) re-checking one definition per edit.
+    ///
+    /// `contract_analysis` must be the analysis of the contract as it was before the edit.
+    ///  If `expression` defines a name, any previous definition of that name is replaced
+    ///  for the purposes of this check. However, definitions which _depend_ on the edited
+    ///  definition are not re-checked: if the edit changes the definition's type, the
+    ///  caller must re-check those dependents (or the whole contract). `contract_analysis`
+    ///  itself is never updated, and the returned type map only covers `expression`.
+    pub fn check_definition(contract_analysis: &ContractAnalysis, analysis_db: &mut AnalysisDatabase,
+                            expression: &SymbolicExpression) -> CheckResult<DefinitionAnalysis> {
+        let mut checker = TypeChecker::new(analysis_db, LimitedCostTracker::new_max_limit(),
+                                           contract_analysis.analysis_options.clone());
+        checker.contract_context = ContractContext::from_contract_analysis(contract_analysis);
+
+        if let Some(define_type) = DefineFunctionsParsed::try_parse(expression)? {
+            if let Some(name) = define_type.defined_name() {
+                checker.contract_context.remove_definition(name);
+            }
+        }
+
+        let mut local_context = TypingContext::new();
+        let mut result_res = checker.try_type_check_define(expression, &mut local_context);
+        if let Err(ref mut error) = result_res {
+            if !error.has_expression() {
+                error.set_expression(expression);
+            }
+        }
+        if result_res?.is_none() {
+            checker.type_check(expression, &local_context)?;
+        }
+
+        let TypeChecker { type_map, diagnostics, .. } = checker;
+        Ok(DefinitionAnalysis { type_map, diagnostics })
+    }
+
+    /// The type of the data variable `name`, cached on the checker after its first lookup.
+    ///   A data variable's type is fixed by its definition, which always precedes its uses,
+    ///   so the cache never needs to be invalidated within an analysis.
+    pub fn get_persisted_variable_type(&mut self, name: &ClarityName) -> Option<TypeSignature> {
+        if let Some(var_type) = self.persisted_variable_types.get(name) {
+            return Some(var_type.clone())
+        }
+
+        let var_type = self.contract_context.get_persisted_variable_type(name)?.clone();
+        self.persisted_variable_types.insert(name.clone(), var_type.clone());
+        Some(var_type)
+    }
+
+    /// Whether `expected_type` admits `found_type`, allowing tuple width subtyping if
+    ///   requested in the analysis options.
+    pub fn admits(&self, expected_type: &TypeSignature, found_type: &TypeSignature) -> bool {
+        expected_type.admits_type_with(found_type, self.options.tuple_width_subtyping)
+    }
+
+    /// The least supertype of `a` and `b`, allowing tuple width subtyping if requested
+    ///   in the analysis options.
+    pub fn least_supertype(&self, a: &TypeSignature, b: &TypeSignature) -> std::result::Result<TypeSignature, CheckErrors> {
+        TypeSignature::least_supertype_with(a, b, self.options.tuple_width_subtyping)
+    }
+
+    /// Records the finding of an opt-in lint at `expr`. Lints are only reported
+    ///   if requested in the analysis options.
+    pub fn add_warning(&mut self, warning: CheckWarnings, expr: &SymbolicExpression) {
+        self.add_warning_at_expressions(warning, std::slice::from_ref(expr))
+    }
+
+    /// Callbacks of `map`, `filter` and `fold` are expected to be pure: a state mutation in the
+    ///   body of one is reported, at the mutation, by the opt-in lints. `callback` names the
+    ///   defined function whose body is checked, or is `None` for an inline lambda.
+    pub fn lint_callback_mutations(&mut self, callback: Option<&ClarityName>, body: &SymbolicExpression) {
+        if !self.options.lint {
+            return
+        }
+        let mut mutations = Vec::new();
+        collect_state_mutations(body, &mut mutations);
+        for mutation in mutations {
+            self.add_warning(CheckWarnings::MutationInCallback(callback.map(|name| name.to_string())), mutation);
+        }
+    }
+
+    /// Records the finding of an opt-in lint spanning several expressions, e.g., the
+    ///   operands of a comparison.
+    pub fn add_warning_at_expressions(&mut self, warning: CheckWarnings, exprs: &[SymbolicExpression]) {
+        if self.options.lint {
+            let mut diagnostic = Diagnostic::warn(&warning);
+            diagnostic.spans = exprs.iter().map(|e| e.span.clone()).collect();
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Type checks an arm of a branching expression. An early return applied directly as the
+    ///   arm may always diverge, e.g., `(unwrap! none (err u1))`: rather than failing to
+    ///   determine its type, it is typed as `NoType`, the bottom type, which unifies with
+    ///   the type of the other arm.
+    pub fn type_check_arm(&mut self, expr: &SymbolicExpression, context: &TypingContext) -> TypeResult {
+        let early_return_input = expr.match_list()
+            .and_then(|application| application.get(1))
+            .map(|input| input.id);
+        let outer_input = std::mem::replace(&mut self.diverging_input, early_return_input);
+        let result = self.type_check(expr, context);
+        self.diverging_input = outer_input;
+        result
+    }
+
+    /// Whether an early return on this input may always diverge, see `type_check_arm`.
+    pub fn may_diverge(&self, input: &SymbolicExpression) -> bool {
+        self.diverging_input == Some(input.id)
+    }
+
     pub fn track_return_type(&mut self, return_type: TypeSignature) -> CheckResult<()> {
         runtime_cost!(cost_functions::ANALYSIS_TYPE_CHECK, self, return_type.type_size()?)?;
 
+        let tuple_width_subtyping = self.options.tuple_width_subtyping;
         match self.function_return_tracker {
             Some(ref mut tracker) => {
                 let new_type = match tracker.take() {
                     Some(expected_type) => {
-                        TypeSignature::least_supertype(&expected_type, &return_type)
+                        TypeSignature::least_supertype_with(&expected_type, &return_type, tuple_width_subtyping)
                             .map_err(|_| CheckErrors::ReturnTypesMustMatch(expected_type, return_type))?
                     },
                     None => return_type
@@ -260,21 +545,63 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
         runtime_cost!(cost_functions::ANALYSIS_STORAGE, self, size)?;
 
         let mut local_context = TypingContext::new();
+        let mut first_error = None;
+        let mut failed_definitions = HashSet::new();
 
         for exp in contract_analysis.expressions.iter() {
-            let mut result_res = self.try_type_check_define(&exp, &mut local_context);
-            if let Err(ref mut error) = result_res {
+            let result = self.try_type_check_define(&exp, &mut local_context)
+                .and_then(|result| match result {
+                    Some(()) => Ok(()),
+                    // was _not_ a define statement, so handle like a normal statement.
+                    None => self.type_check(&exp, &local_context).map(|_| ())
+                });
+
+            if let Err(mut error) = result {
                 if !error.has_expression() {
                     error.set_expression(&exp);
                 }
+                if !self.options.continue_on_error || is_cost_error(&error.err) {
+                    return Err(error)
+                }
+
+                // an expression using a definition which failed would only report
+                //   that the definition is missing, which is not worth reporting.
+                if !references_any(exp, &failed_definitions) {
+                    self.diagnostics.push(error.diagnostic.clone());
+                }
+                if let Some(name) = defined_name(exp) {
+                    failed_definitions.insert(name.clone());
+                }
+                self.function_return_tracker = None;
+                if first_error.is_none() {
+                    first_error = Some(error);
+                }
             }
-            let result = result_res?;
-            if result.is_none() {
-                // was _not_ a define statement, so handle like a normal statement.
-                self.type_check(&exp, &local_context)?;
+        }
+
+        // callbacks may be defined before or after their use, so their bodies
+        //   are only linted once every use has been checked.
+        if self.options.lint {
+            for exp in contract_analysis.expressions.iter() {
+                match DefineFunctionsParsed::try_parse(exp) {
+                    Ok(Some(DefineFunctionsParsed::PrivateFunction { signature, body })) |
+                    Ok(Some(DefineFunctionsParsed::ReadOnlyFunction { signature, body })) |
+                    Ok(Some(DefineFunctionsParsed::PublicFunction { signature, body })) => {
+                        if let Some(name) = signature.get(0).and_then(|name| name.match_atom()) {
+                            if self.iterable_callbacks.contains(name) {
+                                self.lint_callback_mutations(Some(name), body);
+                            }
+                        }
+                    },
+                    _ => {}
+                }
             }
         }
-        Ok(())
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(())
+        }
     }
 
     // Type check an expression, with an expected_type that should _admit_ the expression.
@@ -291,6 +618,7 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
                     .ok_or(CheckErrors::NoSuchContract(contract_identifier.to_string()))?;
 
                 contract_to_check.check_trait_compliance(trait_identifier, trait_definition)?;
+                self.contract_context.add_dependency(contract_identifier.clone());
                 return Ok(expected_type.clone());
             }
             (_, _) => {}
@@ -299,8 +627,8 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
         let actual_type = self.type_check(expr, context)?;
         analysis_typecheck_cost(self, expected_type, &actual_type)?;
 
-        if !expected_type.admits_type(&actual_type) {
-            let mut err: CheckError = CheckErrors::TypeError(expected_type.clone(), actual_type).into();
+        if !self.admits(expected_type, &actual_type) {
+            let mut err = admission_error(expected_type, actual_type);
             err.set_expression(expr);
             Err(err)
         } else {
@@ -335,7 +663,8 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
     fn type_check_function_type(&mut self, func_type: &FunctionType,
                                 args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
         let typed_args = self.type_check_all(args, context)?;
-        func_type.check_args(self, &typed_args)
+        let tuple_width_subtyping = self.options.tuple_width_subtyping;
+        func_type.check_args_with(self, &typed_args, tuple_width_subtyping)
     }
 
     fn get_function_type(&self, function_name: &str) -> Option<FunctionType> {
@@ -350,7 +679,13 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
         let function_name = function_name.match_atom()
             .ok_or(CheckErrors::BadFunctionName)?;
         let mut args = parse_name_type_pairs::<()>(args, &mut ())
-            .map_err(|_| { CheckErrors::BadSyntaxBinding })?;
+            .map_err(|e| match e {
+                // an argument type exceeding MAX_VALUE_SIZE is well-formed, so report its size
+                CheckErrors::ValueTooLarge => e,
+                // a well-formed argument list with a malformed type annotation reports the annotation
+                CheckErrors::InvalidTypeDescription | CheckErrors::UnknownTypeName(_) => e,
+                _ => CheckErrors::BadSyntaxBinding
+            })?;
 
         if self.function_return_tracker.is_some() {
             panic!("Interpreter error: Previous function define left dirty typecheck state.");
@@ -358,9 +693,13 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
 
 
         let mut function_context = context.extend()?;
-        for (arg_name, arg_type) in args.iter() {
+        for ((arg_name, arg_type), arg_expr) in args.iter().zip(&signature[1..]) {
             self.contract_context.check_name_used(arg_name)?;
-            
+
+            if arg_type.contains_empty_sequence() {
+                self.add_warning(CheckWarnings::DegenerateArgumentType(arg_name.to_string(), arg_type.clone()), arg_expr);
+            }
+
             match arg_type {
                 TypeSignature::TraitReferenceType(trait_id) => {
                     function_context.add_trait_reference(&arg_name, &trait_id);
@@ -383,7 +722,7 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
                     if let Some(Some(ref expected)) = self.function_return_tracker {
                         // check if the computed return type matches the return type
                         //   of any early exits from the call graph (e.g., (expects ...) calls)
-                        TypeSignature::least_supertype(expected, &return_type)
+                        self.least_supertype(expected, &return_type)
                             .map_err(|_| CheckErrors::ReturnTypesMustMatch(expected.clone(), return_type))?
                     } else {
                         return_type
@@ -408,25 +747,80 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
 
         let key_type = TypeSignature::from(
             TupleTypeSignature::parse_name_type_pair_list::<()>(key_type, &mut ())
-                .map_err(|_| { CheckErrors::BadMapTypeDefinition })?);
+                .map_err(|e| match e {
+                    CheckErrors::ValueTooLarge => map_type_too_large(map_name, "key"),
+                    _ => CheckErrors::BadMapTypeDefinition.into()
+                })?);
         let value_type = TypeSignature::from(
             TupleTypeSignature::parse_name_type_pair_list::<()>(value_type, &mut ())
-                .map_err(|_| { CheckErrors::BadMapTypeDefinition })?);
+                .map_err(|e| match e {
+                    CheckErrors::ValueTooLarge => map_type_too_large(map_name, "value"),
+                    _ => CheckErrors::BadMapTypeDefinition.into()
+                })?);
+
+        // `map-get?` wraps values in an optional, so the value type must leave room
+        //   for the wrapper, or the map would be unreadable.
+        TypeSignature::new_option(value_type.clone())
+            .map_err(|_| map_type_too_large(map_name, "value"))?;
 
         Ok((map_name.clone(), (key_type, value_type)))
     }
 
     // Aaron: note, using lazy statics here would speed things up a bit and reduce clone()s
-    fn try_native_function_check(&mut self, function: &str, args: &[SymbolicExpression], context: &TypingContext) -> Option<TypeResult> {
+    fn try_native_function_check(&mut self, application_id: u64, function: &str, args: &[SymbolicExpression], context: &TypingContext) -> Option<TypeResult> {
+        if let Some(custom_function) = self.options.custom_natives.get(function).cloned() {
+            return Some(custom_function.type_check_appliction(self, args, context))
+        }
+
         if let Some(ref native_function) = NativeFunctions::lookup_by_name(function) {
+            self.contract_context.add_used_native(*native_function);
             let typed_function = TypedNativeFunction::type_native_function(native_function);
-            Some(typed_function.type_check_appliction(self, args, context))
+            if self.options.strict_numerics {
+                if let TypedNativeFunction::Simple(SimpleNativeFunction(ref function_type)) = typed_function {
+                    if let Err(e) = natives::arithmetic::check_determined_signedness(function_type, args) {
+                        return Some(Err(e))
+                    }
+                }
+            }
+            let mut result = typed_function.type_check_appliction(self, args, context);
+            if result.is_ok() {
+                if let Err(e) = self.fold_native_application(application_id, native_function, args) {
+                    result = Err(e);
+                }
+            }
+            if result.is_ok() && self.options.lint {
+                if let Some(outcome) = natives::arithmetic::fold_constant_comparison(native_function, args, &self.constant_values) {
+                    self.add_warning_at_expressions(CheckWarnings::ConstantComparison(outcome), args);
+                }
+            }
+            Some(result)
         } else {
             None
         }
     }
 
-    fn type_check_function_application(&mut self, expression: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    /// Folds the application of an arithmetic native to constant arguments, recording its
+    ///   value under `application_id`. The arguments must already be type checked, so that
+    ///   their own values have been folded: each application is folded exactly once.
+    fn fold_native_application(&mut self, application_id: u64, function: &NativeFunctions, args: &[SymbolicExpression]) -> CheckResult<()> {
+        if !natives::arithmetic::is_foldable(function) {
+            return Ok(())
+        }
+
+        runtime_cost!(cost_functions::ANALYSIS_CONSTANT_FOLD, self, args.len())?;
+
+        if let Some(value) = natives::arithmetic::fold_native_application(function, args, &self.constant_values)? {
+            self.constant_values.insert(application_id, value);
+        }
+        Ok(())
+    }
+
+    /// The value of `expr` at analysis time, if it is constant. `expr` must already be type checked.
+    pub fn constant_value(&self, expr: &SymbolicExpression) -> Option<Value> {
+        natives::arithmetic::fold_constant_expression(expr, &self.constant_values)
+    }
+
+    fn type_check_function_application(&mut self, application_id: u64, expression: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
         let (function_name, args) = expression.split_first()
             .ok_or(CheckErrors::NonFunctionApplication)?;
 
@@ -434,7 +828,7 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
         let function_name = function_name.match_atom()
             .ok_or(CheckErrors::NonFunctionApplication)?;
 
-        if let Some(type_result) = self.try_native_function_check(function_name, args, context) {
+        if let Some(type_result) = self.try_native_function_check(application_id, function_name, args, context) {
             type_result
         } else {
             let function_type = self.get_function_type(function_name)
@@ -472,7 +866,7 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
                 self.lookup_variable(name, context)?
             },
             List(ref expression) => {
-                self.type_check_function_application(expression, context)?
+                self.type_check_function_application(expr.id, expression, context)?
             },
             TraitReference(_, _) | Field(_) => {
                 return Err(CheckErrors::UnexpectedTraitOrFieldReference.into());
@@ -481,6 +875,10 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
 
         runtime_cost!(cost_functions::ANALYSIS_TYPE_ANNOTATE, self, type_sig.type_size()?)?;
         self.type_map.set_type(expr, type_sig.clone())?;
+        if self.options.trace {
+            self.type_trace.push(TypeTrace { span: expr.span.clone(), expression: expr.to_string(),
+                                             type_signature: type_sig.clone() });
+        }
         Ok(type_sig)
     }
 
@@ -590,7 +988,8 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
                             let type_size = trait_type_size(&trait_sig)?;
                             runtime_cost!(cost_functions::ANALYSIS_USE_TRAIT_ENTRY, self, type_size)?;
                             runtime_cost!(cost_functions::ANALYSIS_BIND_NAME, self, type_size)?;
-                            self.contract_context.add_trait(trait_identifier.name.clone(), trait_sig)?
+                            self.contract_context.add_trait(trait_identifier.name.clone(), trait_sig)?;
+                            self.contract_context.add_dependency(trait_identifier.contract_identifier.clone());
                         },
                         None => {
                             // still had to do a db read, even if it didn't exist!
@@ -600,6 +999,7 @@ impl <'a, 'b> TypeChecker <'a, 'b> {
                     }
                 },
                 DefineFunctionsParsed::ImplTrait { trait_identifier } => {
+                    self.contract_context.add_dependency(trait_identifier.contract_identifier.clone());
                     self.contract_context.add_implemented_trait(trait_identifier.clone())?;
                 },
             };
@@ -0,0 +1,179 @@
+use vm::functions::NativeFunctions;
+use vm::representations::SymbolicExpression;
+use vm::representations::SymbolicExpressionType::{AtomValue, Atom, LiteralValue, List};
+use vm::variables::NativeVariables;
+use vm::types::{Value, FunctionType};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use vm::analysis::type_checker::{CheckResult, CheckErrors};
+
+macro_rules! make_constant_fold_op {
+    ($name: ident, $type: ty) => {
+        fn $name(function: &NativeFunctions, first: $type, rest: &[$type]) -> Result<$type, CheckErrors> {
+            use vm::functions::NativeFunctions::*;
+            if rest.len() == 0 {
+                // a single argument to `-` is a negation, all other
+                //   arithmetic functions return their argument.
+                return match function {
+                    Subtract => first.checked_neg().ok_or(CheckErrors::ArithmeticOverflow),
+                    _ => Ok(first)
+                }
+            }
+
+            let mut total = first;
+            for x in rest.iter() {
+                total = match function {
+                    Add => total.checked_add(*x).ok_or(CheckErrors::ArithmeticOverflow)?,
+                    Subtract => total.checked_sub(*x).ok_or(CheckErrors::ArithmeticOverflow)?,
+                    Multiply => total.checked_mul(*x).ok_or(CheckErrors::ArithmeticOverflow)?,
+                    Divide | Modulo if *x == 0 => return Err(CheckErrors::DivideByZero),
+                    Divide => total.checked_div(*x).ok_or(CheckErrors::ArithmeticOverflow)?,
+                    Modulo => total.checked_rem(*x).ok_or(CheckErrors::ArithmeticOverflow)?,
+                    _ => return Err(CheckErrors::CheckerImplementationFailure)
+                }
+            }
+            Ok(total)
+        }
+    }
+}
+
+make_constant_fold_op!(fold_i128, i128);
+make_constant_fold_op!(fold_u128, u128);
+
+/// The value of an expression at analysis time, if it consists solely of integer and boolean
+///   literals, arithmetic on those literals, and equality checks between them. Applications are
+///   not evaluated here: their values are looked up in `folded`, the applications already folded
+///   by `fold_native_application`, so an expression must be type checked before it is looked up.
+pub fn fold_constant_expression(expr: &SymbolicExpression, folded: &HashMap<u64, Value>) -> Option<Value> {
+    match expr.expr {
+        AtomValue(Value::Int(x)) | LiteralValue(Value::Int(x)) => Some(Value::Int(x)),
+        AtomValue(Value::UInt(x)) | LiteralValue(Value::UInt(x)) => Some(Value::UInt(x)),
+        Atom(ref name) => {
+            match NativeVariables::lookup_by_name(name) {
+                Some(NativeVariables::NativeTrue) => Some(Value::Bool(true)),
+                Some(NativeVariables::NativeFalse) => Some(Value::Bool(false)),
+                _ => None
+            }
+        },
+        List(_) => folded.get(&expr.id).cloned(),
+        _ => None
+    }
+}
+
+/// Whether `fold_native_application` may fold applications of `function`.
+pub fn is_foldable(function: &NativeFunctions) -> bool {
+    use vm::functions::NativeFunctions::*;
+    match function {
+        Add | Subtract | Multiply | Equals | Divide | Modulo => true,
+        _ => false
+    }
+}
+
+/// Evaluates the application of an arithmetic native to constant arguments, whose values
+///   are read with `fold_constant_expression`, returning `ArithmeticOverflow` or `DivideByZero`
+///   if the evaluation would fail at runtime. A division by a constant zero is reported even
+///   when the dividend is not constant.
+pub fn fold_native_application(function: &NativeFunctions, args: &[SymbolicExpression], folded: &HashMap<u64, Value>) -> CheckResult<Option<Value>> {
+    use vm::functions::NativeFunctions::*;
+    match function {
+        Add | Subtract | Multiply | Equals => {},
+        Divide | Modulo => check_constant_divisors(args, folded)?,
+        _ => return Ok(None)
+    }
+
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args.iter() {
+        match fold_constant_expression(arg, folded) {
+            Some(value) => values.push(value),
+            None => return Ok(None)
+        }
+    }
+
+    if let Equals = function {
+        return Ok(values.split_first()
+                  .map(|(first, rest)| Value::Bool(rest.iter().all(|x| x == first))))
+    }
+
+    // mismatched argument types are reported by the type checker, so
+    //   these just decline to fold.
+    match values.split_first() {
+        Some((Value::Int(first), rest)) => {
+            let mut operands = Vec::with_capacity(rest.len());
+            for value in rest.iter() {
+                match value {
+                    Value::Int(x) => operands.push(*x),
+                    _ => return Ok(None)
+                }
+            }
+            Ok(Some(Value::Int(fold_i128(function, *first, &operands)?)))
+        },
+        Some((Value::UInt(first), rest)) => {
+            let mut operands = Vec::with_capacity(rest.len());
+            for value in rest.iter() {
+                match value {
+                    Value::UInt(x) => operands.push(*x),
+                    _ => return Ok(None)
+                }
+            }
+            Ok(Some(Value::UInt(fold_u128(function, *first, &operands)?)))
+        },
+        _ => Ok(None)
+    }
+}
+
+/// Evaluates a comparison between two constant operands at analysis time, e.g., `(> 5 5)`,
+///   returning `Ok(None)` if the function is not a comparison, or if either operand is not constant.
+pub fn fold_constant_comparison(function: &NativeFunctions, args: &[SymbolicExpression], folded: &HashMap<u64, Value>) -> Option<bool> {
+    use vm::functions::NativeFunctions::*;
+    match function {
+        CmpLess | CmpLeq | CmpGreater | CmpGeq if args.len() == 2 => {},
+        _ => return None
+    }
+
+    let ordering = match (fold_constant_expression(&args[0], folded), fold_constant_expression(&args[1], folded)) {
+        (Some(Value::Int(first)), Some(Value::Int(second))) => first.cmp(&second),
+        (Some(Value::UInt(first)), Some(Value::UInt(second))) => first.cmp(&second),
+        _ => return None
+    };
+
+    Some(match function {
+        CmpLess => ordering == Ordering::Less,
+        CmpLeq => ordering != Ordering::Greater,
+        CmpGreater => ordering == Ordering::Greater,
+        _ => ordering != Ordering::Less
+    })
+}
+
+fn check_constant_divisors(args: &[SymbolicExpression], folded: &HashMap<u64, Value>) -> CheckResult<()> {
+    for divisor in args.iter().skip(1) {
+        match fold_constant_expression(divisor, folded) {
+            Some(Value::Int(0)) | Some(Value::UInt(0)) => return Err(CheckErrors::DivideByZero.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// In strict numerics mode, an application of an integer-polymorphic arithmetic function
+///   is rejected if none of its operands determines the signedness: i.e., every operand
+///   is an unprefixed integer literal, which would otherwise default to `int`.
+pub fn check_determined_signedness(function_type: &FunctionType, args: &[SymbolicExpression]) -> CheckResult<()> {
+    match function_type {
+        FunctionType::ArithmeticVariadic | FunctionType::ArithmeticBinary | FunctionType::ArithmeticComparison => {},
+        _ => return Ok(())
+    }
+
+    let all_default_literals = args.len() > 0 && args.iter().all(|arg| {
+        match arg.expr {
+            AtomValue(Value::Int(_)) | LiteralValue(Value::Int(_)) => true,
+            _ => false
+        }
+    });
+
+    if all_default_literals {
+        Err(CheckErrors::CouldNotDetermineType.into())
+    } else {
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 use vm::representations::{SymbolicExpression};
-use vm::types::{TypeSignature, TupleTypeSignature, BlockInfoProperty, MAX_VALUE_SIZE};
-use super::{TypeChecker, TypingContext, TypeResult, FunctionType, no_type}; 
-use vm::analysis::errors::{CheckError, CheckErrors, CheckResult, check_argument_count};
+use vm::types::{TypeSignature, TupleTypeSignature, BlockInfoProperty, Value, MAX_VALUE_SIZE};
+use super::{TypeChecker, TypingContext, TypeResult, FunctionType, no_type};
+use vm::analysis::errors::{CheckError, CheckErrors, CheckWarnings, CheckResult, check_argument_count};
 use vm::costs::{cost_functions};
+use std::convert::TryFrom;
 
 pub fn check_special_get_owner(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(2, args)?;
@@ -40,6 +41,36 @@ pub fn check_special_get_balance(checker: &mut TypeChecker, args: &[SymbolicExpr
     Ok(TypeSignature::UIntType)
 }
 
+pub fn check_special_get_supply(checker: &mut TypeChecker, args: &[SymbolicExpression], _context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+
+    let asset_name = args[0].match_atom()
+        .ok_or(CheckErrors::BadTokenName)?;
+
+    if !checker.contract_context.ft_exists(asset_name) {
+        return Err(CheckErrors::NoSuchFT(asset_name.to_string()).into());
+    }
+
+    runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, checker, 1)?;
+
+    Ok(TypeSignature::UIntType)
+}
+
+pub fn stx_account_type() -> CheckResult<TypeSignature> {
+    Ok(TupleTypeSignature::try_from(vec![
+        ("locked".into(), TypeSignature::UIntType),
+        ("unlock-height".into(), TypeSignature::UIntType),
+        ("unlocked".into(), TypeSignature::UIntType)])?.into())
+}
+
+pub fn check_special_stx_account(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+
+    checker.type_check_expects(&args[0], context, &TypeSignature::PrincipalType)?;
+
+    stx_account_type()
+}
+
 pub fn check_special_mint_asset(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(3, args)?;
 
@@ -56,6 +87,14 @@ pub fn check_special_mint_asset(checker: &mut TypeChecker, args: &[SymbolicExpre
     checker.type_check_expects(&args[1], context, &expected_asset_type)?;
     checker.type_check_expects(&args[2], context, &expected_owner_type)?;
 
+    if checker.options.lint {
+        if let Some(Value::Int(id)) = checker.constant_value(&args[1]) {
+            if id < 0 {
+                checker.add_warning(CheckWarnings::NegativeAssetIdentifier(id), &args[1]);
+            }
+        }
+    }
+
     Ok(TypeSignature::ResponseType(
         Box::new((TypeSignature::BoolType,
                   TypeSignature::UIntType))).into())
@@ -78,7 +117,15 @@ pub fn check_special_mint_token(checker: &mut TypeChecker, args: &[SymbolicExpre
     if !checker.contract_context.ft_exists(asset_name) {
         return Err(CheckErrors::NoSuchFT(asset_name.to_string()).into());
     }
-    
+
+    // amounts are uints, so a negative literal amount is already a type error: the only
+    //   literal which is not positive, and so left for the lint to catch, is u0.
+    if checker.options.lint {
+        if let Some(Value::UInt(0)) = checker.constant_value(&args[1]) {
+            checker.add_warning(CheckWarnings::NonPositiveMintAmount, &args[1]);
+        }
+    }
+
     Ok(TypeSignature::ResponseType(
         Box::new((TypeSignature::BoolType,
                   TypeSignature::UIntType))).into())
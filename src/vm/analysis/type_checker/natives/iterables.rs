@@ -1,6 +1,7 @@
 use vm::functions::NativeFunctions;
+use vm::functions::iterables::Lambda;
 use vm::representations::{SymbolicExpression, SymbolicExpressionType};
-use vm::types::{ TypeSignature, FunctionType };
+use vm::types::{ TypeSignature, FunctionType, FixedFunction, FunctionArg };
 use vm::types::{Value, MAX_VALUE_SIZE};
 pub use vm::types::signatures::{ListTypeData, BufferLength};
 use std::convert::TryFrom;
@@ -8,46 +9,127 @@ use std::convert::TryInto;
 
 use vm::analysis::type_checker::{
     TypeResult, TypingContext, CheckResult, check_argument_count, CheckErrors, no_type, TypeChecker};
+use vm::analysis::types::FunctionKind;
 use super::{TypedNativeFunction, SimpleNativeFunction};
 
 use vm::costs::{cost_functions, analysis_typecheck_cost};
 
+/// Resolves the function name passed to `map`, `filter` and `fold`. User-defined functions
+///   may be referenced before their definition: the definition sorter orders top-level
+///   expressions by their dependencies, so the callee has already been added to the
+///   contract context when the caller is checked. Private and read-only functions may be
+///   applied, but public functions, which may mutate state, may not.
 fn get_simple_native_or_user_define(function_name: &str, checker: &mut TypeChecker) -> CheckResult<FunctionType> {
     runtime_cost!(cost_functions::ANALYSIS_LOOKUP_FUNCTION, checker, 1)?;
     if let Some(ref native_function) = NativeFunctions::lookup_by_name(function_name) {
+        checker.contract_context.add_used_native(*native_function);
         if let TypedNativeFunction::Simple(SimpleNativeFunction(function_type)) = TypedNativeFunction::type_native_function(native_function) {
             Ok(function_type)
         } else {
             Err(CheckErrors::IllegalOrUnknownFunctionApplication(function_name.to_string()).into())
         }
     } else {
-        checker.get_function_type(function_name)
-            .ok_or(CheckErrors::IllegalOrUnknownFunctionApplication(function_name.to_string()).into())
+        match checker.contract_context.get_function(function_name) {
+            Some((FunctionKind::Private, function_type)) | Some((FunctionKind::ReadOnly, function_type)) =>
+                Ok(function_type.clone()),
+            Some((FunctionKind::Public, _)) | None =>
+                Err(CheckErrors::IllegalOrUnknownFunctionApplication(function_name.to_string()).into())
+        }
+    }
+}
+
+/// Infers the function type of a lambda applied to arguments of the given types, by
+///   checking its body with each parameter bound to the corresponding argument type.
+fn infer_lambda_type(checker: &mut TypeChecker, lambda: &Lambda, arg_types: &[TypeSignature], context: &TypingContext) -> CheckResult<FixedFunction> {
+    if lambda.parameters.len() != arg_types.len() {
+        return Err(CheckErrors::IncorrectArgumentCount(lambda.parameters.len(), arg_types.len()).into())
+    }
+
+    let mut lambda_context = context.extend()?;
+    let mut args = Vec::with_capacity(arg_types.len());
+    for (name, arg_type) in lambda.parameters.iter().zip(arg_types.iter()) {
+        checker.contract_context.check_name_used(name)?;
+        if lambda_context.lookup_variable_type(name).is_some() {
+            return Err(CheckErrors::NameAlreadyUsed(name.to_string()).into())
+        }
+
+        runtime_cost!(cost_functions::ANALYSIS_BIND_NAME, checker, arg_type.type_size()?)?;
+        lambda_context.variable_types.insert((*name).clone(), arg_type.clone());
+        args.push(FunctionArg::new(arg_type.clone(), (*name).clone()));
+    }
+
+    let returns = checker.type_check(lambda.body, &lambda_context)?;
+    Ok(FixedFunction { args, returns })
+}
+
+/// The function argument of `map`, `filter` and `fold`: a named function, or an inline lambda.
+enum IterableFunction <'a> {
+    Named(FunctionType),
+    Lambda(Lambda<'a>)
+}
+
+impl <'a> IterableFunction <'a> {
+    fn resolve(expr: &'a SymbolicExpression, checker: &mut TypeChecker) -> CheckResult<IterableFunction<'a>> {
+        if let Some(lambda) = Lambda::parse(expr)? {
+            checker.lint_callback_mutations(None, lambda.body);
+            return Ok(IterableFunction::Lambda(lambda))
+        }
+
+        let function_name = expr.match_atom()
+            .ok_or(CheckErrors::NonFunctionApplication)?;
+        if NativeFunctions::lookup_by_name(function_name).is_none() {
+            checker.iterable_callbacks.insert(function_name.clone());
+        }
+        // we will only lookup native or defined functions here.
+        //   you _cannot_ apply a special function.
+        get_simple_native_or_user_define(function_name, checker)
+            .map(IterableFunction::Named)
+    }
+
+    /// Checks an application of the function. A lambda's body is checked once per call to
+    ///   this method, so each lambda may only be checked once.
+    fn check_args(&self, checker: &mut TypeChecker, arg_types: &[TypeSignature], context: &TypingContext) -> TypeResult {
+        match self {
+            IterableFunction::Named(function_type) => {
+                let tuple_width_subtyping = checker.options.tuple_width_subtyping;
+                function_type.check_args_with(checker, arg_types, tuple_width_subtyping)
+            },
+            IterableFunction::Lambda(lambda) => infer_lambda_type(checker, lambda, arg_types, context)
+                .map(|function_type| function_type.returns)
+        }
+    }
+}
+
+/// The items of the empty list `(list)` have no type to check an iterable function against,
+///   so such a sequence must be given a type, e.g., by binding it as a typed argument.
+fn list_items_type(list_data: ListTypeData) -> CheckResult<TypeSignature> {
+    let (items_type, _) = list_data.destruct();
+    if items_type.is_no_type() {
+        Err(CheckErrors::CouldNotDetermineType.into())
+    } else {
+        Ok(items_type)
     }
 }
 
 pub fn check_special_map(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(2, args)?;
     
-    let function_name = args[0].match_atom()
-        .ok_or(CheckErrors::NonFunctionApplication)?;
-    // we will only lookup native or defined functions here.
-    //   you _cannot_ map a special function.
-    let function_type = get_simple_native_or_user_define(function_name, checker)?;
+    let function = IterableFunction::resolve(&args[0], checker)?;
 
     runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
     let argument_type = checker.type_check(&args[1], context)?;
     
     match argument_type {
         TypeSignature::ListType(list_data) => {
-            let (arg_items_type, arg_length) = list_data.destruct();
-            let mapped_type = function_type.check_args(checker, &[arg_items_type])?;
-            TypeSignature::list_of(mapped_type, arg_length)
+            let arg_length = list_data.get_max_len();
+            let arg_items_type = list_items_type(list_data)?;
+            let mapped_type = function.check_args(checker, &[arg_items_type], context)?;
+            TypeSignature::constructed_list_of(mapped_type, arg_length)
                 .map_err(|_| CheckErrors::ConstructedListTooLarge.into())
         },
         TypeSignature::BufferType(buffer_data) => {
-            let mapped_type = function_type.check_args(checker, &[TypeSignature::min_buffer()])?;
-            TypeSignature::list_of(mapped_type, buffer_data.into())
+            let mapped_type = function.check_args(checker, &[TypeSignature::min_buffer()], context)?;
+            TypeSignature::constructed_list_of(mapped_type, buffer_data.into())
                 .map_err(|_| CheckErrors::ConstructedListTooLarge.into())
         },
         _ => Err(CheckErrors::ExpectedListOrBuffer(argument_type).into())
@@ -57,25 +139,30 @@ pub fn check_special_map(checker: &mut TypeChecker, args: &[SymbolicExpression],
 pub fn check_special_filter(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(2, args)?;
     
-    let function_name = args[0].match_atom()
-        .ok_or(CheckErrors::NonFunctionApplication)?;
-    // we will only lookup native or defined functions here.
-    //   you _cannot_ map a special function.
-    let function_type = get_simple_native_or_user_define(function_name, checker)?;
+    let function = IterableFunction::resolve(&args[0], checker)?;
 
     runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
     let argument_type = checker.type_check(&args[1], context)?;
 
     {
         let input_type = match argument_type {
-            TypeSignature::ListType(ref list_data) => Ok(list_data.clone().destruct().0),
+            TypeSignature::ListType(ref list_data) => list_items_type(list_data.clone()),
             TypeSignature::BufferType(_) => Ok(TypeSignature::min_buffer()),
-            _ => Err(CheckErrors::ExpectedListOrBuffer(argument_type.clone()))
+            _ => Err(CheckErrors::ExpectedListOrBuffer(argument_type.clone()).into())
         }?;
     
-        let filter_type = function_type.check_args(checker, &[input_type])?;
-
-        if TypeSignature::BoolType != filter_type {
+        let filter_type = function.check_args(checker, &[input_type], context)?;
+
+        // a predicate may also return an `(optional bool)`, where `none` filters the item out.
+        let is_predicate = match filter_type {
+            TypeSignature::BoolType => true,
+            TypeSignature::OptionalType(ref inner_type) => match **inner_type {
+                TypeSignature::BoolType | TypeSignature::NoType => true,
+                _ => false
+            },
+            _ => false
+        };
+        if !is_predicate {
             return Err(CheckErrors::TypeError(TypeSignature::BoolType, filter_type).into())
         }
     }
@@ -86,19 +173,15 @@ pub fn check_special_filter(checker: &mut TypeChecker, args: &[SymbolicExpressio
 pub fn check_special_fold(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(3, args)?;
     
-    let function_name = args[0].match_atom()
-        .ok_or(CheckErrors::NonFunctionApplication)?;
-    // we will only lookup native or defined functions here.
-    //   you _cannot_ fold a special function.
-    let function_type = get_simple_native_or_user_define(function_name, checker)?;
+    let function = IterableFunction::resolve(&args[0], checker)?;
     
     runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
     let argument_type = checker.type_check(&args[1], context)?;
 
     let input_type = match argument_type {
-        TypeSignature::ListType(list_data) => Ok(list_data.destruct().0),
+        TypeSignature::ListType(list_data) => list_items_type(list_data),
         TypeSignature::BufferType(_) => Ok(TypeSignature::min_buffer()),
-        _ => Err(CheckErrors::ExpectedListOrBuffer(argument_type))
+        _ => Err(CheckErrors::ExpectedListOrBuffer(argument_type).into())
     }?;
 
     let initial_value_type = checker.type_check(&args[2], context)?;
@@ -106,12 +189,27 @@ pub fn check_special_fold(checker: &mut TypeChecker, args: &[SymbolicExpression]
     // fold: f(A, B) -> A
     //     where A = initial_value_type
     //           B = list items type
+
+    let function_type = match function {
+        IterableFunction::Named(function_type) => function_type,
+        IterableFunction::Lambda(lambda) => {
+            // a lambda's body can only be checked once, so its accumulator takes the type
+            //   of the initial value, which must admit the lambda's return type.
+            let return_type = infer_lambda_type(checker, &lambda, &[input_type, initial_value_type.clone()], context)?
+                .returns;
+            if !checker.admits(&initial_value_type, &return_type) {
+                return Err(CheckErrors::TypeError(initial_value_type, return_type).into())
+            }
+            return Ok(initial_value_type)
+        }
+    };
     
+    let tuple_width_subtyping = checker.options.tuple_width_subtyping;
     // f must accept the initial value and the list items type
-    let return_type = function_type.check_args(checker, &[input_type.clone(), initial_value_type])?;
+    let return_type = function_type.check_args_with(checker, &[input_type.clone(), initial_value_type], tuple_width_subtyping)?;
 
     // f must _also_ accepts its own return type!
-    let return_type = function_type.check_args(checker, &[input_type, return_type])?;
+    let return_type = function_type.check_args_with(checker, &[input_type, return_type], tuple_width_subtyping)?;
     
     Ok(return_type)
 }
@@ -132,10 +230,10 @@ pub fn check_special_concat(checker: &mut TypeChecker, args: &[SymbolicExpressio
                 let (lhs_entry_type, lhs_max_len) = lhs_list.destruct();
                 let (rhs_entry_type, rhs_max_len) = rhs_list.destruct();
 
-                let list_entry_type = TypeSignature::least_supertype(&lhs_entry_type, &rhs_entry_type)?;
+                let list_entry_type = checker.least_supertype(&lhs_entry_type, &rhs_entry_type)?;
                 let new_len = lhs_max_len.checked_add(rhs_max_len)
                     .ok_or(CheckErrors::MaxLengthOverflow)?;
-                let return_type = TypeSignature::list_of(list_entry_type, new_len)?;
+                let return_type = TypeSignature::constructed_list_of(list_entry_type, new_len)?;
                 return Ok(return_type);
             } else {
                 return Err(CheckErrors::TypeError(rhs_type.clone(), TypeSignature::ListType(lhs_list)).into());
@@ -168,10 +266,10 @@ pub fn check_special_append(checker: &mut TypeChecker, args: &[SymbolicExpressio
 
             analysis_typecheck_cost(checker, &lhs_entry_type, &rhs_type)?;
 
-            let list_entry_type = TypeSignature::least_supertype(&lhs_entry_type, &rhs_type)?;
+            let list_entry_type = checker.least_supertype(&lhs_entry_type, &rhs_type)?;
             let new_len = lhs_max_len.checked_add(1)
                 .ok_or(CheckErrors::MaxLengthOverflow)?;
-            let return_type = TypeSignature::list_of(list_entry_type, new_len)?;
+            let return_type = TypeSignature::constructed_list_of(list_entry_type, new_len)?;
             return Ok(return_type);
         },
         _ => Err(CheckErrors::ExpectedListApplication.into())
@@ -197,14 +295,20 @@ pub fn check_special_as_max_len(checker: &mut TypeChecker, args: &[SymbolicExpre
     let iterable = checker.type_check(&args[0], context)?;
     analysis_typecheck_cost(checker, &iterable, &iterable)?;
 
+    if let Some(narrowed) = iterable.narrow_len(expected_len) {
+        return Ok(TypeSignature::OptionalType(Box::new(narrowed)))
+    }
+
+    // a sequence always fits in a larger maximum length, which the result type still reports.
     match iterable {
         TypeSignature::ListType(list) => {
             let (lhs_entry_type, _) = list.destruct();
-            let resized_list = ListTypeData::new_list(lhs_entry_type, expected_len)?;
-            Ok(TypeSignature::OptionalType(Box::new(TypeSignature::ListType(resized_list))))
+            let resized_list = TypeSignature::constructed_list_of(lhs_entry_type, expected_len)?;
+            Ok(TypeSignature::OptionalType(Box::new(resized_list)))
         },
         TypeSignature::BufferType(_) => {
-            Ok(TypeSignature::OptionalType(Box::new(TypeSignature::BufferType(BufferLength::try_from(expected_len).unwrap()))))
+            let resized_buffer = BufferLength::try_from(expected_len)?;
+            Ok(TypeSignature::OptionalType(Box::new(TypeSignature::BufferType(resized_buffer))))
         },
         _ => Err(CheckErrors::ExpectedListOrBuffer(iterable).into())
     }
@@ -2,12 +2,13 @@ use vm::representations::{SymbolicExpression, SymbolicExpressionType};
 use vm::types::{TypeSignature, Value, PrincipalData};
 
 use vm::functions::tuples;
+use vm::functions::single_field_key_type;
 use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
 
 use super::check_special_tuple_cons;
 use vm::analysis::type_checker::{TypeResult, TypingContext, 
                                  check_arguments_at_least,
-                                 CheckError, CheckErrors, no_type, TypeChecker};
+                                 CheckError, CheckErrors, no_type, admission_error, TypeChecker};
 
 use vm::costs::{cost_functions, analysis_typecheck_cost};
 
@@ -39,13 +40,51 @@ pub fn check_special_fetch_entry(checker: &mut TypeChecker, args: &[SymbolicExpr
     runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, &mut checker.cost_track, value_type.type_size()?)?;
     analysis_typecheck_cost(&mut checker.cost_track, expected_key_type, &key_type)?;
 
+    check_map_lookup(checker, expected_key_type, value_type, key_type)
+}
+
+/// Reads an entry from the map of another contract, which must already be published.
+pub fn check_special_fetch_contract_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_arguments_at_least(3, args)?;
+
+    let contract_identifier = match args[0].expr {
+        SymbolicExpressionType::LiteralValue(Value::Principal(PrincipalData::Contract(ref contract_identifier))) => contract_identifier,
+        _ => return Err(CheckError::new(CheckErrors::ContractCallExpectName))
+    };
+
+    let map_name = args[1].match_atom()
+        .ok_or(CheckErrors::BadMapName)?;
+
+    checker.type_map.set_type(&args[0], no_type())?;
+    checker.type_map.set_type(&args[1], no_type())?;
+
+    let key_type = check_and_type_map_arg_tuple(checker, &args[2], context)?;
+
+    let (expected_key_type, value_type) = checker.db.get_map_type(contract_identifier, map_name)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, &mut checker.cost_track, expected_key_type.type_size()?)?;
+    runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, &mut checker.cost_track, value_type.type_size()?)?;
+    analysis_typecheck_cost(&mut checker.cost_track, &expected_key_type, &key_type)?;
+
+    checker.contract_context.add_dependency(contract_identifier.clone());
+
+    check_map_lookup(checker, &expected_key_type, &value_type, key_type)
+}
+
+/// Checks the key of a read from a map with the given types, returning the type of the read.
+fn check_map_lookup(checker: &TypeChecker, expected_key_type: &TypeSignature, value_type: &TypeSignature,
+                    key_type: TypeSignature) -> TypeResult {
     let option_type = TypeSignature::new_option(value_type.clone())?;
 
-    if !expected_key_type.admits_type(&key_type) {
-        return Err(CheckError::new(CheckErrors::TypeError(expected_key_type.clone(), key_type)))
-    } else {
+    if checker.admits(expected_key_type, &key_type) {
         return Ok(option_type)
     }
+
+    // a map keyed by a single-field tuple may be read with the value of that field alone.
+    match single_field_key_type(expected_key_type) {
+        Some((_, field_type)) if checker.admits(field_type, &key_type) => Ok(option_type),
+        _ => Err(admission_error(expected_key_type, key_type))
+    }
 }
 
 pub fn check_special_delete_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
@@ -62,14 +101,15 @@ pub fn check_special_delete_entry(checker: &mut TypeChecker, args: &[SymbolicExp
     runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, &mut checker.cost_track, expected_key_type.type_size()?)?;
     analysis_typecheck_cost(&mut checker.cost_track, expected_key_type, &key_type)?;
     
-    if !expected_key_type.admits_type(&key_type) {
-        return Err(CheckError::new(CheckErrors::TypeError(expected_key_type.clone(), key_type)))
+    if !checker.admits(expected_key_type, &key_type) {
+        return Err(admission_error(expected_key_type, key_type))
     } else {
         return Ok(TypeSignature::BoolType)
     }
 }
 
-fn check_set_or_insert_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+/// Checks the key and value tuples of a write to a map, returning the map's value type.
+fn check_set_entry_args(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_arguments_at_least(3, args)?;
     
     let map_name = args[0].match_atom()
@@ -87,19 +127,29 @@ fn check_set_or_insert_entry(checker: &mut TypeChecker, args: &[SymbolicExpressi
     analysis_typecheck_cost(&mut checker.cost_track, expected_key_type, &key_type)?;
     analysis_typecheck_cost(&mut checker.cost_track, expected_value_type, &value_type)?;
     
-    if !expected_key_type.admits_type(&key_type) {
-        return Err(CheckError::new(CheckErrors::TypeError(expected_key_type.clone(), key_type)))
-    } else if !expected_value_type.admits_type(&value_type) {
-        return Err(CheckError::new(CheckErrors::TypeError(expected_value_type.clone(), value_type)))
+    if !checker.admits(expected_key_type, &key_type) {
+        return Err(admission_error(expected_key_type, key_type))
+    } else if !checker.admits(expected_value_type, &value_type) {
+        return Err(admission_error(expected_value_type, value_type))
     } else {
-        return Ok(TypeSignature::BoolType)
+        return Ok(expected_value_type.clone())
     }
 }
 
+fn check_set_or_insert_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_set_entry_args(checker, args, context)?;
+    Ok(TypeSignature::BoolType)
+}
+
 pub fn check_special_set_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_set_or_insert_entry(checker, args, context)
 }
 
+pub fn check_special_set_get_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    let value_type = check_set_entry_args(checker, args, context)?;
+    Ok(TypeSignature::new_option(value_type)?)
+}
+
 pub fn check_special_insert_entry(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_set_or_insert_entry(checker, args, context)
 }
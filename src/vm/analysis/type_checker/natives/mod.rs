@@ -1,27 +1,36 @@
 use vm::errors::{Error as InterpError, RuntimeErrorType};
-use vm::functions::{NativeFunctions, handle_binding_list};
+use vm::functions::{NativeFunctions, handle_tuple_field_list, handle_let_binding_list, LetBindingTarget,
+                    parse_event_topic, TEST_ASSERT_EVENT_TOPIC};
+use vm::functions::iterables::Lambda;
 use vm::{ClarityName, SymbolicExpression, SymbolicExpressionType};
-use vm::types::{BUFF_32, BUFF_20, BUFF_64, TypeSignature, TupleTypeSignature,
-                BlockInfoProperty, Value, PrincipalData, MAX_VALUE_SIZE, FunctionArg,
+use vm::ast::parser::CONTRACT_MAX_NAME_LENGTH;
+use vm::types::{BUFF_32, BUFF_20, BUFF_16, BUFF_64, BUFF_33, BUFF_65, BUFF_1, TypeSignature, TupleTypeSignature,
+                Value, PrincipalData, MAX_VALUE_SIZE, WRAPPER_VALUE_SIZE, MAX_UINT_BUFFER_LEN, BufferLength, FunctionArg,
                 FunctionType, FixedFunction, FunctionSignature};
-use super::{TypeChecker, TypingContext, TypeResult, no_type, check_argument_count,
+use super::{TypeChecker, TypingContext, TypeResult, no_type, admission_error, check_argument_count,
             check_arguments_at_least}; 
-use vm::analysis::errors::{CheckError, CheckErrors, CheckResult};
+use vm::analysis::errors::{CheckError, CheckErrors, CheckWarnings, CheckResult};
 use std::convert::TryFrom;
+use std::collections::HashSet;
+use std::cmp;
 
 use vm::costs::{cost_functions, analysis_typecheck_cost, CostOverflowingMath};
 
+pub mod arithmetic;
 mod assets;
 mod iterables;
 mod maps;
 mod options;
 
+#[derive(Clone)]
 pub enum TypedNativeFunction {
     Special(SpecialNativeFunction),
     Simple(SimpleNativeFunction)
 }
 
-pub struct SpecialNativeFunction(&'static dyn Fn(&mut TypeChecker, &[SymbolicExpression], &TypingContext) -> TypeResult);
+#[derive(Clone)]
+pub struct SpecialNativeFunction(pub &'static dyn Fn(&mut TypeChecker, &[SymbolicExpression], &TypingContext) -> TypeResult);
+#[derive(Clone)]
 pub struct SimpleNativeFunction(pub FunctionType);
 
 fn check_special_list_cons(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
@@ -29,7 +38,7 @@ fn check_special_list_cons(checker: &mut TypeChecker, args: &[SymbolicExpression
     for type_arg in typed_args.iter() {
         runtime_cost!(cost_functions::ANALYSIS_LIST_ITEMS_CHECK, checker, type_arg.type_size()?)?;
     }
-    TypeSignature::parent_list_type(&typed_args)
+    TypeSignature::constructed_parent_list_type(&typed_args, checker.options.tuple_width_subtyping)
         .map_err(|x| x.into())
         .map(TypeSignature::from)
 }
@@ -39,6 +48,151 @@ fn check_special_print(checker: &mut TypeChecker, args: &[SymbolicExpression], c
     checker.type_check(&args[0], context)
 }
 
+fn check_special_print_event(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+    let topic = parse_event_topic(&args[0])?;
+    let value_type = checker.type_check(&args[1], context)?;
+    checker.contract_context.add_print_event(topic, value_type.clone());
+    Ok(value_type)
+}
+
+/// Checks a `test-assert`, whose message is recorded like the value of a `print-event`
+///   under the `test-assert` topic.
+fn check_special_test_assert(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+    checker.type_check_expects(&args[0], context, &TypeSignature::BoolType)?;
+    let message_type = checker.type_check_expects(&args[1], context, &TypeSignature::max_buffer())?;
+    checker.contract_context.add_print_event(TEST_ASSERT_EVENT_TOPIC.to_string(), message_type);
+    Ok(TypeSignature::BoolType)
+}
+
+fn check_special_hash_pair(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+    // merkle nodes are exactly 32 bytes, so unlike a fixed function argument,
+    //   shorter buffer types are rejected too.
+    for arg in args.iter() {
+        let node_type = checker.type_check(arg, context)?;
+        if node_type != BUFF_32 {
+            return Err(admission_error(&BUFF_32, node_type))
+        }
+    }
+    Ok(BUFF_32.clone())
+}
+
+/// Hashes, signatures and public keys have fixed encodings, so the buffer
+///   arguments of the secp256k1 functions must be of exactly the expected length.
+fn check_exact_buffer_args(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext,
+                           expected_types: &[TypeSignature]) -> CheckResult<()> {
+    check_argument_count(expected_types.len(), args)?;
+    for (arg, expected_type) in args.iter().zip(expected_types.iter()) {
+        let arg_type = checker.type_check(arg, context)?;
+        if &arg_type != expected_type {
+            return Err(admission_error(expected_type, arg_type))
+        }
+    }
+    Ok(())
+}
+
+fn check_special_secp256k1_recover(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_exact_buffer_args(checker, args, context, &[BUFF_32, BUFF_65])?;
+    Ok(TypeSignature::new_response(BUFF_33, TypeSignature::UIntType)?)
+}
+
+fn check_special_secp256k1_verify(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_exact_buffer_args(checker, args, context, &[BUFF_32, BUFF_65, BUFF_33])?;
+    Ok(TypeSignature::BoolType)
+}
+
+/// The parts of a principal, as returned by `principal-destruct?`. The name of a contract
+///   principal is at most `CONTRACT_MAX_NAME_LENGTH` bytes.
+fn principal_parts_type() -> CheckResult<TypeSignature> {
+    let name_type = TypeSignature::new_option(contract_name_type()?)?;
+    Ok(TupleTypeSignature::try_from(vec![
+        ("version".into(), BUFF_1),
+        ("hash-bytes".into(), BUFF_20),
+        ("name".into(), name_type)])?.into())
+}
+
+fn contract_name_type() -> CheckResult<TypeSignature> {
+    Ok(TypeSignature::BufferType(BufferLength::try_from(CONTRACT_MAX_NAME_LENGTH as u32)?))
+}
+
+/// Checks that `expr` is a buffer of exactly `expected` bytes, so that a version or hash of
+///   the wrong length is caught before it reaches `principal-construct?`.
+fn check_principal_part(checker: &mut TypeChecker, expr: &SymbolicExpression, context: &TypingContext,
+                        expected: &TypeSignature) -> CheckResult<()> {
+    let actual = checker.type_check(expr, context)?;
+    if &actual != expected {
+        return Err(admission_error(expected, actual))
+    }
+    Ok(())
+}
+
+fn check_special_principal_destruct(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+    checker.type_check_expects(&args[0], context, &TypeSignature::PrincipalType)?;
+
+    let parts_type = principal_parts_type()?;
+    Ok(TypeSignature::new_response(parts_type.clone(), parts_type)?)
+}
+
+fn check_special_principal_construct(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_arguments_at_least(2, args)?;
+    if args.len() > 3 {
+        return Err(CheckErrors::IncorrectArgumentCount(3, args.len()).into())
+    }
+
+    check_principal_part(checker, &args[0], context, &BUFF_1)?;
+    check_principal_part(checker, &args[1], context, &BUFF_20)?;
+    if let Some(name) = args.get(2) {
+        checker.type_check_expects(name, context, &contract_name_type()?)?;
+    }
+
+    let error_type = TupleTypeSignature::try_from(vec![
+        ("error_code".into(), TypeSignature::UIntType),
+        ("value".into(), TypeSignature::new_option(TypeSignature::PrincipalType)?)])?;
+    Ok(TypeSignature::new_response(TypeSignature::PrincipalType, error_type.into())?)
+}
+
+fn check_special_buff_to_uint(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+    let input_type = checker.type_check(&args[0], context)?;
+    match input_type {
+        TypeSignature::BufferType(ref len) if u32::from(len) as usize > MAX_UINT_BUFFER_LEN =>
+            Err(CheckErrors::BufferTooLargeForInt(u32::from(len)).into()),
+        TypeSignature::BufferType(_) => Ok(TypeSignature::UIntType),
+        _ => Err(CheckErrors::TypeError(BUFF_16, input_type).into())
+    }
+}
+
+fn check_special_checked_arithmetic(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+    let arg_types = checker.type_check_all(args, context)?;
+    // typed as the arithmetic binary functions, but an overflow is returned as an `err` code.
+    let value_type = FunctionType::ArithmeticBinary.check_args(checker, &arg_types)?;
+    Ok(TypeSignature::new_response(value_type, TypeSignature::UIntType)?)
+}
+
+fn check_special_to_consensus_buff(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+    let input_type = checker.type_check(&args[0], context)?;
+    // the buffer is sized by `max_serialized_size()` rather than `TypeSignature::size()`: a
+    //   serialization prefixes every value with its type, so may be longer than `size()`,
+    //   e.g., 17 bytes for an `int`, whose `size()` is 16.
+    // serializations which cannot fit in an optional buffer evaluate to none.
+    let max_buffer_size = (MAX_VALUE_SIZE - WRAPPER_VALUE_SIZE) as u64;
+    let buffer_size = cmp::min(input_type.max_serialized_size(), max_buffer_size) as u32;
+    Ok(TypeSignature::new_option(TypeSignature::BufferType(BufferLength::try_from(buffer_size)?))?)
+}
+
+fn check_special_from_consensus_buff(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+    let expected_type = TypeSignature::parse_type_repr(&args[0], checker)?;
+    checker.type_map.set_type(&args[0], no_type())?;
+    checker.type_check_expects(&args[1], context, &TypeSignature::max_buffer())?;
+    Ok(TypeSignature::new_option(expected_type)?)
+}
+
 fn check_special_as_contract(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(1, args)?;
     checker.type_check(&args[0], context)
@@ -78,6 +232,8 @@ fn check_special_get(checker: &mut TypeChecker, args: &[SymbolicExpression], con
         
     let argument_type = checker.type_check(&args[1], context)?;
     
+    // a response is not unwrapped implicitly, as the field of an `err` could not be read:
+    //   it is reported as `ExpectedTuple`, with a suggestion to unwrap it first.
     if let TypeSignature::TupleType(tuple_type_sig) = argument_type {
         inner_handle_tuple_get(&tuple_type_sig, field_to_get, checker)
     } else if let TypeSignature::OptionalType(value_type_sig) = argument_type {
@@ -93,14 +249,80 @@ fn check_special_get(checker: &mut TypeChecker, args: &[SymbolicExpression], con
     }
 }
 
+fn check_special_get_in(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+
+    let path = args[1].match_list()
+        .ok_or(CheckErrors::BadTupleFieldName)?;
+    check_arguments_at_least(1, path)?;
+
+    let mut field_type = checker.type_check(&args[0], context)?;
+    let mut is_optional = false;
+
+    for segment in path.iter() {
+        let field_to_get = segment.match_atom()
+            .ok_or(CheckErrors::BadTupleFieldName)?;
+
+        let tuple_type_sig = match field_type {
+            TypeSignature::TupleType(tuple_type_sig) => tuple_type_sig,
+            TypeSignature::OptionalType(value_type_sig) => {
+                is_optional = true;
+                if let TypeSignature::TupleType(tuple_type_sig) = *value_type_sig {
+                    tuple_type_sig
+                } else {
+                    return Err(CheckErrors::ExpectedTuple(*value_type_sig).into())
+                }
+            },
+            _ => return Err(CheckErrors::ExpectedTuple(field_type).into())
+        };
+
+        field_type = inner_handle_tuple_get(&tuple_type_sig, field_to_get, checker)?;
+    }
+
+    if is_optional {
+        Ok(TypeSignature::new_option(field_type)?)
+    } else {
+        Ok(field_type)
+    }
+}
+
+fn check_special_get_at(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(3, args)?;
+
+    let list_type = checker.type_check(&args[0], context)?;
+    checker.type_check_expects(&args[1], context, &TypeSignature::IntType)?;
+    let field_to_get = args[2].match_atom()
+        .ok_or(CheckErrors::BadTupleFieldName)?;
+
+    // the index may be out of bounds, so the field is always read as an optional.
+    if let TypeSignature::ListType(list_type_data) = list_type {
+        let (entry_type, _) = list_type_data.destruct();
+        if let TypeSignature::TupleType(tuple_type_sig) = entry_type {
+            let field_type = inner_handle_tuple_get(&tuple_type_sig, field_to_get, checker)?;
+            Ok(TypeSignature::new_option(field_type)?)
+        } else {
+            Err(CheckErrors::ExpectedTuple(entry_type).into())
+        }
+    } else {
+        Err(CheckErrors::ExpectedListApplication.into())
+    }
+}
+
 pub fn check_special_tuple_cons(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_arguments_at_least(1, args)?;
     
     let mut tuple_type_data = Vec::new();
+    let mut field_names = HashSet::new();
 
     runtime_cost!(cost_functions::ANALYSIS_CHECK_TUPLE_CONS, checker, args.len())?;
 
-    handle_binding_list(args, |var_name, var_sexp| {
+    // a punned field, `(a)`, is typed as the variable `a`
+    handle_tuple_field_list(args, |var_name, var_sexp| {
+        // report a repeated field by name, rather than as a generic
+        //   tuple construction failure.
+        if !field_names.insert(var_name.clone()) {
+            return Err(CheckErrors::NameAlreadyUsed(var_name.to_string()).into())
+        }
         checker.type_check(var_sexp, context)
             .and_then(|var_type| {
                 runtime_cost!(cost_functions::ANALYSIS_TUPLE_ITEMS_CHECK, checker, var_type.type_size()?)?;
@@ -120,23 +342,69 @@ fn check_special_let(checker: &mut TypeChecker, args: &[SymbolicExpression], con
 
     let binding_list = args[0].match_list()
         .ok_or(CheckError::new(CheckErrors::BadLetSyntax))?;
+
+    if binding_list.len() > checker.options.max_let_bindings as usize {
+        return Err(CheckErrors::TooManyBindings(checker.options.max_let_bindings,
+                                                u32::try_from(binding_list.len()).unwrap_or(u32::max_value())).into())
+    }
     
     let mut out_context = context.extend()?;
 
     runtime_cost!(cost_functions::ANALYSIS_CHECK_LET, checker, args.len())?;
 
-    handle_binding_list(binding_list, |var_name, var_sexp| {
-        checker.contract_context.check_name_used(var_name)?;
-        if out_context.lookup_variable_type(var_name).is_some() {
-            return Err(CheckError::new(CheckErrors::NameAlreadyUsed(var_name.to_string())))
+    handle_let_binding_list(binding_list, |target, var_sexp| {
+        let var_names: Vec<&ClarityName> = match &target {
+            LetBindingTarget::Name(var_name) => vec![*var_name],
+            LetBindingTarget::Tuple(fields) => fields.iter().map(|(_, var_name)| *var_name).collect()
+        };
+        for var_name in var_names.iter() {
+            checker.contract_context.check_name_used(var_name)?;
+            if out_context.lookup_variable_type(var_name).is_some() {
+                return Err(CheckError::new(CheckErrors::NameAlreadyUsed(var_name.to_string())))
+            }
         }
 
         let typed_result = checker.type_check(var_sexp, context)?;
-        runtime_cost!(cost_functions::ANALYSIS_BIND_NAME, checker, typed_result.type_size()?)?;
-        out_context.variable_types.insert(var_name.clone(), typed_result);
+
+        let named_types = match target {
+            LetBindingTarget::Name(var_name) => vec![(var_name, typed_result)],
+            LetBindingTarget::Tuple(fields) => {
+                // each field of a tuple pattern must be present in the bound tuple
+                let tuple_type = match typed_result {
+                    TypeSignature::TupleType(tuple_type) => tuple_type,
+                    _ => return Err(CheckErrors::ExpectedTuple(typed_result).into())
+                };
+                let mut named_types = Vec::with_capacity(fields.len());
+                for (field_name, var_name) in fields.into_iter() {
+                    let field_type = tuple_type.field_type(field_name)
+                        .ok_or_else(|| CheckErrors::NoSuchTupleField(field_name.to_string(), tuple_type.clone()))?;
+                    named_types.push((var_name, field_type.clone()));
+                }
+                named_types
+            }
+        };
+
+        for (var_name, var_type) in named_types.into_iter() {
+            // a pattern may not bind the same name twice
+            if out_context.lookup_variable_type(var_name).is_some() {
+                return Err(CheckError::new(CheckErrors::NameAlreadyUsed(var_name.to_string())))
+            }
+
+            runtime_cost!(cost_functions::ANALYSIS_BIND_NAME, checker, var_type.type_size()?)?;
+            out_context.variable_types.insert(var_name.clone(), var_type);
+        }
         Ok(())
     })?;
-    
+
+    // lambdas are only applied in place by `map`, `filter` and `fold`, so a lambda returned
+    //   from a `let` has no type, and would capture bindings that go out of scope.
+    if let Some(lambda) = Lambda::parse(&args[args.len() - 1])? {
+        return match find_captured_binding(lambda.body, &out_context) {
+            Some(name) => Err(CheckErrors::EscapingBinding(name.to_string()).into()),
+            None => Err(CheckErrors::CouldNotDetermineType.into())
+        }
+    }
+
     let mut typed_args = checker.type_check_all(&args[1..args.len()], &out_context)?;
     
     let last_return = typed_args.pop()
@@ -145,18 +413,36 @@ fn check_special_let(checker: &mut TypeChecker, args: &[SymbolicExpression], con
     Ok(last_return)
 }
 
+/// Returns the first name bound by the innermost `let` of `context` that `expr` refers to.
+fn find_captured_binding<'a>(expr: &'a SymbolicExpression, context: &TypingContext) -> Option<&'a ClarityName> {
+    match expr.expr {
+        SymbolicExpressionType::Atom(ref name) if context.variable_types.contains_key(name) => Some(name),
+        SymbolicExpressionType::List(ref children) =>
+            children.iter().filter_map(|child| find_captured_binding(child, context)).next(),
+        _ => None
+    }
+}
+
+/// An unknown data variable, with a suggestion of the closest defined one, if any.
+fn no_such_data_variable(checker: &TypeChecker, var_name: &str) -> CheckError {
+    let mut error = CheckError::new(CheckErrors::NoSuchDataVariable(var_name.to_string()));
+    error.diagnostic.suggestion = checker.contract_context.closest_persisted_variable(var_name)
+        .map(|closest| format!("did you mean '{}'?", closest));
+    error
+}
+
 fn check_special_fetch_var(checker: &mut TypeChecker, args: &[SymbolicExpression], _context: &TypingContext) -> TypeResult {
     check_argument_count(1, args)?;
     
     let var_name = args[0].match_atom()
         .ok_or(CheckError::new(CheckErrors::BadMapName))?;
 
-    let value_type = checker.contract_context.get_persisted_variable_type(var_name)
-        .ok_or(CheckError::new(CheckErrors::NoSuchDataVariable(var_name.to_string())))?;
+    let value_type = checker.get_persisted_variable_type(var_name)
+        .ok_or_else(|| no_such_data_variable(checker, var_name))?;
 
     runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, &mut checker.cost_track, value_type.type_size()?)?;
 
-    Ok(value_type.clone())
+    Ok(value_type)
 }
 
 fn check_special_set_var(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
@@ -167,14 +453,14 @@ fn check_special_set_var(checker: &mut TypeChecker, args: &[SymbolicExpression],
         
     let value_type = checker.type_check(&args[1], context)?;
     
-    let expected_value_type = checker.contract_context.get_persisted_variable_type(var_name)
-        .ok_or(CheckErrors::NoSuchDataVariable(var_name.to_string()))?;
+    let expected_value_type = checker.get_persisted_variable_type(var_name)
+        .ok_or_else(|| no_such_data_variable(checker, var_name))?;
 
     runtime_cost!(cost_functions::ANALYSIS_TYPE_LOOKUP, &mut checker.cost_track, expected_value_type.type_size()?)?;
     analysis_typecheck_cost(&mut checker.cost_track, &value_type, &expected_value_type)?;
 
-    if !expected_value_type.admits_type(&value_type) {
-        return Err(CheckError::new(CheckErrors::TypeError(expected_value_type.clone(), value_type)))
+    if !checker.admits(&expected_value_type, &value_type) {
+        return Err(admission_error(&expected_value_type, value_type))
     } else {
         return Ok(TypeSignature::BoolType)
     }
@@ -185,33 +471,103 @@ fn check_special_equals(checker: &mut TypeChecker, args: &[SymbolicExpression],
 
     let mut arg_types = checker.type_check_all(args, context)?;
 
+    // fails at the first argument which is incompatible with all the arguments before it,
+    //   reporting its position.
     let mut arg_type = arg_types[0].clone();
-    for x_type in arg_types.drain(..) {
+    for (index, x_type) in arg_types.drain(..).enumerate() {
         analysis_typecheck_cost(checker, &x_type, &arg_type)?;
-        arg_type = TypeSignature::least_supertype(&x_type, &arg_type)
-            .map_err(|_| CheckErrors::TypeError(x_type, arg_type))?;
+        arg_type = checker.least_supertype(&x_type, &arg_type)
+            .map_err(|_| CheckErrors::EqualsArgumentTypeMismatch(index, x_type, arg_type))?;
     }
 
     Ok(TypeSignature::BoolType)
 }
 
+/// Matches an `if` condition of the form `(is-some name)` or `(is-none name)`, returning the
+///   guarded name, and whether it is known to be `some` in the `then` branch (`true`)
+///   or in the `else` branch (`false`).
+fn match_optional_guard(condition: &SymbolicExpression) -> Option<(&ClarityName, bool)> {
+    let expressions = condition.match_list()?;
+    if expressions.len() != 2 {
+        return None
+    }
+    let name = expressions[1].match_atom()?;
+    match expressions[0].match_atom().and_then(|f| NativeFunctions::lookup_by_name(f)) {
+        Some(NativeFunctions::IsSome) => Some((name, true)),
+        Some(NativeFunctions::IsNone) => Some((name, false)),
+        _ => None
+    }
+}
+
 fn check_special_if(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(3, args)?;
-    
-    checker.type_check_expects(&args[0], context, &TypeSignature::BoolType)?;
 
-    let arg_types = checker.type_check_all(&args[1..], context)?;
-    
-    let expr1 = &arg_types[0];
-    let expr2 = &arg_types[1];
+    // besides a `bool`, the condition may be an `(optional bool)`, where `none` counts as `false`.
+    let condition_type = checker.type_check(&args[0], context)?;
+    analysis_typecheck_cost(checker, &TypeSignature::BoolType, &condition_type)?;
+    match condition_type {
+        TypeSignature::BoolType => {},
+        TypeSignature::OptionalType(ref inner_type)
+            if **inner_type == TypeSignature::BoolType || inner_type.is_no_type() => {},
+        _ => return Err(CheckErrors::TypeError(TypeSignature::BoolType, condition_type).into())
+    }
+
+    if checker.options.lint {
+        if let Some(Value::Bool(condition)) = checker.constant_value(&args[0]) {
+            checker.add_warning(CheckWarnings::ConstantIfCondition(condition), &args[0]);
+        }
+        if args[1] == args[2] {
+            checker.add_warning_at_expressions(CheckWarnings::IdenticalIfArms, &args[1..]);
+        }
+    }
+
+    // when the condition is an `is-some` or `is-none` guard on a local optional, that
+    //   name is typed as the optional's inner type in the branch where it must be `some`.
+    let refinement = match_optional_guard(&args[0])
+        .and_then(|(name, refines_then_branch)| {
+            match context.lookup_variable_type(name) {
+                Some(TypeSignature::OptionalType(inner_type)) if !inner_type.is_no_type() =>
+                    Some((name, refines_then_branch, (**inner_type).clone())),
+                _ => None
+            }
+        });
+
+    let refined_context = match refinement {
+        Some((name, refines_then_branch, inner_type)) => {
+            runtime_cost!(cost_functions::ANALYSIS_BIND_NAME, checker, inner_type.type_size()?)?;
+            let mut refined_context = context.extend()?;
+            refined_context.variable_types.insert(name.clone(), inner_type);
+            Some((refines_then_branch, refined_context))
+        },
+        None => None
+    };
+
+    let (then_context, else_context) = match refined_context {
+        Some((true, ref refined_context)) => (refined_context, context),
+        Some((false, ref refined_context)) => (context, refined_context),
+        None => (context, context)
+    };
+
+    // an arm which always diverges types as `NoType`, so the `if` takes the other arm's type.
+    //   if both arms diverge, there is no type for the `if` to take.
+    let expr1 = &checker.type_check_arm(&args[1], then_context)?;
+    let expr2 = &checker.type_check_arm(&args[2], else_context)?;
+    if expr1.is_no_type() && expr2.is_no_type() {
+        return Err(CheckErrors::CouldNotDetermineType.into())
+    }
 
     analysis_typecheck_cost(checker, expr1, expr2)?;
 
-    TypeSignature::least_supertype(expr1, expr2)
-        .map_err(|_| CheckErrors::IfArmsMustMatch(expr1.clone(), expr2.clone()).into())
+    // least_supertype recurses through nested types, so a mismatch is reported
+    //   at the innermost types which disagree.
+    checker.least_supertype(expr1, expr2)
+        .map_err(|e| match e {
+            CheckErrors::TypeError(inner1, inner2) => CheckErrors::IfArmsMustMatch(inner1, inner2).into(),
+            _ => CheckErrors::IfArmsMustMatch(expr1.clone(), expr2.clone()).into()
+        })
 }
 
-fn check_contract_call(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+pub fn check_contract_call(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_arguments_at_least(2, args)?;
 
     let func_name = args[1].match_atom()
@@ -223,9 +579,20 @@ fn check_contract_call(checker: &mut TypeChecker, args: &[SymbolicExpression], c
             // Static dispatch
             let contract_call_function = {
                 if let Some(FunctionType::Fixed(function)) = checker.db.get_public_function_type(&contract_identifier, func_name)? {
-                    Ok(function)
+                    // public functions are checked to return a response when they are defined, but the
+                    //   callee's stored analysis may predate that check: report it at the callee's name.
+                    if let TypeSignature::ResponseType(_) = function.returns {
+                        Ok(function)
+                    } else {
+                        let mut err = CheckError::new(CheckErrors::PublicFunctionMustReturnResponse(function.returns));
+                        err.set_expression(&args[1]);
+                        Err(err)
+                    }
                 } else if let Some(FunctionType::Fixed(function)) = checker.db.get_read_only_function_type(&contract_identifier, func_name)? {
                     Ok(function)
+                } else if checker.db.has_private_function(&contract_identifier, func_name)? {
+                    Err(CheckError::new(CheckErrors::ContractCallPrivateFunction(contract_identifier.to_string(),
+                                                                                 func_name.to_string())))
                 } else {
                     Err(CheckError::new(CheckErrors::NoSuchPublicFunction(contract_identifier.to_string(),
                                                                           func_name.to_string())))
@@ -235,6 +602,7 @@ fn check_contract_call(checker: &mut TypeChecker, args: &[SymbolicExpression], c
             let func_signature = FunctionSignature::from(contract_call_function);
 
             runtime_cost!(cost_functions::ANALYSIS_GET_FUNCTION_ENTRY, checker, func_signature.total_type_size()?)?;
+            checker.contract_context.add_dependency(contract_identifier.clone());
 
             func_signature
         },
@@ -260,8 +628,26 @@ fn check_contract_call(checker: &mut TypeChecker, args: &[SymbolicExpression], c
     };
 
     check_argument_count(expected_sig.args.len(), &args[2..])?;
-    for (expected_type, arg) in expected_sig.args.iter().zip(&args[2..]) {
-        checker.type_check_expects(arg, context, expected_type)?;
+    for (index, (expected_type, arg)) in expected_sig.args.iter().zip(&args[2..]).enumerate() {
+        if let TypeSignature::TraitReferenceType(_) = expected_type {
+            // trait arguments are checked for trait compliance rather than admission
+            checker.type_check_expects(arg, context, expected_type)?;
+            continue;
+        }
+
+        let actual_type = checker.type_check(arg, context)?;
+        analysis_typecheck_cost(checker, expected_type, &actual_type)?;
+        if !checker.admits(expected_type, &actual_type) {
+            // name the 1-based position of the argument, which is easy to lose track of
+            //   in calls with many arguments
+            let mut err = match admission_error(expected_type, actual_type) {
+                CheckError { err: CheckErrors::TypeError(expected_type, actual_type), .. } =>
+                    CheckErrors::ContractCallArgumentTypeError(func_name.to_string(), index + 1, expected_type, actual_type).into(),
+                other => other
+            };
+            err.set_expression(arg);
+            return Err(err)
+        }
     }
 
     Ok(expected_sig.returns)
@@ -273,12 +659,12 @@ fn check_get_block_info(checker: &mut TypeChecker, args: &[SymbolicExpression],
     let block_info_prop_str = args[0].match_atom()
         .ok_or(CheckError::new(CheckErrors::GetBlockInfoExpectPropertyName))?;
 
-    let block_info_prop = BlockInfoProperty::lookup_by_name(block_info_prop_str)
+    let block_info_type = checker.options.block_info_properties.lookup(block_info_prop_str)
         .ok_or(CheckError::new(CheckErrors::NoSuchBlockInfoProperty(block_info_prop_str.to_string())))?;
 
     checker.type_check_expects(&args[1], &context, &TypeSignature::UIntType)?;
 
-    Ok(TypeSignature::new_option(block_info_prop.type_result())?)
+    Ok(TypeSignature::new_option(block_info_type)?)
 }
 
 impl TypedNativeFunction {
@@ -315,6 +701,8 @@ impl TypedNativeFunction {
                                                 ClarityName::try_from("value".to_owned())
                                                 .expect("FAIL: ClarityName failed to accept default arg name"))],
                     returns: TypeSignature::IntType }))),
+            BuffToUIntLe | BuffToUIntBe => Special(SpecialNativeFunction(&check_special_buff_to_uint)),
+            CheckedAdd | CheckedSub | CheckedMul => Special(SpecialNativeFunction(&check_special_checked_arithmetic)),
             Not =>
                 Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction { 
                     args: vec![FunctionArg::new(TypeSignature::BoolType, ClarityName::try_from("value".to_owned())
@@ -350,6 +738,19 @@ impl TypedNativeFunction {
                          TypeSignature::UIntType,
                          TypeSignature::IntType],
                     BUFF_32.clone()))),
+            Ripemd160 =>
+                Simple(SimpleNativeFunction(FunctionType::UnionArgs(
+                    vec![TypeSignature::max_buffer(),
+                         TypeSignature::UIntType,
+                         TypeSignature::IntType],
+                    BUFF_20.clone()))),
+            HashPair => Special(SpecialNativeFunction(&check_special_hash_pair)),
+            Secp256k1Recover => Special(SpecialNativeFunction(&check_special_secp256k1_recover)),
+            Secp256k1Verify => Special(SpecialNativeFunction(&check_special_secp256k1_verify)),
+            ToConsensusBuff => Special(SpecialNativeFunction(&check_special_to_consensus_buff)),
+            FromConsensusBuff => Special(SpecialNativeFunction(&check_special_from_consensus_buff)),
+            PrincipalDestruct => Special(SpecialNativeFunction(&check_special_principal_destruct)),
+            PrincipalConstruct => Special(SpecialNativeFunction(&check_special_principal_construct)),
             StxTransfer =>
                 Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
                     args: vec![
@@ -375,7 +776,9 @@ impl TypedNativeFunction {
                                          .expect("FAIL: ClarityName failed to accept default arg name")),
                     ],
                     returns: TypeSignature::new_response(TypeSignature::BoolType, TypeSignature::UIntType).unwrap() }))),
+            StxGetAccount => Special(SpecialNativeFunction(&assets::check_special_stx_account)),
             GetTokenBalance => Special(SpecialNativeFunction(&assets::check_special_get_balance)),
+            GetTokenSupply => Special(SpecialNativeFunction(&assets::check_special_get_supply)),
             GetAssetOwner => Special(SpecialNativeFunction(&assets::check_special_get_owner)),
             TransferToken => Special(SpecialNativeFunction(&assets::check_special_transfer_token)),
             TransferAsset => Special(SpecialNativeFunction(&assets::check_special_transfer_asset)),
@@ -395,13 +798,19 @@ impl TypedNativeFunction {
             Len => Special(SpecialNativeFunction(&iterables::check_special_len)),
             ListCons => Special(SpecialNativeFunction(&check_special_list_cons)),
             FetchEntry => Special(SpecialNativeFunction(&maps::check_special_fetch_entry)),
+            FetchContractEntry => Special(SpecialNativeFunction(&maps::check_special_fetch_contract_entry)),
             SetEntry => Special(SpecialNativeFunction(&maps::check_special_set_entry)),
             InsertEntry => Special(SpecialNativeFunction(&maps::check_special_insert_entry)),
             DeleteEntry => Special(SpecialNativeFunction(&maps::check_special_delete_entry)),
+            SetGetEntry => Special(SpecialNativeFunction(&maps::check_special_set_get_entry)),
             TupleCons => Special(SpecialNativeFunction(&check_special_tuple_cons)),
             TupleGet => Special(SpecialNativeFunction(&check_special_get)),
+            TupleGetIn => Special(SpecialNativeFunction(&check_special_get_in)),
+            TupleGetAt => Special(SpecialNativeFunction(&check_special_get_at)),
             Begin => Special(SpecialNativeFunction(&check_special_begin)),
             Print => Special(SpecialNativeFunction(&check_special_print)),
+            PrintEvent => Special(SpecialNativeFunction(&check_special_print_event)),
+            TestAssert => Special(SpecialNativeFunction(&check_special_test_assert)),
             AsContract => Special(SpecialNativeFunction(&check_special_as_contract)),
             ContractCall => Special(SpecialNativeFunction(&check_contract_call)),
             GetBlockInfo => Special(SpecialNativeFunction(&check_get_block_info)),
@@ -418,6 +827,9 @@ impl TypedNativeFunction {
             Match => Special(SpecialNativeFunction(&options::check_special_match)),
             IsOkay => Special(SpecialNativeFunction(&options::check_special_is_response)),
             IsErr => Special(SpecialNativeFunction(&options::check_special_is_response)),
+            GetOk => Special(SpecialNativeFunction(&options::check_special_get_ok)),
+            GetErr => Special(SpecialNativeFunction(&options::check_special_get_err)),
+            Flatten => Special(SpecialNativeFunction(&options::check_special_flatten)),
             IsNone => Special(SpecialNativeFunction(&options::check_special_is_optional)),
             IsSome => Special(SpecialNativeFunction(&options::check_special_is_optional)),
             AtBlock => Special(SpecialNativeFunction(&check_special_at_block)),
@@ -50,6 +50,52 @@ pub fn check_special_is_response(checker: &mut TypeChecker, args: &[SymbolicExpr
     }
 }
 
+pub fn check_special_get_ok(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+
+    let input = checker.type_check(&args[0], context)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_OPTION_CHECK, checker, 1)?;
+
+    if let TypeSignature::ResponseType(response_type) = input {
+        Ok(TypeSignature::new_option(response_type.0)?)
+    } else {
+        Err(CheckErrors::ExpectedResponseType(input).into())
+    }
+}
+
+pub fn check_special_get_err(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+
+    let input = checker.type_check(&args[0], context)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_OPTION_CHECK, checker, 1)?;
+
+    if let TypeSignature::ResponseType(response_type) = input {
+        Ok(TypeSignature::new_option(response_type.1)?)
+    } else {
+        Err(CheckErrors::ExpectedResponseType(input).into())
+    }
+}
+
+pub fn check_special_flatten(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(1, args)?;
+
+    let input = checker.type_check(&args[0], context)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_OPTION_CHECK, checker, 1)?;
+
+    match input {
+        TypeSignature::OptionalType(ref inner_type) => match **inner_type {
+            TypeSignature::OptionalType(_) => Ok((**inner_type).clone()),
+            // `none` flattens to `none`
+            TypeSignature::NoType => Ok(input.clone()),
+            _ => Err(CheckErrors::ExpectedNestedOptionalType(input.clone()).into())
+        },
+        _ => Err(CheckErrors::ExpectedNestedOptionalType(input).into())
+    }
+}
+
 pub fn check_special_is_optional(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(1, args)?;
     
@@ -74,7 +120,7 @@ pub fn check_special_default_to(checker: &mut TypeChecker, args: &[SymbolicExpre
 
     if let TypeSignature::OptionalType(input_type) = input {
         let contained_type = *input_type;
-        TypeSignature::least_supertype(&default, &contained_type)
+        checker.least_supertype(&default, &contained_type)
             .map_err(|_| CheckErrors::DefaultTypesMustMatch(default, contained_type).into())
     } else {
         return Err(CheckErrors::ExpectedOptionalType(input).into())
@@ -92,12 +138,14 @@ pub fn check_special_asserts(checker: &mut TypeChecker, args: &[SymbolicExpressi
     Ok(TypeSignature::BoolType)
 }
 
-fn inner_unwrap(input: TypeSignature, checker: &mut TypeChecker) -> TypeResult {
+/// Unwraps the `some` or `ok` type of the input. If that type is unknown, the unwrap always
+///   diverges, which is only allowed where `may_diverge` (see `TypeChecker::type_check_arm`).
+fn inner_unwrap(input: TypeSignature, checker: &mut TypeChecker, may_diverge: bool) -> TypeResult {
     runtime_cost!(cost_functions::ANALYSIS_OPTION_CHECK, checker, 1)?;
 
     match input {
         TypeSignature::OptionalType(input_type) => {
-            if input_type.is_no_type() {
+            if input_type.is_no_type() && !may_diverge {
                 Err(CheckErrors::CouldNotDetermineResponseOkType.into())
             } else {
                 Ok(*input_type)
@@ -105,7 +153,7 @@ fn inner_unwrap(input: TypeSignature, checker: &mut TypeChecker) -> TypeResult {
         }
         TypeSignature::ResponseType(response_type) => { 
             let ok_type = response_type.0;
-            if ok_type.is_no_type() {
+            if ok_type.is_no_type() && !may_diverge {
                 Err(CheckErrors::CouldNotDetermineResponseOkType.into())
             } else {
                 Ok(ok_type)
@@ -115,12 +163,12 @@ fn inner_unwrap(input: TypeSignature, checker: &mut TypeChecker) -> TypeResult {
     }
 }
 
-fn inner_unwrap_err(input: TypeSignature, checker: &mut TypeChecker) -> TypeResult {
+fn inner_unwrap_err(input: TypeSignature, checker: &mut TypeChecker, may_diverge: bool) -> TypeResult {
     runtime_cost!(cost_functions::ANALYSIS_OPTION_CHECK, checker, 1)?;
 
     if let TypeSignature::ResponseType(response_type) = input {
         let err_type = response_type.1;
-        if err_type.is_no_type() {
+        if err_type.is_no_type() && !may_diverge {
             Err(CheckErrors::CouldNotDetermineResponseErrType.into())
         } else {
             Ok(err_type)
@@ -138,7 +186,8 @@ pub fn check_special_unwrap_or_ret(checker: &mut TypeChecker, args: &[SymbolicEx
 
     checker.track_return_type(on_error)?;
 
-    inner_unwrap(input, checker)
+    let may_diverge = checker.may_diverge(&args[0]);
+    inner_unwrap(input, checker, may_diverge)
 }
 
 pub fn check_special_unwrap_err_or_ret(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
@@ -149,19 +198,21 @@ pub fn check_special_unwrap_err_or_ret(checker: &mut TypeChecker, args: &[Symbol
 
     checker.track_return_type(on_error)?;
 
-    inner_unwrap_err(input, checker)
+    let may_diverge = checker.may_diverge(&args[0]);
+    inner_unwrap_err(input, checker, may_diverge)
 }
 
 pub fn check_special_try_ret(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(1, args)?;
     
     let input = checker.type_check(&args[0], context)?;
+    let may_diverge = checker.may_diverge(&args[0]);
 
     runtime_cost!(cost_functions::ANALYSIS_OPTION_CHECK, checker, 1)?;
 
     match input {
         TypeSignature::OptionalType(input_type) => {
-            if input_type.is_no_type() {
+            if input_type.is_no_type() && !may_diverge {
                 Err(CheckErrors::CouldNotDetermineResponseOkType.into())
             } else {
                 checker.track_return_type(TypeSignature::new_option(TypeSignature::NoType)?)?;
@@ -170,7 +221,7 @@ pub fn check_special_try_ret(checker: &mut TypeChecker, args: &[SymbolicExpressi
         }
         TypeSignature::ResponseType(response_type) => { 
             let (ok_type, err_type) = *response_type;
-            if ok_type.is_no_type() {
+            if ok_type.is_no_type() && !may_diverge {
                 Err(CheckErrors::CouldNotDetermineResponseOkType.into())
             } else if err_type.is_no_type() {
                 Err(CheckErrors::CouldNotDetermineResponseErrType.into())
@@ -189,7 +240,8 @@ pub fn check_special_unwrap(checker: &mut TypeChecker, args: &[SymbolicExpressio
     
     let input = checker.type_check(&args[0], context)?;
 
-    inner_unwrap(input, checker)
+    let may_diverge = checker.may_diverge(&args[0]);
+    inner_unwrap(input, checker, may_diverge)
 }
 
 pub fn check_special_unwrap_err(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
@@ -197,7 +249,8 @@ pub fn check_special_unwrap_err(checker: &mut TypeChecker, args: &[SymbolicExpre
     
     let input = checker.type_check(&args[0], context)?;
 
-    inner_unwrap_err(input, checker)
+    let may_diverge = checker.may_diverge(&args[0]);
+    inner_unwrap_err(input, checker, may_diverge)
 }
 
 fn eval_with_new_binding(body: &SymbolicExpression, bind_name: ClarityName, bind_type: TypeSignature, 
@@ -241,7 +294,7 @@ fn check_special_match_opt(option_type: TypeSignature, checker: &mut TypeChecker
 
     analysis_typecheck_cost(checker, &some_branch_type, &none_branch_type)?;
 
-    TypeSignature::least_supertype(&some_branch_type, &none_branch_type)
+    checker.least_supertype(&some_branch_type, &none_branch_type)
         .map_err(|_| CheckErrors::MatchArmsMustMatch(some_branch_type, none_branch_type).into())
 }
 
@@ -274,7 +327,7 @@ fn check_special_match_resp(resp_type: (TypeSignature, TypeSignature), checker:
 
     analysis_typecheck_cost(checker, &ok_branch_type, &err_branch_type)?;
 
-    TypeSignature::least_supertype(&ok_branch_type, &err_branch_type)
+    checker.least_supertype(&ok_branch_type, &err_branch_type)
         .map_err(|_| CheckErrors::MatchArmsMustMatch(ok_branch_type, err_branch_type).into())
 }
 
@@ -3,6 +3,7 @@ use vm::types::{TypeSignature, QualifiedContractIdentifier};
 use vm::ast::parse;
 use vm::analysis::errors::CheckErrors;
 use vm::analysis::{AnalysisDatabase, mem_type_check};
+use super::assert_function_returns;
 use std::convert::TryInto;
 
 fn buff_type(size: u32) -> TypeSignature {
@@ -144,14 +145,12 @@ fn test_bad_asset_usage() {
         CheckErrors::NoSuchNFT("stackoos".to_string()),
         CheckErrors::TypeError(buff_type(10),
                                TypeSignature::UIntType),
-        CheckErrors::TypeError(buff_type(10),
-                               buff_type(15)),
+        CheckErrors::ValueTooLarge,
         CheckErrors::BadTokenName,
         CheckErrors::NoSuchNFT("stackoos".to_string()),
         CheckErrors::TypeError(buff_type(10),
                                TypeSignature::UIntType),
-        CheckErrors::TypeError(buff_type(10),
-                               buff_type(15)),
+        CheckErrors::ValueTooLarge,
         CheckErrors::TypeError(TypeSignature::PrincipalType,
                                TypeSignature::UIntType),
         CheckErrors::NoSuchFT("stackoos".to_string()),
@@ -192,3 +191,99 @@ fn test_bad_asset_usage() {
         assert_eq!(&actual_err.err, expected_err);
     }
 }
+
+#[test]
+fn test_stx_account() {
+    let good = "(define-read-only (account (p principal)) (stx-account p))";
+    let (_, analysis) = mem_type_check(good).unwrap();
+    assert_function_returns(&analysis, "account", TypeSignature::from("(tuple (locked uint) (unlock-height uint) (unlocked uint))"));
+
+    let bad = ["(stx-account u1)",
+               "(stx-account tx-sender tx-sender)"];
+    let bad_expected = [CheckErrors::TypeError(TypeSignature::PrincipalType, TypeSignature::UIntType),
+                        CheckErrors::IncorrectArgumentCount(1, 2)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_stx_burn_in_public_function() {
+    let good = "(define-public (burn (amount uint)) (stx-burn? amount tx-sender))";
+    let (_, analysis) = mem_type_check(good).unwrap();
+    assert_function_returns(&analysis, "burn", TypeSignature::new_response(TypeSignature::BoolType, TypeSignature::UIntType).unwrap());
+
+    let bad = ["(define-public (burn (amount int)) (stx-burn? amount tx-sender))",
+               "(define-public (burn (amount uint) (sender (buff 20))) (stx-burn? amount sender))"];
+    let bad_expected = [CheckErrors::TypeError(TypeSignature::UIntType, TypeSignature::IntType),
+                        CheckErrors::TypeError(TypeSignature::PrincipalType, buff_type(20))];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_duplicate_token_names() {
+    let bad = ["(define-fungible-token stackaroos)
+                (define-fungible-token stackaroos u100)",
+               "(define-non-fungible-token stackaroos (buff 10))
+                (define-non-fungible-token stackaroos uint)",
+               "(define-fungible-token stackaroos)
+                (define-non-fungible-token stackaroos (buff 10))",
+               "(define-non-fungible-token stackaroos (buff 10))
+                (define-fungible-token stackaroos)",
+               "(define-read-only (stackaroos) u1)
+                (define-fungible-token stackaroos)"];
+
+    for bad_test in bad.iter() {
+        assert_eq!(CheckErrors::NameAlreadyUsed("stackaroos".to_string()),
+                   mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_get_supply() {
+    let good = "(define-fungible-token stackaroos)
+                (define-read-only (supply) (ft-get-supply stackaroos))";
+    let (_, analysis) = mem_type_check(good).unwrap();
+    assert_function_returns(&analysis, "supply", TypeSignature::UIntType);
+
+    let bad = ["(define-fungible-token stackaroos)
+                (ft-get-supply stackoos)",
+               "(define-non-fungible-token stackaroos uint)
+                (ft-get-supply stackaroos)",
+               "(define-fungible-token stackaroos)
+                (ft-get-supply stackaroos stackaroos)"];
+    let bad_expected = [CheckErrors::NoSuchFT("stackoos".to_string()),
+                        CheckErrors::NoSuchFT("stackaroos".to_string()),
+                        CheckErrors::IncorrectArgumentCount(1, 2)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_asset_read_return_types() {
+    let contract = "(define-fungible-token stackaroos)
+                    (define-non-fungible-token stacka-nfts (buff 10))
+                    (define-read-only (owner (id (buff 10))) (nft-get-owner? stacka-nfts id))
+                    (define-read-only (balance (account principal)) (ft-get-balance stackaroos account))";
+    let (_, analysis) = mem_type_check(contract).unwrap();
+
+    assert_function_returns(&analysis, "owner", TypeSignature::new_option(TypeSignature::PrincipalType).unwrap());
+    assert_function_returns(&analysis, "balance", TypeSignature::UIntType);
+
+    let bad = ["(define-fungible-token stackaroos)
+                (nft-get-owner? stackaroos u1)",
+               "(define-non-fungible-token stacka-nfts uint)
+                (ft-get-balance stacka-nfts tx-sender)"];
+    let bad_expected = [CheckErrors::NoSuchNFT("stackaroos".to_string()),
+                        CheckErrors::NoSuchFT("stacka-nfts".to_string())];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
@@ -5,9 +5,13 @@ use vm::ast::parse;
 use vm::analysis::errors::CheckErrors;
 use vm::analysis::{AnalysisDatabase, contract_interface_builder::build_contract_interface};
 use vm::database::MemoryBackingStore;
+use vm::costs::LimitedCostTracker;
 use vm::analysis::mem_type_check;
 use vm::analysis::type_check;
-use vm::types::QualifiedContractIdentifier;
+use vm::analysis::{run_analysis_with_options, AnalysisOptions};
+use vm::analysis::type_checker::{type_check_contract_call_expr, type_check_expr_expects};
+use vm::analysis::types::ContractAnalysis;
+use vm::types::{QualifiedContractIdentifier, TypeSignature, FunctionType, FixedFunction};
 
 const SIMPLE_TOKENS: &str =
         "(define-map tokens ((account principal)) ((balance uint)))
@@ -401,9 +405,9 @@ fn test_names_tokens_contracts_bad() {
 
     let err = db.execute(|db| type_check(&names_contract_id, &mut names_contract, db, true)).unwrap_err();
     assert!(match &err.err {
-            &CheckErrors::TypeError(ref expected_type, ref actual_type) => {
+            &CheckErrors::ContractCallArgumentTypeError(_, position, ref expected_type, ref actual_type) => {
                 eprintln!("Received TypeError on: {} {}", expected_type, actual_type);
-                format!("{} {}", expected_type, actual_type) == "uint bool"
+                format!("{} {} {}", position, expected_type, actual_type) == "2 uint bool"
             },
             _ => false
     });
@@ -488,6 +492,30 @@ fn test_same_function_name() {
     }).unwrap();
 }
 
+#[test]
+fn test_contract_call_private_function() {
+    let ca_id = QualifiedContractIdentifier::local("contract-a").unwrap();
+    let cb_id = QualifiedContractIdentifier::local("contract-b").unwrap();
+
+    let contract_b =
+        "(define-private (hidden (a int)) (+ a 1))";
+
+    let contract_a =
+        "(define-read-only (call-hidden (a int))
+           (contract-call? .contract-b hidden a))";
+
+    let mut ca = parse(&ca_id, contract_a).unwrap();
+    let mut cb = parse(&cb_id, contract_b).unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let err = db.execute(|db| {
+        type_check(&cb_id, &mut cb, db, true)?;
+        type_check(&ca_id, &mut ca, db, true)
+    }).unwrap_err();
+    assert_eq!(err.err, CheckErrors::ContractCallPrivateFunction(cb_id.to_string(), "hidden".to_string()));
+}
+
 #[test]
 fn test_expects() {
     use vm::analysis::type_check;
@@ -578,3 +606,195 @@ fn test_expects() {
     });
 
 }
+
+#[test]
+fn test_standalone_contract_call_expr() {
+    let contract_id = QualifiedContractIdentifier::local("c").unwrap();
+    let mut contract = parse(&contract_id,
+                             "(define-read-only (get-value (x uint)) (+ x u1))").unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    db.execute(|db| type_check(&contract_id, &mut contract, db, true)).unwrap();
+
+    let query_id = QualifiedContractIdentifier::transient();
+    let good = parse(&query_id, "(contract-call? .c get-value u1)").unwrap();
+    let bad_arg = parse(&query_id, "(contract-call? .c get-value 1)").unwrap();
+    let bad_function = parse(&query_id, "(contract-call? .c set-value u1)").unwrap();
+    let not_a_call = parse(&query_id, "(+ 1 2)").unwrap();
+
+    assert_eq!(db.execute(|db| type_check_contract_call_expr(db, &good[0])).unwrap(),
+               TypeSignature::UIntType);
+    assert_eq!(db.execute(|db| type_check_contract_call_expr(db, &bad_arg[0])).unwrap_err().err,
+               CheckErrors::ContractCallArgumentTypeError("get-value".to_string(), 1,
+                                                          TypeSignature::UIntType, TypeSignature::IntType));
+    assert_eq!(db.execute(|db| type_check_contract_call_expr(db, &bad_function[0])).unwrap_err().err,
+               CheckErrors::NoSuchPublicFunction(contract_id.to_string(), "set-value".to_string()));
+    assert_eq!(db.execute(|db| type_check_contract_call_expr(db, &not_a_call[0])).unwrap_err().err,
+               CheckErrors::IllegalOrUnknownFunctionApplication("+".to_string()));
+}
+
+#[test]
+fn test_contract_call_argument_position() {
+    let callee_src = "(define-public (transfer (amount uint) (sender principal) (memo (buff 4)) (fee uint))
+                        (ok amount))";
+    let caller_src = "(define-public (pay)
+                        (contract-call? .callee transfer u10 tx-sender 10 u1))";
+
+    let callee_id = QualifiedContractIdentifier::local("callee").unwrap();
+    let caller_id = QualifiedContractIdentifier::local("caller").unwrap();
+    let mut callee = parse(&callee_id, callee_src).unwrap();
+    let mut caller = parse(&caller_id, caller_src).unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    db.execute(|db| type_check(&callee_id, &mut callee, db, true)).unwrap();
+    let err = db.execute(|db| type_check(&caller_id, &mut caller, db, true)).unwrap_err();
+
+    assert_eq!(err.err, CheckErrors::ContractCallArgumentTypeError("transfer".to_string(), 3,
+                                                                    TypeSignature::from("(buff 4)"),
+                                                                    TypeSignature::IntType));
+    assert_eq!(err.diagnostic.message, "argument 3 of call to 'transfer' expects type '(buff 4)', found 'int'");
+}
+
+#[test]
+fn test_contract_call_stale_non_response() {
+    // an analysis stored before public functions were required to return a response
+    let callee_id = QualifiedContractIdentifier::local("callee").unwrap();
+    let mut callee = ContractAnalysis::new(callee_id.clone(), vec![], LimitedCostTracker::new_max_limit());
+    callee.public_function_types.insert("get-count".into(),
+                                        FunctionType::Fixed(FixedFunction { args: vec![], returns: TypeSignature::IntType }));
+
+    let caller_id = QualifiedContractIdentifier::local("caller").unwrap();
+    let mut caller = parse(&caller_id, "(define-public (count) (contract-call? .callee get-count))").unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    db.execute(|db| db.insert_contract(&callee_id, &callee)).unwrap();
+    let err = db.execute(|db| type_check(&caller_id, &mut caller, db, true)).unwrap_err();
+
+    assert_eq!(err.err, CheckErrors::PublicFunctionMustReturnResponse(TypeSignature::IntType));
+    // the error is reported at the callee's name
+    assert_eq!(err.expressions.unwrap()[0].match_atom().unwrap().as_str(), "get-count");
+}
+
+#[test]
+fn test_standalone_expr_expects() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let query_id = QualifiedContractIdentifier::transient();
+    let sum = parse(&query_id, "(+ 1 2)").unwrap();
+
+    db.execute(|db| type_check_expr_expects(db, &sum[0], &TypeSignature::IntType)).unwrap();
+    assert_eq!(db.execute(|db| type_check_expr_expects(db, &sum[0], &TypeSignature::BoolType)).unwrap_err().err,
+               CheckErrors::TypeError(TypeSignature::BoolType, TypeSignature::IntType));
+}
+
+#[test]
+fn test_contract_map_get() {
+    let kv_id = QualifiedContractIdentifier::local("kv").unwrap();
+    let reader_id = QualifiedContractIdentifier::local("reader").unwrap();
+    let mut kv = parse(&kv_id,
+                       "(define-map store ((key int)) ((value uint)))").unwrap();
+    let mut reader = parse(&reader_id,
+                           "(define-read-only (lookup (key int)) (contract-map-get? .kv store ((key key))))
+                            (define-read-only (lookup-field (key int)) (contract-map-get? .kv store key))").unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    db.execute(|db| type_check(&kv_id, &mut kv, db, true)).unwrap();
+    let analysis = db.execute(|db| type_check(&reader_id, &mut reader, db, true)).unwrap();
+
+    for function in ["lookup", "lookup-field"].iter() {
+        match analysis.get_read_only_function_type(function) {
+            Some(FunctionType::Fixed(FixedFunction { returns, .. })) =>
+                assert_eq!(returns, &TypeSignature::from("(optional (tuple (value uint)))")),
+            _ => panic!("{} should be a fixed function", function)
+        }
+    }
+    assert!(analysis.dependencies.contains(&kv_id));
+
+    let bad = ["(contract-map-get? .kv missing ((key 1)))",
+               "(contract-map-get? .unknown store ((key 1)))",
+               "(contract-map-get? .kv store ((key u1)))",
+               "(contract-map-get? kv store ((key 1)))"];
+    let bad_expected = [
+        CheckErrors::NoSuchMap("missing".to_string()),
+        CheckErrors::NoSuchContract(QualifiedContractIdentifier::local("unknown").unwrap().to_string()),
+        CheckErrors::TypeError(TypeSignature::from("(tuple (key int))"), TypeSignature::from("(tuple (key uint))")),
+        CheckErrors::ContractCallExpectName];
+
+    let bad_id = QualifiedContractIdentifier::local("bad").unwrap();
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        let mut bad_contract = parse(&bad_id, bad_test).unwrap();
+        let err = db.execute(|db| type_check(&bad_id, &mut bad_contract, db, false)).unwrap_err();
+        assert_eq!(expected, &err.err);
+    }
+}
+
+#[test]
+fn test_contract_analysis_summary() {
+    let defs_id = QualifiedContractIdentifier::local("defs").unwrap();
+    let mut defs = parse(&defs_id,
+                         "(define-trait getter ((get-value () (response uint uint))))
+                          (define-public (ping) (ok true))").unwrap();
+
+    let summary_id = QualifiedContractIdentifier::local("summary").unwrap();
+    let mut summary = parse(&summary_id,
+                            "(use-trait getter .defs.getter)
+                             (define-constant limit u10)
+                             (define-data-var counter uint u0)
+                             (define-map balances ((owner principal)) ((amount uint)))
+                             (define-fungible-token coin)
+                             (define-non-fungible-token badge uint)
+                             (define-private (clamp (x uint)) (if true (if (> x limit) limit x) x))
+                             (define-read-only (get-counter) (var-get counter))
+                             (define-public (read-from (source <getter>))
+                               (contract-call? source get-value))
+                             (define-public (ping-defs) (contract-call? .defs ping))").unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    db.execute(|db| type_check(&defs_id, &mut defs, db, true)).unwrap();
+
+    let options = AnalysisOptions { lint: true, ..AnalysisOptions::default() };
+    let analysis = run_analysis_with_options(&summary_id, &mut summary, &mut db, false,
+                                             LimitedCostTracker::new_max_limit(), options)
+        .map_err(|(e, _)| e).unwrap();
+
+    assert!(analysis.public_function_types.contains_key("read-from"));
+    assert!(analysis.public_function_types.contains_key("ping-defs"));
+    assert!(analysis.read_only_function_types.contains_key("get-counter"));
+    assert!(analysis.private_function_types.contains_key("clamp"));
+    assert_eq!(analysis.variable_types.get("limit"), Some(&TypeSignature::UIntType));
+    assert_eq!(analysis.persisted_variable_types.get("counter"), Some(&TypeSignature::UIntType));
+    assert!(analysis.map_types.contains_key("balances"));
+    assert!(analysis.fungible_tokens.contains("coin"));
+    assert_eq!(analysis.non_fungible_tokens.get("badge"), Some(&TypeSignature::UIntType));
+    assert_eq!(analysis.dependencies.iter().collect::<Vec<_>>(), vec![&defs_id]);
+    assert_eq!(analysis.diagnostics.len(), 1);
+}
+
+#[test]
+fn test_used_natives() {
+    use vm::functions::NativeFunctions;
+    use std::collections::HashSet;
+
+    let contract = "(define-data-var counter uint u0)
+                    (define-public (add-all (amounts (list 5 uint)))
+                      (begin (var-set counter (fold + amounts (var-get counter)))
+                             (ok (var-get counter))))";
+
+    let (_, analysis) = mem_type_check(contract).unwrap();
+
+    let expected: HashSet<_> = vec![NativeFunctions::Begin, NativeFunctions::SetVar, NativeFunctions::Fold,
+                                    NativeFunctions::Add, NativeFunctions::FetchVar, NativeFunctions::ConsOkay]
+        .into_iter().collect();
+    assert_eq!(analysis.used_natives(), &expected);
+}
@@ -1,19 +1,22 @@
 use vm::ast::{parse, build_ast};
 use vm::ast::errors::ParseErrors;
 use vm::representations::SymbolicExpression;
-use vm::analysis::type_checker::{TypeResult, TypeChecker, TypingContext};
+use vm::analysis::type_checker::{TypeResult, TypeChecker, TypingContext, TypedNativeFunction,
+                                 SimpleNativeFunction, SpecialNativeFunction};
 use vm::analysis::{AnalysisDatabase};
-use vm::analysis::errors::CheckErrors;
-use vm::analysis::mem_type_check;
-use vm::analysis::type_check;
+use vm::analysis::errors::{CheckErrors, CheckWarnings};
+use vm::diagnostic::{Diagnostic, Level, diagnostics_to_json};
+use vm::analysis::{mem_type_check, mem_type_check_with_options, AnalysisOptions, CustomNatives, BlockInfoRegistry, FunctionKind};
+use vm::analysis::{type_check, run_analysis_diagnostics};
+use vm::costs::LimitedCostTracker;
 use vm::analysis::types::ContractAnalysis;
 use vm::contexts::{OwnedEnvironment};
-use vm::types::{Value, PrincipalData, TypeSignature, FunctionType, FixedFunction, BUFF_32, BUFF_64,
-                QualifiedContractIdentifier};
+use vm::types::{Value, PrincipalData, TypeSignature, TupleTypeSignature, FunctionType, FixedFunction,
+                FunctionArg, QualifiedContractIdentifier, MAX_LIST_LENGTH};
 
 use vm::database::MemoryBackingStore;
 use vm::types::TypeSignature::{IntType, BoolType, BufferType, UIntType, PrincipalType};
-use std::convert::TryInto;
+use std::convert::{TryInto, TryFrom};
 
 mod assets;
 mod contracts;
@@ -55,6 +58,25 @@ fn test_get_block_info(){
     }
 }
 
+#[test]
+fn test_custom_block_info_property() {
+    let mut block_info_properties = BlockInfoRegistry::default();
+    block_info_properties.register("burn-block-height".into(), UIntType);
+    let options = AnalysisOptions { block_info_properties, ..AnalysisOptions::default() };
+
+    let (type_sig, _) = mem_type_check_with_options("(get-block-info? burn-block-height u1)", options.clone()).unwrap();
+    assert_eq!(type_sig.unwrap(), TypeSignature::new_option(UIntType).unwrap());
+
+    // the built-in properties are still resolved
+    let (type_sig, _) = mem_type_check_with_options("(get-block-info? time u1)", options.clone()).unwrap();
+    assert_eq!(type_sig.unwrap(), TypeSignature::new_option(UIntType).unwrap());
+
+    assert_eq!(mem_type_check_with_options("(get-block-info? burn-block-hash u1)", options).unwrap_err().err,
+               CheckErrors::NoSuchBlockInfoProperty("burn-block-hash".to_string()));
+    assert_eq!(mem_type_check("(get-block-info? burn-block-height u1)").unwrap_err().err,
+               CheckErrors::NoSuchBlockInfoProperty("burn-block-height".to_string()));
+}
+
 #[test]
 fn test_define_trait(){
     let good = [
@@ -167,6 +189,63 @@ fn test_stx_ops(){
     }
 }
 
+#[test]
+fn test_hash_pair() {
+    let good = [format!("(hash-pair {} {})", "0x0000000000000000000000000000000000000000000000000000000000000000", "0x0101010101010101010101010101010101010101010101010101010101010101"),
+                format!("(define-private (root (leaf (buff 32)) (sibling (buff 32))) (hash-pair leaf sibling))
+                         (root (sha256 1) (keccak256 2))")];
+    for good_test in good.iter() {
+        assert_eq!("(buff 32)", &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+
+    let bad = [format!("(hash-pair {} 0x0102)", "0x0000000000000000000000000000000000000000000000000000000000000000"),
+               format!("(hash-pair (sha512 1) {})", "0x0000000000000000000000000000000000000000000000000000000000000000"),
+               format!("(hash-pair {} u1)", "0x0000000000000000000000000000000000000000000000000000000000000000"),
+               format!("(hash-pair {})", "0x0000000000000000000000000000000000000000000000000000000000000000")];
+    let bad_expected = [CheckErrors::TypeError(BufferType(32_u32.try_into().unwrap()),
+                                               BufferType(2_u32.try_into().unwrap())),
+                        CheckErrors::ValueTooLarge,
+                        CheckErrors::TypeError(BufferType(32_u32.try_into().unwrap()), UIntType),
+                        CheckErrors::IncorrectArgumentCount(2, 1)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_secp256k1() {
+    let hash = "0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+    let signature = "0x00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6";
+    let pubkey = "0x0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219";
+
+    let good = [(format!("(secp256k1-recover? {} {})", hash, signature), "(response (buff 33) uint)"),
+                (format!("(secp256k1-verify {} {} {})", hash, signature, pubkey), "bool"),
+                (format!("(secp256k1-verify (sha256 1) {} (unwrap-panic (secp256k1-recover? (sha256 1) {})))", signature, signature), "bool")];
+    for (good_test, expected) in good.iter() {
+        assert_eq!(expected, &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+
+    // each argument must be a buffer of exactly the expected length
+    let bad = [format!("(secp256k1-recover? (hash160 1) {})", signature),
+               format!("(secp256k1-recover? {} {})", hash, pubkey),
+               format!("(secp256k1-verify {} {} {})", hash, signature, hash),
+               format!("(secp256k1-verify {} {} u1)", hash, signature),
+               format!("(secp256k1-verify {} {})", hash, signature)];
+    let bad_expected = [CheckErrors::TypeError(BufferType(32_u32.try_into().unwrap()),
+                                               BufferType(20_u32.try_into().unwrap())),
+                        CheckErrors::TypeError(BufferType(65_u32.try_into().unwrap()),
+                                               BufferType(33_u32.try_into().unwrap())),
+                        CheckErrors::TypeError(BufferType(33_u32.try_into().unwrap()),
+                                               BufferType(32_u32.try_into().unwrap())),
+                        CheckErrors::TypeError(BufferType(33_u32.try_into().unwrap()), UIntType),
+                        CheckErrors::IncorrectArgumentCount(3, 2)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_destructuring_opts(){
     let good = [
@@ -176,9 +255,9 @@ fn test_destructuring_opts(){
         "(unwrap-panic (ok 3))",
         "(unwrap-panic (some 3))",
         "(unwrap-err-panic (err 3))",
-        "(match (some 1) inner-value (+ 1 inner-value) (/ 1 0))",
+        "(match (some 1) inner-value (+ 1 inner-value) (/ 1 1))",
         "(define-private (foo) (if (> 1 0) (ok 1) (err 8)))
-         (match (foo) ok-val (+ 1 ok-val) err-val (/ err-val 0))",
+         (match (foo) ok-val (+ 1 ok-val) err-val (/ err-val 1))",
         "(define-private (t1 (x uint)) (if (> x u1) (ok x) (err false)))
          (define-private (t2 (x uint))
            (if (> x u4)
@@ -222,14 +301,14 @@ fn test_destructuring_opts(){
          CheckErrors::CouldNotDetermineResponseOkType),
         ("(unwrap-panic (err 3))",
          CheckErrors::CouldNotDetermineResponseOkType),
-        ("(match none inner-value (/ 1 0) (+ 1 8))",
+        ("(match none inner-value (/ 1 1) (+ 1 8))",
          CheckErrors::CouldNotDetermineMatchTypes),
-        ("(match (ok 1) ok-val (/ ok-val 0) err-val (+ err-val 7))",
+        ("(match (ok 1) ok-val (/ ok-val 1) err-val (+ err-val 7))",
          CheckErrors::CouldNotDetermineMatchTypes),
-        ("(match (err 1) ok-val (/ ok-val 0) err-val (+ err-val 7))",
+        ("(match (err 1) ok-val (/ ok-val 1) err-val (+ err-val 7))",
          CheckErrors::CouldNotDetermineMatchTypes),
         ("(define-private (foo) (if (> 1 0) (ok 1) (err u8)))
-         (match (foo) ok-val (+ 1 ok-val) err-val (/ err-val u0))",
+         (match (foo) ok-val (+ 1 ok-val) err-val (/ err-val u1))",
          CheckErrors::MatchArmsMustMatch(TypeSignature::IntType, TypeSignature::UIntType)),
         ("(match (some 1) inner-value (+ 1 inner-value) (> 1 28))",
          CheckErrors::MatchArmsMustMatch(TypeSignature::IntType, TypeSignature::BoolType)),
@@ -250,7 +329,7 @@ fn test_destructuring_opts(){
              CheckErrors::ExpectedName))),
         ("(match)",
          CheckErrors::RequiresAtLeastArguments(1, 0)),
-        ("(match 1 ok-val (/ ok-val 0) err-val (+ err-val 7))",
+        ("(match 1 ok-val (/ ok-val 1) err-val (+ err-val 7))",
          CheckErrors::BadMatchInput(TypeSignature::from("int"))),
         ("(default-to 3 5)",
          CheckErrors::ExpectedOptionalType(TypeSignature::IntType)),
@@ -299,7 +378,7 @@ fn test_destructuring_opts(){
 fn test_at_block(){
     let good = [("(at-block (sha256 u0) u1)", "uint")];
 
-    let bad = [("(at-block (sha512 u0) u1)", CheckErrors::TypeError(BUFF_32.clone(), BUFF_64.clone())),
+    let bad = [("(at-block (sha512 u0) u1)", CheckErrors::ValueTooLarge),
                ("(at-block (sha256 u0) u1 u2)", CheckErrors::IncorrectArgumentCount(2, 3))];
 
     for (good_test, expected) in good.iter() {
@@ -361,6 +440,95 @@ fn test_simple_arithmetic_checks() {
     }
 }
 
+#[test]
+fn test_checked_arithmetic() {
+    let good = ["(+! 1 2)",
+                "(-! u1 u2)",
+                "(*! 2 3)",
+                "(unwrap-panic (+! u1 u2))"];
+    let expected = ["(response int uint)", "(response uint uint)", "(response int uint)", "uint"];
+    let bad = ["(+! 1 u2)",
+               "(-! true false)",
+               "(*! 1 2 3)",
+               "(+! 1)"];
+    let bad_expected = [ CheckErrors::TypeError(IntType, UIntType),
+                         CheckErrors::UnionTypeError(vec![IntType, UIntType], BoolType),
+                         CheckErrors::IncorrectArgumentCount(2, 3),
+                         CheckErrors::IncorrectArgumentCount(2, 1) ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_constant_folded_arithmetic() {
+    let good = ["(+ 1 2 (* 3 4))",
+                "(- u10 u3)",
+                "(/ 10 (- 5 3))",
+                "(- 170141183460469231731687303715884105727)",
+                "(* 2 (+ 1 (- 1 1)))"];
+    let expected = ["int", "uint", "int", "int", "int"];
+    let bad = ["(+ 170141183460469231731687303715884105727 1)",
+               "(* u340282366920938463463374607431768211455 u2)",
+               "(- u1 u2)",
+               "(+ 1 (* 170141183460469231731687303715884105727 2))",
+               "(/ 10 0)",
+               "(mod u10 u0)",
+               "(/ 10 (- 5 5))"];
+    let bad_expected = [ CheckErrors::ArithmeticOverflow,
+                         CheckErrors::ArithmeticOverflow,
+                         CheckErrors::ArithmeticOverflow,
+                         CheckErrors::ArithmeticOverflow,
+                         CheckErrors::DivideByZero,
+                         CheckErrors::DivideByZero,
+                         CheckErrors::DivideByZero ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_nested_constant_folding() {
+    // each application is folded once, from the values of its already folded arguments.
+    let depth = 32;
+    let nested = format!("{}0{}", "(+ 1 ".repeat(depth), ")".repeat(depth));
+
+    assert_eq!("int", &format!("{}", type_check_helper(&nested).unwrap()));
+    assert_eq!(CheckErrors::DivideByZero,
+               type_check_helper(&format!("(/ 1 (- {} {}))", nested, depth)).unwrap_err().err);
+    assert_eq!(CheckErrors::ArithmeticOverflow,
+               type_check_helper(&format!("(* {} 170141183460469231731687303715884105727)", nested)).unwrap_err().err);
+}
+
+#[test]
+fn test_literal_zero_divisor() {
+    let good = ["(define-private (halve (x int)) (/ x 2)) (halve 4)",
+                "(define-private (divide (x int) (y int)) (/ x y)) (divide 4 0)"];
+    let bad = ["(/ 1 0)",
+               "(mod 1 0)",
+               "(define-private (zero (x int)) (/ x 0))",
+               "(define-private (zero (x uint)) (mod x u1 u0))",
+               "(define-private (zero (x int)) (/ x (- 2 2)))"];
+
+    for good_test in good.iter() {
+        mem_type_check(good_test).unwrap();
+    }
+
+    for bad_test in bad.iter() {
+        assert_eq!(CheckErrors::DivideByZero, mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_simple_hash_checks() {
     let good = ["(hash160 u1)",
@@ -370,10 +538,14 @@ fn test_simple_hash_checks() {
                 "(sha512/256 u10)",
                 "(sha512/256 10)",
                 "(sha256 (keccak256 u1))",
-                "(sha256 (keccak256 1))"];
-    let expected = ["(buff 20)", "(buff 20)", "(buff 64)", "(buff 64)", "(buff 32)", "(buff 32)", "(buff 32)", "(buff 32)" ];
+                "(sha256 (keccak256 1))",
+                "(ripemd160 u1)",
+                "(ripemd160 (sha256 1))"];
+    let expected = ["(buff 20)", "(buff 20)", "(buff 64)", "(buff 64)", "(buff 32)", "(buff 32)", "(buff 32)", "(buff 32)",
+                    "(buff 20)", "(buff 20)" ];
 
     let bad_types = ["(hash160 true)",
+                     "(ripemd160 true)",
                      "(sha256 false)",
                      "(sha512 false)",
                      "(sha512/256 false)",
@@ -404,19 +576,23 @@ fn test_simple_ifs() {
     let good = ["(if (> 1 2) (+ 1 2 3) (- 1 2))",
                 "(if true true false)",
                 "(if true \"abcdef\" \"abc\")",
-                "(if true \"a\" \"abcdef\")" ];
-    let expected = [ "int", "bool", "(buff 6)", "(buff 6)" ];
+                "(if true \"a\" \"abcdef\")",
+                "(if (some true) 1 2)",
+                "(if none u1 u2)" ];
+    let expected = [ "int", "bool", "(buff 6)", "(buff 6)", "int", "uint" ];
 
     let bad = ["(if true true 1)",
                "(if true \"a\" false)",
                "(if)",
-               "(if 0 1 0)"];
+               "(if 0 1 0)",
+               "(if (some 1) 1 0)"];
 
     let bad_expected = [
         CheckErrors::IfArmsMustMatch(BoolType, IntType),
         CheckErrors::IfArmsMustMatch(buff_type(1), BoolType),
         CheckErrors::IncorrectArgumentCount(3, 0),
-        CheckErrors::TypeError(BoolType, IntType)
+        CheckErrors::TypeError(BoolType, IntType),
+        CheckErrors::TypeError(BoolType, TypeSignature::new_option(IntType).unwrap())
     ];
 
     for (good_test, expected) in good.iter().zip(expected.iter()) {
@@ -428,6 +604,333 @@ fn test_simple_ifs() {
     }
 }
 
+/// Asserts that the function defined as `name`, of whichever kind, has a fixed type returning `expected`.
+fn assert_function_returns(analysis: &ContractAnalysis, name: &str, expected: TypeSignature) {
+    match analysis.get_function(name) {
+        Some((_, FunctionType::Fixed(FixedFunction { returns, .. }))) => assert_eq!(returns, &expected),
+        _ => panic!("expected a fixed function type for {}", name)
+    }
+}
+
+fn lint_helper(exp: &str) -> Vec<Diagnostic> {
+    mem_type_check_with_options(exp, AnalysisOptions { lint: true, ..AnalysisOptions::default() })
+        .unwrap().1.diagnostics
+}
+
+#[test]
+fn test_constant_if_conditions() {
+    let tests = ["(if true 1 2)",
+                 "(if false 1 2)",
+                 "(if (is-eq 1 (- 2 1)) 1 2)",
+                 "(if (is-eq u1 u2) 1 2)"];
+    let expected = [ CheckWarnings::ConstantIfCondition(true),
+                     CheckWarnings::ConstantIfCondition(false),
+                     CheckWarnings::ConstantIfCondition(true),
+                     CheckWarnings::ConstantIfCondition(false) ];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        let diagnostics = lint_helper(test);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, Level::Warning);
+        assert_eq!(diagnostics[0].message, Diagnostic::warn(expected).message);
+        assert_eq!(diagnostics[0].spans[0].start_column, 5);
+        // the lint does not alter the type of the if expression
+        assert_eq!("int", &format!("{}", type_check_helper(test).unwrap()));
+    }
+
+    assert!(lint_helper("(define-private (f (x bool)) (if x 1 2))").is_empty());
+    assert!(mem_type_check("(if true 1 2)").unwrap().1.diagnostics.is_empty());
+}
+
+#[test]
+fn test_identical_if_arms() {
+    let test = "(define-private (f (c bool) (a int)) (if c (+ a 1) (+ a 1)))";
+    let diagnostics = lint_helper(test);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, Level::Warning);
+    assert_eq!(diagnostics[0].message, Diagnostic::warn(&CheckWarnings::IdenticalIfArms).message);
+    assert!(diagnostics[0].suggestion.is_some());
+    // the warning spans both arms
+    assert_eq!(diagnostics[0].spans.len(), 2);
+
+    let distinct = ["(define-private (f (c bool) (a int)) (if c (+ a 1) (+ a 2)))",
+                    "(define-private (f (c bool) (a int)) (if c (+ a 1) (+ 1 a)))",
+                    "(define-private (f (c bool) (a int)) (if c a (+ a 0)))"];
+    for test in distinct.iter() {
+        assert!(lint_helper(test).is_empty());
+    }
+    assert!(mem_type_check(test).unwrap().1.diagnostics.is_empty());
+}
+
+#[test]
+fn test_suspicious_mint_literals() {
+    let tokens = "(define-fungible-token stackaroos)
+                  (define-non-fungible-token ids int)";
+
+    let diagnostics = lint_helper(&format!("{} (ft-mint? stackaroos u0 tx-sender)", tokens));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, Diagnostic::warn(&CheckWarnings::NonPositiveMintAmount).message);
+
+    let diagnostics = lint_helper(&format!("{} (nft-mint? ids (- 5) tx-sender)", tokens));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, Diagnostic::warn(&CheckWarnings::NegativeAssetIdentifier(-5)).message);
+    assert!(diagnostics[0].suggestion.is_some());
+
+    let fine = ["(ft-mint? stackaroos u1 tx-sender)",
+                "(define-private (mint (amount uint)) (ft-mint? stackaroos amount tx-sender))",
+                "(nft-mint? ids 0 tx-sender)"];
+    for test in fine.iter() {
+        assert!(lint_helper(&format!("{} {}", tokens, test)).is_empty());
+    }
+
+    // a negative amount does not type check in the first place
+    assert_eq!(mem_type_check(&format!("{} (ft-mint? stackaroos -5 tx-sender)", tokens)).unwrap_err().err,
+               CheckErrors::TypeError(UIntType, IntType));
+}
+
+#[test]
+fn test_constant_comparisons() {
+    let tests = ["(>= 5 5)",
+                 "(> 5 5)",
+                 "(< u1 (+ u1 u1))",
+                 "(<= (* 2 3) 5)"];
+    let expected = [ CheckWarnings::ConstantComparison(true),
+                     CheckWarnings::ConstantComparison(false),
+                     CheckWarnings::ConstantComparison(true),
+                     CheckWarnings::ConstantComparison(false) ];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        let diagnostics = lint_helper(test);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, Level::Warning);
+        assert_eq!(diagnostics[0].message, Diagnostic::warn(expected).message);
+        // the warning spans both operands
+        assert_eq!(diagnostics[0].spans.len(), 2);
+        assert_eq!("bool", &format!("{}", type_check_helper(test).unwrap()));
+    }
+
+    assert!(lint_helper("(define-private (f (x int)) (> x 5))").is_empty());
+    assert!(mem_type_check("(> 5 5)").unwrap().1.diagnostics.is_empty());
+}
+
+#[test]
+fn test_tuple_width_subtyping() {
+    let width = AnalysisOptions { tuple_width_subtyping: true, ..AnalysisOptions::default() };
+    let tests = ["(define-private (get-a (x (tuple (a int)))) (get a x))
+                  (get-a (tuple (a 1) (b u2)))",
+                 "(if true (tuple (a 1) (b u2)) (tuple (a 3) (c true)))",
+                 "(list (tuple (a 1) (b (tuple (c 2) (d 3)))) (tuple (a 4) (b (tuple (c 5)))))"];
+    let expected = ["int", "(tuple (a int))", "(list 2 (tuple (a int) (b (tuple (c int)))))"];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check_with_options(test, width.clone()).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    // the default is to require tuples to match exactly
+    let strict_expected = [
+        CheckErrors::TypeError(TypeSignature::from("(tuple (a int))"), TypeSignature::from("(tuple (a int) (b uint))")),
+        CheckErrors::IfArmsMustMatch(TypeSignature::from("(tuple (a int) (b uint))"), TypeSignature::from("(tuple (a int) (c bool))")),
+        CheckErrors::TypeError(TypeSignature::from("(tuple (c int) (d int))"), TypeSignature::from("(tuple (c int))")) ];
+
+    for (test, expected) in tests.iter().zip(strict_expected.iter()) {
+        assert_eq!(&mem_type_check(test).unwrap_err().err, expected);
+    }
+
+    // a supertype must share at least one field, and fewer fields are never admitted
+    let bad = ["(if true (tuple (a 1)) (tuple (b 1)))",
+               "(define-private (get-a (x (tuple (a int) (b int)))) (get a x))
+                (get-a (tuple (a 1)))"];
+    let bad_expected = [
+        CheckErrors::IfArmsMustMatch(TypeSignature::from("(tuple (a int))"), TypeSignature::from("(tuple (b int))")),
+        CheckErrors::TypeError(TypeSignature::from("(tuple (a int) (b int))"), TypeSignature::from("(tuple (a int))")) ];
+
+    for (test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(&mem_type_check_with_options(test, width.clone()).unwrap_err().err, expected);
+    }
+}
+
+#[test]
+fn test_mutations_in_callbacks() {
+    let named = "(define-data-var total int 0)
+                 (define-public (tally-all) (ok (map tally (list 1 2 3))))
+                 (define-private (tally (x int)) (begin (var-set total (+ (var-get total) x)) x))";
+    let diagnostics = lint_helper(named);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].level, Level::Warning);
+    assert_eq!(diagnostics[0].message,
+               Diagnostic::warn(&CheckWarnings::MutationInCallback(Some("tally".to_string()))).message);
+    // the warning spans the mutation, not the callback's definition
+    assert_eq!(diagnostics[0].spans.len(), 1);
+    assert_eq!(diagnostics[0].spans[0].start_line, 3);
+
+    let lambda = "(define-data-var total int 0)
+                  (define-public (tally-all)
+                    (ok (filter (lambda (x) (var-set total x)) (list 1 2 3))))";
+    let diagnostics = lint_helper(lambda);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message,
+               Diagnostic::warn(&CheckWarnings::MutationInCallback(None)).message);
+    assert_eq!(diagnostics[0].spans[0].start_line, 3);
+
+    let set_get = "(define-map seen ((x int)) ((count int)))
+                   (define-private (mark (x int)) (begin (map-set-get seen ((x x)) ((count 1))) x))
+                   (define-public (mark-all) (ok (map mark (list 1 2 3))))";
+    let diagnostics = lint_helper(set_get);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message,
+               Diagnostic::warn(&CheckWarnings::MutationInCallback(Some("mark".to_string()))).message);
+    assert_eq!(diagnostics[0].spans[0].start_line, 2);
+
+    let unused_as_callback = "(define-data-var total int 0)
+                              (define-private (tally (x int)) (begin (var-set total (+ (var-get total) x)) x))
+                              (define-private (double (x int)) (* 2 x))
+                              (define-public (tally-all) (ok (map double (list (tally 1) 2 3))))";
+    assert!(lint_helper(unused_as_callback).is_empty());
+    assert!(mem_type_check(named).unwrap().1.diagnostics.is_empty());
+}
+
+#[test]
+fn test_degenerate_argument_types() {
+    let tests = ["(define-private (f (x (buff 0))) 1)",
+                 "(define-read-only (f (a int) (x (list 0 int))) a)",
+                 "(define-public (f (x (tuple (memo (optional (buff 0)))))) (ok 1))"];
+    let expected = [ CheckWarnings::DegenerateArgumentType("x".to_string(), buff_type(0)),
+                     CheckWarnings::DegenerateArgumentType("x".to_string(), TypeSignature::from("(list 0 int)")),
+                     CheckWarnings::DegenerateArgumentType("x".to_string(), TypeSignature::from("(tuple (memo (optional (buff 0))))")) ];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        let diagnostics = lint_helper(test);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, Level::Warning);
+        assert_eq!(diagnostics[0].message, Diagnostic::warn(expected).message);
+    }
+
+    assert_eq!(lint_helper("(define-private (f (x (buff 0))) 1)")[0].spans[0].start_column, 20);
+    assert!(lint_helper("(define-private (f (x (buff 1)) (y (list 1 int))) 1)").is_empty());
+    assert!(mem_type_check("(define-private (f (x (buff 0))) 1)").unwrap().1.diagnostics.is_empty());
+}
+
+#[test]
+fn test_diagnostics_before_error() {
+    let contract_identifier = QualifiedContractIdentifier::transient();
+    let mut contract = parse(&contract_identifier,
+                             "(define-private (a) (if true 1 2))
+                              (define-private (b) (if false 1 2))
+                              (define-private (c) (+ 1 true))").unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let options = AnalysisOptions { lint: true, ..AnalysisOptions::default() };
+    let diagnostics = run_analysis_diagnostics(&contract_identifier, &mut contract, &mut db,
+                                               LimitedCostTracker::new_max_limit(), options);
+
+    let levels: Vec<_> = diagnostics.iter().map(|d| d.level.clone()).collect();
+    assert_eq!(levels, vec![Level::Warning, Level::Warning, Level::Error]);
+    assert_eq!(diagnostics[2].message, Diagnostic::err(&CheckErrors::TypeError(IntType, BoolType)).message);
+}
+
+#[test]
+fn test_diagnostics_to_json() {
+    let contract_identifier = QualifiedContractIdentifier::transient();
+    let mut contract = parse(&contract_identifier,
+                             "(define-private (a) (if true 1 2))
+                              (define-private (b) (+ 1 true))").unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let options = AnalysisOptions { lint: true, continue_on_error: true, ..AnalysisOptions::default() };
+    let diagnostics = run_analysis_diagnostics(&contract_identifier, &mut contract, &mut db,
+                                               LimitedCostTracker::new_max_limit(), options);
+
+    let json: serde_json::Value = serde_json::from_str(&diagnostics_to_json(&diagnostics)).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0]["level"], "Warning");
+    assert_eq!(entries[0]["message"], Diagnostic::warn(&CheckWarnings::ConstantIfCondition(true)).message.as_str());
+    assert!(entries[0]["suggestion"].is_null());
+    let span = &entries[0]["spans"].as_array().unwrap()[0];
+    assert_eq!(span["start_line"], 1);
+    for field in ["start_column", "end_line", "end_column"].iter() {
+        assert!(span[field].is_u64());
+    }
+
+    assert_eq!(entries[1]["level"], "Error");
+    assert_eq!(entries[1]["message"], Diagnostic::err(&CheckErrors::TypeError(IntType, BoolType)).message.as_str());
+    assert_eq!(entries[1]["spans"][0]["start_line"], 2);
+
+    assert_eq!(diagnostics_to_json(&[]), "[]");
+}
+
+#[test]
+fn test_continue_on_error() {
+    let contract_identifier = QualifiedContractIdentifier::transient();
+    let mut contract = parse(&contract_identifier,
+                             "(define-private (a) (+ 1 true))
+                              (define-private (b) (and 1 true))
+                              (define-private (c) (+ (a) 1))
+                              (define-private (d) (+ 1 2))").unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let options = AnalysisOptions { continue_on_error: true, ..AnalysisOptions::default() };
+    let diagnostics = run_analysis_diagnostics(&contract_identifier, &mut contract, &mut db,
+                                               LimitedCostTracker::new_max_limit(), options.clone());
+
+    // `c` uses the failed definition `a`, so its error is not reported.
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].message, Diagnostic::err(&CheckErrors::TypeError(IntType, BoolType)).message);
+    assert_eq!(diagnostics[1].message, Diagnostic::err(&CheckErrors::TypeError(BoolType, IntType)).message);
+    assert!(diagnostics.iter().all(|d| d.level == Level::Error));
+
+    // the analysis still fails, with the first error
+    let program = "(define-private (a) (+ 1 true)) (define-private (b) (and 1 true))";
+    assert_eq!(mem_type_check_with_options(program, options).unwrap_err().err,
+               CheckErrors::TypeError(IntType, BoolType));
+    let diagnostics = run_analysis_diagnostics(&contract_identifier, &mut parse(&contract_identifier, program).unwrap(),
+                                               &mut db, LimitedCostTracker::new_max_limit(), AnalysisOptions::default());
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn test_strict_numerics() {
+    let strict = AnalysisOptions { strict_numerics: true, ..AnalysisOptions::default() };
+
+    let ambiguous = ["(+ 1 2)", "(< 1 2)", "(mod 5 2)",
+                     "(define-private (f (x int)) (* 2 3))"];
+    for test in ambiguous.iter() {
+        mem_type_check(test).unwrap();
+        assert_eq!(mem_type_check_with_options(test, strict.clone()).unwrap_err().err,
+                   CheckErrors::CouldNotDetermineType);
+    }
+
+    let determined = ["(+ u1 u2)", "(< u1 u2)",
+                      "(define-private (f (x int)) (* x 3))",
+                      "(let ((x 1)) (- x 1))",
+                      "(is-eq 1 2)"];
+    for test in determined.iter() {
+        mem_type_check_with_options(test, strict.clone()).unwrap();
+    }
+}
+
+fn let_with_bindings(count: usize) -> String {
+    let bindings: Vec<String> = (0..count).map(|i| format!("(x{} {})", i, i)).collect();
+    format!("(let ({}) x0)", bindings.join(" "))
+}
+
+#[test]
+fn test_let_binding_limit() {
+    mem_type_check(&let_with_bindings(256)).unwrap();
+    assert_eq!(mem_type_check(&let_with_bindings(257)).unwrap_err().err,
+               CheckErrors::TooManyBindings(256, 257));
+
+    let options = AnalysisOptions { max_let_bindings: 2, ..AnalysisOptions::default() };
+    mem_type_check_with_options(&let_with_bindings(2), options.clone()).unwrap();
+    assert_eq!(mem_type_check_with_options(&let_with_bindings(3), options).unwrap_err().err,
+               CheckErrors::TooManyBindings(2, 3));
+}
+
 #[test]
 fn test_simple_lets() {
     let good = ["(let ((x 1) (y 2) (z 3)) (if (> x 2) (+ 1 x y) (- 1 z)))",
@@ -451,6 +954,44 @@ fn test_simple_lets() {
     }
 }
 
+#[test]
+fn test_begin_intermediate_types() {
+    let (type_sig, analysis) = mem_type_check("(begin (+ 1 2) (is-eq 1 2) u3)").unwrap();
+    assert_eq!(type_sig, Some(UIntType));
+
+    let statements = &analysis.expressions[0].match_list().unwrap()[1..];
+    let intermediate_types: Vec<_> = statements.iter()
+        .map(|statement| analysis.get_expression_type(statement).cloned())
+        .collect();
+    assert_eq!(intermediate_types, vec![Some(IntType), Some(BoolType), Some(UIntType)]);
+}
+
+#[test]
+fn test_let_tuple_destructuring() {
+    let good = ["(let (({ a: x, b: y } (tuple (a 1) (b 2)))) (+ x y))",
+                "(let (({ b: y } (tuple (a 1) (b u2))) (z y)) z)"];
+
+    let expected = ["int", "uint"];
+
+    let bad = ["(let (({ a: x, c: y } (tuple (a 1) (b 2)))) (+ x y))",
+               "(let (({ a: x } 1)) x)",
+               "(let (({ a: x, b: x } (tuple (a 1) (b 2)))) x)"];
+    let bad_expected = [ CheckErrors::NoSuchTupleField("c".to_string(),
+                                                       TupleTypeSignature::try_from(vec![
+                                                           ("a".into(), IntType),
+                                                           ("b".into(), IntType)]).unwrap()),
+                         CheckErrors::ExpectedTuple(IntType),
+                         CheckErrors::NameAlreadyUsed("x".to_string()) ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_eqs() {
     let good = ["(is-eq (list 1 2 3 4 5) (list 1 2 3 4 5 6 7))",
@@ -462,11 +1003,40 @@ fn test_eqs() {
     let bad = [
         "(is-eq 1 2 false)",
         "(is-eq 1 2 3 (list 2))",
-        "(is-eq (some 1) (some true))" ];
+        "(is-eq (some 1) (some true))",
+        "(is-eq 1 2 true)" ];
 
-    let bad_expected = [ CheckErrors::TypeError(BoolType, IntType),
-                         CheckErrors::TypeError(TypeSignature::list_of(IntType, 1).unwrap(), IntType),
-                         CheckErrors::TypeError("(optional bool)".into(), "(optional int)".into()) ];
+    let bad_expected = [ CheckErrors::EqualsArgumentTypeMismatch(2, BoolType, IntType),
+                         CheckErrors::EqualsArgumentTypeMismatch(3, TypeSignature::list_of(IntType, 1).unwrap(), IntType),
+                         CheckErrors::EqualsArgumentTypeMismatch(1, "(optional bool)".into(), "(optional int)".into()),
+                         CheckErrors::EqualsArgumentTypeMismatch(2, BoolType, IntType) ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+    
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_list_eqs() {
+    let good = ["(is-eq (list 1 2) (list 1 2 3 4))",
+                "(is-eq (list (list 1) (list 2 3)) (list (list 1 2 3)))",
+                "(is-eq (list) (list 1 2))",
+                "(is-eq (list (some 1)) (list none none))"];
+
+    let expected = ["bool", "bool", "bool", "bool"];
+
+    let bad = [
+        "(is-eq (list 1 2) (list true))",
+        "(is-eq (list (list 1)) (list (list u1 u2)))" ];
+
+    let bad_expected = [ CheckErrors::EqualsArgumentTypeMismatch(1, TypeSignature::list_of(BoolType, 1).unwrap(),
+                                                                 TypeSignature::list_of(IntType, 2).unwrap()),
+                         CheckErrors::EqualsArgumentTypeMismatch(1, TypeSignature::list_of(TypeSignature::list_of(UIntType, 2).unwrap(), 1).unwrap(),
+                                                                 TypeSignature::list_of(TypeSignature::list_of(IntType, 1).unwrap(), 1).unwrap()) ];
 
     for (good_test, expected) in good.iter().zip(expected.iter()) {
         assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
@@ -503,6 +1073,173 @@ fn test_asserts() {
 }
 
 
+#[test]
+fn test_filter_optional_predicate() {
+    let good = "(define-private (positive (x int)) (if (is-eq x 0) none (some (> x 0))))
+                (filter positive (list 1 0 -1))";
+    assert_eq!("(list 3 int)", &format!("{}", mem_type_check(good).unwrap().0.unwrap()));
+
+    let bad = "(define-private (half (x int)) (if (is-eq x 0) none (some (/ x 2))))
+               (filter half (list 1 0 -1))";
+    assert_eq!(mem_type_check(bad).unwrap_err().err,
+               CheckErrors::TypeError(BoolType, TypeSignature::new_option(IntType).unwrap()));
+}
+
+#[test]
+fn test_inline_lambdas() {
+    let good = ["(map (lambda (x) (+ x 1)) (list 1 2 3))",
+                "(let ((offset u10)) (map (lambda (x) (+ x offset)) (list u1 u2)))",
+                "(filter (lambda (x) (> x 1)) (list 1 2 3))",
+                "(map (lambda (b) (is-eq b \"a\")) \"abc\")",
+                "(fold (lambda (x acc) (+ acc (* x x))) (list 1 2 3) 0)"];
+    let expected = ["(list 3 int)", "(list 2 uint)", "(list 3 int)", "(list 3 bool)", "int"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+
+    let bad = ["(map (lambda (x y) (+ x y)) (list 1 2 3))",
+               "(fold (lambda (x) x) (list 1 2 3) 0)",
+               "(map (lambda (x) (+ x u1)) (list 1 2 3))",
+               "(filter (lambda (x) x) (list 1 2 3))",
+               "(fold (lambda (x acc) (concat acc (list x))) (list 1 2 3) (list 0))",
+               "(map (lambda x (+ x 1)) (list 1 2 3))",
+               "(let ((x 1)) (map (lambda (x) x) (list 1 2 3)))",
+               "(define-data-var n int 0)
+                (define-read-only (f) (map (lambda (x) (var-set n x)) (list 1)))"];
+    let bad_expected = [
+        CheckErrors::IncorrectArgumentCount(2, 1),
+        CheckErrors::IncorrectArgumentCount(1, 2),
+        CheckErrors::TypeError(IntType, UIntType),
+        CheckErrors::TypeError(BoolType, IntType),
+        CheckErrors::TypeError(TypeSignature::from("(list 1 int)"), TypeSignature::from("(list 2 int)")),
+        CheckErrors::BadLambdaSyntax,
+        CheckErrors::NameAlreadyUsed("x".to_string()),
+        CheckErrors::WriteAttemptedInReadOnly ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_lambda_escaping_let() {
+    let bad = ["(let ((offset 10)) (lambda (x) (+ x offset)))",
+               "(let ((offset 10) (scale 2)) (+ offset 1) (lambda (x) (* (+ x 1) scale)))",
+               "(let ((offset 10)) (lambda (x) (+ x 1)))"];
+    let bad_expected = [
+        CheckErrors::EscapingBinding("offset".to_string()),
+        CheckErrors::EscapingBinding("scale".to_string()),
+        CheckErrors::CouldNotDetermineType ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_diverging_if_arm() {
+    let good = ["(define-private (check (c bool)) (if c (ok 1) (unwrap! none (err u1)))) (check true)",
+                "(define-private (check (x (optional int))) (if (is-some x) (ok 1) (try! (err u2)))) (check none)",
+                "(if true 1 (unwrap-panic none))",
+                "(if true (unwrap-err-panic (ok 1)) u2)"];
+    let expected = ["(response int uint)", "(response int uint)", "int", "uint"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+
+    // only an early return applied directly as an arm may diverge
+    let bad = ["(if true 1 (+ 1 (unwrap-panic none)))",
+               "(unwrap-panic none)"];
+
+    for bad_test in bad.iter() {
+        assert_eq!(CheckErrors::CouldNotDetermineResponseOkType, mem_type_check(bad_test).unwrap_err().err);
+    }
+
+    // if both arms diverge, the `if` has no type
+    let bad = ["(if true (unwrap-panic none) (unwrap-panic (err 1)))",
+               "(define-private (check (c bool)) (if c (unwrap! none (err u1)) (try! (err u2))))"];
+
+    for bad_test in bad.iter() {
+        assert_eq!(CheckErrors::CouldNotDetermineType, mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_empty_list_if_arms() {
+    let good = ["(define-private (pick (c bool)) (if c (list 1 2) (list))) (pick true)",
+                "(define-private (pick (c bool)) (if c (list) (list 1 2))) (pick true)",
+                "(define-private (pick (c bool)) (if c (list (list u1)) (list))) (pick true)"];
+    let expected = ["(list 2 int)", "(list 2 int)", "(list 1 (list 1 uint))"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+
+    let empty = TypeSignature::from("(list 0 int)");
+    let non_empty = TypeSignature::from("(list 2 bool)");
+    assert_eq!(TypeSignature::least_supertype(&empty, &non_empty).unwrap(),
+               TypeSignature::least_supertype(&non_empty, &empty).unwrap());
+}
+
+#[test]
+fn test_no_type_unification() {
+    // `NoType` unifies with any type, in either position
+    for other in [TypeSignature::IntType, TypeSignature::from("(optional uint)"), TypeSignature::NoType].iter() {
+        assert_eq!(other, &TypeSignature::least_supertype(&TypeSignature::NoType, other).unwrap());
+        assert_eq!(other, &TypeSignature::least_supertype(other, &TypeSignature::NoType).unwrap());
+    }
+
+    let good = ["(append (list) 1)",
+                "(concat (list) (list u1 u2))",
+                "(default-to 1 none)",
+                "(let ((a 1)) a)",
+                "(get a (tuple (a 1)))"];
+    let expected = ["(list 1 int)", "(list 2 uint)", "int", "int", "int"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+}
+
+#[test]
+fn test_map_output_type() {
+    // the mapped list takes the function's return type, not the input item type
+    let contract = "(define-private (is-positive (x int)) (> x 0))
+                    (map is-positive (list 1 -2 3))";
+    let type_sig = mem_type_check(contract).unwrap().0.unwrap();
+    assert_eq!(type_sig, TypeSignature::list_of(BoolType, 3).unwrap());
+    assert_eq!("(list 3 bool)", &type_sig.to_string());
+}
+
+#[test]
+fn test_nested_map_bounds() {
+    // every sequence type carries a maximum length, so nested iteration is always bounded
+    //   by the product of the outer and inner lengths
+    let contract = "(define-private (double (x int)) (* x 2))
+                    (define-private (double-all (items (list 4 int))) (map double items))
+                    (map double-all (list (list 1 2) (list 3 4 5 6) (list 7)))";
+    assert_eq!("(list 3 (list 4 int))", &mem_type_check(contract).unwrap().0.unwrap().to_string());
+}
+
+#[test]
+fn test_iterating_unconstrained_list() {
+    let bad = ["(define-private (double (x int)) (* x 2)) (map double (list))",
+               "(define-private (is-big (x int)) (> x 2)) (filter is-big (list))",
+               "(define-private (sum (x int) (acc int)) (+ x acc)) (fold sum (list) 0)",
+               "(map (lambda (x) (* x 2)) (list))"];
+    for bad_test in bad.iter() {
+        assert_eq!(CheckErrors::CouldNotDetermineType, mem_type_check(bad_test).unwrap_err().err);
+    }
+
+    // an empty list bound to a typed argument is constrained by the annotation
+    let good = "(define-private (double (x int)) (* x 2))
+                (define-private (double-all (items (list 3 int))) (map double items))
+                (double-all (list))";
+    assert_eq!("(list 3 int)", &format!("{}", mem_type_check(good).unwrap().0.unwrap()));
+}
+
 #[test]
 fn test_lists() {
     let good = [
@@ -621,6 +1358,44 @@ fn test_buff() {
     }
 }
 
+#[test]
+fn test_function_arg_size_limit() {
+    let at_limit = "(define-private (f (x (tuple (a (buff 524273)) (b (buff 524273))))) (get a x))";
+    mem_type_check(at_limit).unwrap();
+
+    let over_limit = "(define-private (f (x (tuple (a (buff 524273)) (b (buff 524274))))) (get a x))";
+    assert_eq!(mem_type_check(over_limit).unwrap_err().err, CheckErrors::ValueTooLarge);
+}
+
+#[test]
+fn test_buff_too_large() {
+    let bad = [
+        "(define-data-var v (buff 2) 0x0102)
+         (var-set v 0xdeadbeef)",
+        "(define-map m ((k (buff 2))) ((v (buff 2))))
+         (map-set m ((k 0x01)) ((v 0xdeadbeef)))",
+        "(define-map m ((k (buff 2))) ((v (buff 2))))
+         (map-get? m ((k 0xdeadbeef)))",
+        "(define-private (f (x (buff 2))) x)
+         (f 0xdeadbeef)",
+        "(define-public (f (x (buff 2))) (ok x))
+         (define-public (g) (f 0xdeadbeef))" ];
+
+    for bad_test in bad.iter() {
+        let err = mem_type_check(bad_test).unwrap_err();
+        assert_eq!(CheckErrors::ValueTooLarge, err.err);
+        assert_eq!("expecting a buffer of at most 2 bytes, found a buffer of 4 bytes", &err.diagnostic.message);
+    }
+
+    // a tuple with a mismatch other than an oversized buffer is still a type error
+    let mismatch = "(define-map m ((k (buff 2))) ((v (buff 2)) (w int)))
+                    (map-set m ((k 0x01)) ((v 0xdeadbeef) (w u1)))";
+    assert!(match mem_type_check(mismatch).unwrap_err().err {
+        CheckErrors::TypeError(_, _) => true,
+        _ => false
+    });
+}
+
 #[test]
 fn test_buff_fold() {
     let good = [
@@ -802,6 +1577,88 @@ fn test_tuples() {
     }
 }
 
+#[test]
+fn test_get_in() {
+    let nested = "(tuple (a (tuple (b (tuple (c 1) (d true))))))";
+    let good = [format!("(get-in {} (a b c))", nested),
+                format!("(get-in {} (a b))", nested),
+                format!("(get-in (some {}) (a b d))", nested),
+                "(get-in (tuple (a (some (tuple (b u1))))) (a b))".to_string()];
+    let expected = [ "int", "(tuple (c int) (d bool))", "(optional bool)", "(optional uint)" ];
+
+    let bad = [format!("(get-in {} (a x c))", nested),
+               format!("(get-in {} (a b c d))", nested),
+               format!("(get-in {} ())", nested),
+               format!("(get-in {} a)", nested)];
+    let bad_expected = [ CheckErrors::NoSuchTupleField("x".to_string(),
+                                                       TupleTypeSignature::try_from(vec![
+                                                           ("b".into(), "(tuple (c int) (d bool))".into())]).unwrap()),
+                         CheckErrors::ExpectedTuple(IntType),
+                         CheckErrors::RequiresAtLeastArguments(1, 0),
+                         CheckErrors::BadTupleFieldName ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+    
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_get_at() {
+    let items = "(list (tuple (id 1) (name \"alice\")) (tuple (id 2) (name \"bob\")))";
+    let good = [format!("(get-at {} 0 id)", items),
+                format!("(get-at {} 5 name)", items)];
+    let expected = [ "(optional int)", "(optional (buff 5))" ];
+
+    let bad = [format!("(get-at {} 0 x)", items),
+               format!("(get-at {} u0 id)", items),
+               format!("(get-at {} 0)", items),
+               "(get-at (list 1 2) 0 id)".to_string(),
+               "(get-at (tuple (id 1)) 0 id)".to_string()];
+    let bad_expected = [ CheckErrors::NoSuchTupleField("x".to_string(),
+                                                       TupleTypeSignature::try_from(vec![
+                                                           ("id".into(), IntType),
+                                                           ("name".into(), buff_type(5))]).unwrap()),
+                         CheckErrors::TypeError(IntType, UIntType),
+                         CheckErrors::IncorrectArgumentCount(3, 2),
+                         CheckErrors::ExpectedTuple(IntType),
+                         CheckErrors::ExpectedListApplication ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_function_arg_annotations() {
+    let contract_src = "(define-private (total (entry (tuple (amount uint) (fee uint))))
+                          (+ (get amount entry) (get fee entry)))
+                        (total (tuple (amount u1) (fee u2)))";
+    let (type_sig, analysis) = mem_type_check(contract_src).unwrap();
+    assert_eq!(type_sig, Some(UIntType));
+
+    let expected = FunctionType::Fixed(FixedFunction {
+        args: vec![FunctionArg::new(TypeSignature::from("(tuple (amount uint) (fee uint))"), "entry".into())],
+        returns: UIntType });
+    assert_eq!(analysis.get_private_function("total"), Some(&expected));
+
+    let bad = ["(define-private (f (x (list int))) x)",
+               "(define-private (f (x (tuple (a integer)))) x)"];
+    let bad_expected = [ CheckErrors::InvalidTypeDescription,
+                         CheckErrors::UnknownTypeName("integer".to_string()) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_empty_tuple_should_fail() {
     let contract_src = r#"
@@ -813,6 +1670,72 @@ fn test_empty_tuple_should_fail() {
                CheckErrors::BadSyntaxBinding);
 }
 
+#[test]
+fn test_duplicate_tuple_fields() {
+    let bad = ["(tuple (a 1) (a 2))",
+               "(tuple (a 1) (b (tuple (c 1) (c u2))))",
+               "(let ((x 1)) (tuple (value x) (flag true) (value 3)))"];
+
+    let bad_expected = [CheckErrors::NameAlreadyUsed("a".to_string()),
+                        CheckErrors::NameAlreadyUsed("c".to_string()),
+                        CheckErrors::NameAlreadyUsed("value".to_string())];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_print_event() {
+    let good = "(define-private (deposit (amount uint))
+                  (begin (print-event \"deposit\" { amount: amount, sender: tx-sender })
+                         (print-event \"count\" 1)))";
+
+    let (_, analysis) = mem_type_check(good).unwrap();
+    let deposit_type = TypeSignature::TupleType(TupleTypeSignature::try_from(vec![
+        ("amount".into(), TypeSignature::UIntType),
+        ("sender".into(), TypeSignature::PrincipalType)]).unwrap());
+    assert_eq!(analysis.print_events,
+               vec![("deposit".to_string(), deposit_type),
+                    ("count".to_string(), TypeSignature::IntType)]);
+
+    let bad = ["(let ((topic \"deposit\")) (print-event topic 1))",
+               "(print-event 0x00ff 1)",
+               "(print-event 1 1)",
+               "(print-event \"deposit\")"];
+
+    let bad_expected = [CheckErrors::ExpectedLiteral,
+                        CheckErrors::ExpectedLiteral,
+                        CheckErrors::ExpectedLiteral,
+                        CheckErrors::IncorrectArgumentCount(2, 1)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_test_assert() {
+    let good = "(define-private (check-sum (x int))
+                  (test-assert (is-eq (+ x 1) 2) \"sum\"))";
+
+    let (_, analysis) = mem_type_check(good).unwrap();
+    assert_function_returns(&analysis, "check-sum", BoolType);
+    assert_eq!(analysis.print_events, vec![("test-assert".to_string(), buff_type(3))]);
+
+    let bad = ["(test-assert 1 \"sum\")",
+               "(test-assert true 1)",
+               "(test-assert true)"];
+
+    let bad_expected = [CheckErrors::TypeError(BoolType, IntType),
+                        CheckErrors::TypeError(TypeSignature::max_buffer(), IntType),
+                        CheckErrors::IncorrectArgumentCount(2, 1)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_define() {
     let good = ["(define-private (foo (x int) (y int)) (+ x y))
@@ -834,6 +1757,34 @@ fn test_define() {
     }
 }
 
+#[test]
+fn test_iterable_callback_kinds() {
+    let good = ["(define-read-only (positive (x int)) (> x 0))
+                 (filter positive (list -1 2 3))",
+                "(define-read-only (double (x int)) (* 2 x))
+                 (define-read-only (double-all (xs (list 3 int))) (map double xs))
+                 (double-all (list 1 2 3))",
+                "(define-read-only (add (x int) (acc int)) (+ x acc))
+                 (fold add (list 1 2 3) 0)"];
+    let expected = ["(list 3 int)", "(list 3 int)", "int"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &mem_type_check(good_test).unwrap().0.unwrap().to_string());
+    }
+
+    let bad = ["(define-public (positive (x int)) (ok (> x 0)))
+                (map positive (list -1 2 3))",
+               "(define-public (add (x int) (acc (response int int))) (ok x))
+                (fold add (list 1 2 3) (ok 0))"];
+    let bad_expected = [
+        CheckErrors::IllegalOrUnknownFunctionApplication("positive".to_string()),
+        CheckErrors::IllegalOrUnknownFunctionApplication("add".to_string()) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(&mem_type_check(bad_test).unwrap_err().err, expected);
+    }
+}
+
 #[test]
 fn test_high_order_map() {
     let good = [
@@ -854,6 +1805,95 @@ fn test_high_order_map() {
     }
 }
 
+#[test]
+fn test_forward_referenced_iterable_functions() {
+    let good = [
+        "(define-private (double-all (xs (list 3 int))) (map double xs))
+         (define-private (double (x int)) (* 2 x))
+         (double-all (list 1 2 3))",
+        "(define-private (keep-positive (xs (list 3 int))) (filter is-positive xs))
+         (define-private (is-positive (x int)) (> x 0))
+         (keep-positive (list 1 -2 3))",
+        "(define-private (sum (xs (list 3 int))) (fold add xs 0))
+         (define-private (add (x int) (acc int)) (+ x acc))
+         (sum (list 1 2 3))",
+    ];
+
+    let expected = [
+        "(list 3 int)",
+        "(list 3 int)",
+        "int",
+    ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    let bad = "(define-private (double-all (xs (list 3 int))) (map triple xs))
+               (define-private (double (x int)) (* 2 x))";
+    assert_eq!(mem_type_check(bad).unwrap_err().err,
+               CheckErrors::IllegalOrUnknownFunctionApplication("triple".to_string()));
+}
+
+#[test]
+fn test_list_dimension_limit() {
+    fn nested_list(dimension: usize) -> String {
+        format!("{}1{}", "(list ".repeat(dimension), ")".repeat(dimension))
+    }
+
+    let deepest = mem_type_check(&nested_list(31)).unwrap().0.unwrap();
+    assert_eq!(deepest.list_dimension(), 31);
+
+    assert_eq!(mem_type_check(&nested_list(32)).unwrap_err().err,
+               CheckErrors::ListDimensionTooHigh(32));
+}
+
+#[test]
+fn test_constructed_list_length_limit() {
+    // bools are one byte each, so these lists are far below the maximum value size.
+    assert_eq!("(optional (list 65535 bool))",
+               &format!("{}", type_check_helper("(as-max-len? (list true false) u65535)").unwrap()));
+
+    let good = ["(define-private (grow (l (list 65534 bool))) (append l true))",
+                "(define-private (join (a (list 30000 bool)) (b (list 35535 bool))) (concat a b))",
+                "(define-private (expand (x (buff 1))) true)
+                 (define-private (expand-all (b (buff 65535))) (map expand b))"];
+
+    for good_test in good.iter() {
+        mem_type_check(good_test).unwrap();
+    }
+
+    let bad = ["(as-max-len? (list true false) u65536)",
+               "(define-private (grow (l (list 65535 bool))) (append l true))",
+               "(define-private (join (a (list 30000 bool)) (b (list 35536 bool))) (concat a b))",
+               "(define-private (expand (x (buff 1))) true)
+                (define-private (expand-all (b (buff 65536))) (map expand b))"];
+
+    for bad_test in bad.iter() {
+        assert_eq!(mem_type_check(bad_test).unwrap_err().err, CheckErrors::ConstructedListTooLarge);
+    }
+
+    let too_long = format!("(list {})", "true ".repeat(MAX_LIST_LENGTH as usize + 1));
+    assert_eq!(type_check_helper(&too_long).unwrap_err().err, CheckErrors::ConstructedListTooLarge);
+
+    // the limit is only enforced during analysis, so values built at runtime are unaffected.
+    Value::list_from(vec![Value::Bool(true); MAX_LIST_LENGTH as usize + 1]).unwrap();
+}
+
+#[test]
+fn test_map_result_size_limit() {
+    // each result is a (buff 100000), so ten results fit in a value and eleven do not.
+    let prelude = "(define-data-var payload (buff 100000) 0x00)
+                   (define-private (expand (x int)) (var-get payload))";
+
+    let good = format!("{} (map expand (list 1 2 3 4 5 6 7 8 9 10))", prelude);
+    assert_eq!("(list 10 (buff 100000))", &mem_type_check(&good).unwrap().0.unwrap().to_string());
+
+    let bad = format!("{} (map expand (list 1 2 3 4 5 6 7 8 9 10 11))", prelude);
+    assert_eq!(mem_type_check(&bad).unwrap_err().err, CheckErrors::ConstructedListTooLarge);
+}
+
 #[test]
 fn test_simple_uints() {
     let good = [
@@ -874,20 +1914,121 @@ fn test_simple_uints() {
 
     let bad = ["(> u1 1)", "(to-uint true)", "(to-int false)"];
 
-    let bad_expected = [ CheckErrors::TypeError(UIntType, IntType),
-                         CheckErrors::TypeError(IntType, BoolType),
-                         CheckErrors::TypeError(UIntType, BoolType) ];
+    let bad_expected = [ CheckErrors::TypeError(UIntType, IntType),
+                         CheckErrors::TypeError(IntType, BoolType),
+                         CheckErrors::TypeError(UIntType, BoolType) ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(&mem_type_check(bad_test).unwrap_err().err, expected);
+    }
+}
+
+#[test]
+fn test_response_projections() {
+    let good = ["(define-private (check (x (response bool int))) (get-ok x))
+                 (check (ok true))",
+                "(define-private (check (x (response bool int))) (get-err x))
+                 (check (ok true))",
+                "(get-ok (err u1))",
+                "(get-err (err u1))"];
+    let expected = ["(optional bool)", "(optional int)", "(optional UnknownType)", "(optional uint)"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    let bad = ["(get-ok (some 1))",
+               "(get-err 1)",
+               "(get-ok (ok 1) (ok 2))"];
+    let bad_expected = [
+        CheckErrors::ExpectedResponseType(TypeSignature::from("(optional int)")),
+        CheckErrors::ExpectedResponseType(IntType),
+        CheckErrors::IncorrectArgumentCount(1, 2) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(&mem_type_check(bad_test).unwrap_err().err, expected);
+    }
+}
+
+#[test]
+fn test_flatten() {
+    let good = ["(flatten (get a (some (tuple (a (some u2))))))",
+                "(define-private (f (x (optional (optional int)))) (flatten x)) (f none)",
+                "(flatten (some none))",
+                "(flatten none)"];
+    let expected = ["(optional uint)", "(optional int)", "(optional UnknownType)", "(optional UnknownType)"];
 
     for (good_test, expected) in good.iter().zip(expected.iter()) {
         let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
         assert_eq!(expected, &type_sig.to_string());
     }
 
+    let bad = ["(flatten (some 1))",
+               "(flatten 1)",
+               "(flatten (some (some 1)) none)"];
+    let bad_expected = [
+        CheckErrors::ExpectedNestedOptionalType(TypeSignature::from("(optional int)")),
+        CheckErrors::ExpectedNestedOptionalType(IntType),
+        CheckErrors::IncorrectArgumentCount(1, 2) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(&mem_type_check(bad_test).unwrap_err().err, expected);
+    }
+}
+
+#[test]
+fn test_buff_to_uint() {
+    let good = ["(buff-to-uint-le 0x0102030405060708)",
+                "(define-private (f (x (buff 16))) (buff-to-uint-be x)) (f 0x01)"];
+
+    for good_test in good.iter() {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(UIntType, type_sig);
+    }
+
+    let bad = ["(buff-to-uint-le 0x0102030405060708090a0b0c0d0e0f1011121314)",
+               "(define-private (f (x (buff 20))) (buff-to-uint-be x))",
+               "(buff-to-uint-le u1)",
+               "(buff-to-uint-be 0x01 0x02)"];
+    let bad_expected = [
+        CheckErrors::BufferTooLargeForInt(20),
+        CheckErrors::BufferTooLargeForInt(20),
+        CheckErrors::TypeError(buff_type(16), UIntType),
+        CheckErrors::IncorrectArgumentCount(1, 2) ];
+
     for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
         assert_eq!(&mem_type_check(bad_test).unwrap_err().err, expected);
     }
 }
 
+/// The runtime cost of analyzing a function which accesses a data variable `accesses` times.
+fn var_access_analysis_cost(accesses: usize) -> u64 {
+    let mut contract = "(define-data-var counter int 0)
+                        (define-public (bump) (begin".to_string();
+    for _ in 0..accesses {
+        contract.push_str(" (var-set counter (+ (var-get counter) 1))");
+    }
+    contract.push_str(" (ok (var-get counter))))");
+
+    mem_type_check(&contract).unwrap().1
+        .take_contract_cost_tracker().get_total().runtime
+}
+
+#[test]
+fn test_var_access_analysis_cost() {
+    // a data variable's type is cached on the checker after its first lookup, so every
+    //   access costs the same to check.
+    let increment_50_100 = var_access_analysis_cost(100) - var_access_analysis_cost(50);
+    let increment_100_150 = var_access_analysis_cost(150) - var_access_analysis_cost(100);
+    assert_eq!(increment_50_100, increment_100_150);
+}
+
 #[test]
 fn test_response_inference() {
     let good = ["(define-private (foo (x int)) (err x))
@@ -944,6 +2085,108 @@ fn test_response_inference() {
     }
 }
 
+#[test]
+fn test_response_unification() {
+    let good = ["(if true (ok 1) (err \"x\"))",
+                "(if true (ok u1) (if false (err \"x\") (err \"abc\")))",
+                "(match (some 1) x (ok x) (err false))",
+                "(define-private (f (x int))
+                   (begin (asserts! (> x 0) (err \"neg\"))
+                          (ok x)))
+                 (f 1)"];
+
+    let expected = ["(response int (buff 1))",
+                    "(response uint (buff 3))",
+                    "(response int bool)",
+                    "(response int (buff 3))"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    // the constrained sides must still agree
+    assert_eq!(mem_type_check("(if true (ok 1) (ok u1))").unwrap_err().err,
+               CheckErrors::IfArmsMustMatch(IntType, UIntType));
+}
+
+#[test]
+fn test_deeply_nested_if_arms() {
+    let good = ["(if true (some (some (some 1))) (some (some (some 2))))",
+                "(if true (some (some none)) (some (some (some 2))))",
+                "(if true (ok (list (some (some 1)))) (err u1))",
+                "(if true (tuple (a (some (some (ok 1))))) (tuple (a (some (some (err u1))))))"];
+    let expected = ["(optional (optional (optional int)))",
+                    "(optional (optional (optional int)))",
+                    "(response (list 1 (optional (optional int))) uint)",
+                    "(tuple (a (optional (optional (response int uint)))))"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    // a mismatch is reported at the innermost types which disagree
+    let bad = ["(if true (some (some (some 1))) (some (some (some u1))))",
+               "(if true (tuple (a (some (some (ok 1))))) (tuple (a (some (some (ok true))))))"];
+    let bad_expected = [CheckErrors::IfArmsMustMatch(IntType, UIntType),
+                        CheckErrors::IfArmsMustMatch(IntType, BoolType)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_response_constructors_against_declared_types() {
+    // functions do not declare return types, but `ok` and `err` are checked against
+    //   each declared response type they flow into, leaving the unconstrained side open.
+    let good = ["(define-private (check (x (response int bool))) (is-ok x))
+                 (check (ok 1))",
+                "(define-data-var last (response int bool) (ok 1))
+                 (var-set last (err false))",
+                "(define-map results ((id int)) ((result (response int bool))))
+                 (map-set results { id: 1 } { result: (err true) })"];
+
+    for good_test in good.iter() {
+        mem_type_check(good_test).unwrap();
+    }
+
+    let response_int_bool = TypeSignature::new_response(IntType, BoolType).unwrap();
+    let bad = ["(define-private (check (x (response int bool))) (is-ok x))
+                (check (ok u1))",
+               "(define-data-var last (response int bool) (err u1))"];
+    let bad_expected = [CheckErrors::TypeError(response_int_bool.clone(),
+                                               TypeSignature::new_response(UIntType, TypeSignature::NoType).unwrap()),
+                        CheckErrors::TypeError(response_int_bool,
+                                               TypeSignature::new_response(TypeSignature::NoType, UIntType).unwrap())];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_public_function_must_return_response() {
+    let good = ["(define-public (f (x int)) (ok x))",
+                "(define-public (f (x int)) (if (> x 0) (ok x) (err u1)))",
+                "(define-read-only (f (x int)) x)",
+                "(define-private (f (x int)) x)"];
+
+    for good_test in good.iter() {
+        mem_type_check(good_test).unwrap();
+    }
+
+    let bad = ["(define-public (f (x int)) x)",
+               "(define-public (f (x int)) (some x))"];
+    let bad_expected = [CheckErrors::PublicFunctionMustReturnResponse(IntType),
+                        CheckErrors::PublicFunctionMustReturnResponse(TypeSignature::new_option(IntType).unwrap())];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_function_arg_names() {
     use vm::analysis::type_check;
@@ -1056,6 +2299,40 @@ fn test_options() {
 
 }
 
+#[test]
+fn test_optional_guard_refinement() {
+    let good = [
+        "(define-private (inc-or-zero (x (optional int)))
+           (if (is-some x) (+ x 1) 0))
+         (inc-or-zero (some 1))",
+        "(define-private (inc-or-zero (x (optional int)))
+           (if (is-none x) 0 (+ x 1)))
+         (inc-or-zero none)",
+        "(let ((x (some { a: 1 })))
+           (if (is-none x) 0 (get a x)))",
+        // names bound to `none` are not refined
+        "(let ((x none)) (if (is-some x) 1 0))",
+    ];
+
+    let expected = [ "int", "int", "int", "int" ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    // the refinement only applies to the guarded branch
+    let bad = ["(define-private (inc-or-zero (x (optional int)))
+                  (if (is-some x) 0 (+ x 1)))",
+               "(define-private (inc-or-zero (x (optional int)))
+                  (if (is-none x) (+ x 1) 0))"];
+
+    for bad_test in bad.iter() {
+        assert_eq!(mem_type_check(bad_test).unwrap_err().err,
+                   CheckErrors::UnionTypeError(vec![IntType, UIntType],
+                                               TypeSignature::new_option(IntType).unwrap()));
+    }
+}
 
 #[test]
 fn test_list_nones() {
@@ -1206,6 +2483,56 @@ fn test_direct_access_to_persisted_var_should_fail() {
     });
 }
 
+#[test]
+fn test_data_var_default_value() {
+    let good = ["(define-data-var v (buff 4) 0x01020304)",
+                "(define-data-var v (buff 4) 0x01)",
+                "(define-data-var v (optional int) none)"];
+    for good_test in good.iter() {
+        mem_type_check(good_test).unwrap();
+    }
+
+    let bad = ["(define-data-var v (buff 4) 0x0102030405)",
+               "(define-data-var v (buff 4) u1)",
+               "(define-data-var v int u1)"];
+    let bad_expected = [
+        CheckErrors::ValueTooLarge,
+        CheckErrors::TypeError(buff_type(4), UIntType),
+        CheckErrors::TypeError(IntType, UIntType) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_nested_tuple_data_var() {
+    let prelude = "(define-data-var v (tuple (a int) (b (optional (list 3 int)))) (tuple (a 0) (b none)))
+                   (define-read-only (get-v) (var-get v))";
+
+    let (_, analysis) = mem_type_check(prelude).unwrap();
+    assert_function_returns(&analysis, "get-v", TypeSignature::from("(tuple (a int) (b (optional (list 3 int))))"));
+
+    let good = ["(var-set v (tuple (a 1) (b none)))",
+                "(var-set v (tuple (a 1) (b (some (list 1 2)))))",
+                "(var-set v (tuple (a 1) (b (some (list)))))",
+                "(var-set v (var-get v))"];
+    for good_test in good.iter() {
+        assert_eq!("bool", &format!("{}", type_check_helper(&format!("{} {}", prelude, good_test)).unwrap()));
+    }
+
+    let bad = ["(var-set v (tuple (a 1) (b (some (list 1 2 3 4)))))",
+               "(var-set v (tuple (a 1) (b (some (list u1)))))",
+               "(var-set v (tuple (a 1) (b (list 1))))",
+               "(var-set v (tuple (a 1)))"];
+    for bad_test in bad.iter() {
+        assert!(match mem_type_check(&format!("{} {}", prelude, bad_test)).unwrap_err().err {
+            CheckErrors::TypeError(_, _) => true,
+            _ => false
+        });
+    }
+}
+
 #[test]
 fn test_data_var_shadowed_by_let_should_fail() {
     let contract_src = r#"
@@ -1240,6 +2567,28 @@ fn test_mutating_unknown_data_var_should_fail() {
     });
 }
 
+#[test]
+fn test_unknown_data_var_suggestion() {
+    let contract_src = r#"
+        (define-data-var cursor int 0)
+        (define-data-var counter int 0)
+        (define-private (get-cursor) (var-get cursr))
+    "#;
+
+    let res = mem_type_check(contract_src).unwrap_err();
+    assert_eq!(res.err, CheckErrors::NoSuchDataVariable("cursr".to_string()));
+    assert_eq!(res.diagnostic.suggestion, Some("did you mean 'cursor'?".to_string()));
+
+    let contract_src = r#"
+        (define-data-var cursor int 0)
+        (define-private (set-total) (var-set total 1))
+    "#;
+
+    let res = mem_type_check(contract_src).unwrap_err();
+    assert_eq!(res.err, CheckErrors::NoSuchDataVariable("total".to_string()));
+    assert_eq!(res.diagnostic.suggestion, None);
+}
+
 #[test]
 fn test_accessing_unknown_data_var_should_fail() {
     let contract_src = r#"
@@ -1314,6 +2663,28 @@ fn test_define_constant_shadowed_by_argument_should_fail() {
     });
 }
 
+#[test]
+fn test_map_type_size_limits() {
+    // a value tuple of (buff 1048560) is one byte under the limit, leaving room
+    //   for the optional wrapper returned by map-get?
+    let good = "(define-map big ((key int)) ((val (buff 1048560))))
+                (map-get? big { key: 1 })";
+    mem_type_check(good).unwrap();
+
+    let bad = ["(define-map big ((key int)) ((val (buff 1048561))))",
+               "(define-map big ((key int)) ((val (buff 1048562))))",
+               "(define-map big ((key (buff 1048562))) ((val int)))"];
+    let bad_expected = ["the value type of map 'big' is greater than the maximum allowed value size",
+                        "the value type of map 'big' is greater than the maximum allowed value size",
+                        "the key type of map 'big' is greater than the maximum allowed value size"];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        let err = mem_type_check(bad_test).unwrap_err();
+        assert_eq!(CheckErrors::ValueTooLarge, err.err);
+        assert_eq!(expected, &err.diagnostic.message);
+    }
+}
+
 #[test]
 fn test_tuple_map() {
     let t = "(define-map tuples ((name int))
@@ -1438,6 +2809,85 @@ fn test_fetch_entry_matching_type_signatures() {
     }
 }
 
+#[test]
+fn test_set_get_entry() {
+    let contract_src = "(define-map kv-store ((key int)) ((value int)))
+                        (define-private (swap (key int) (value int))
+                          (map-set-get kv-store ((key key)) ((value value))))
+                        (swap 1 2)";
+    let (type_sig, _) = mem_type_check(contract_src).unwrap();
+    assert_eq!(type_sig, Some(TypeSignature::from("(optional (tuple (value int)))")));
+
+    let bad = ["(define-map kv-store ((key int)) ((value int))) (map-set-get kv-store ((key 1)) ((value u2)))",
+               "(define-map kv-store ((key int)) ((value int))) (map-set-get kv-store ((key u1)) ((value 2)))",
+               "(map-set-get kv-store ((key 1)) ((value 2)))"];
+    let bad_expected = [
+        CheckErrors::TypeError(TypeSignature::from("(tuple (value int))"), TypeSignature::from("(tuple (value uint))")),
+        CheckErrors::TypeError(TypeSignature::from("(tuple (key int))"), TypeSignature::from("(tuple (key uint))")),
+        CheckErrors::NoSuchMap("kv-store".to_string()) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_principal_destruct() {
+    let parts = "(tuple (version (buff 1)) (hash-bytes (buff 20)) (name (optional (buff 40))))";
+    let expected = TypeSignature::from(format!("(response {} {})", parts, parts).as_str());
+
+    let good = ["(principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+                "(principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.contract)",
+                "(principal-destruct? tx-sender)"];
+    for good_test in good.iter() {
+        assert_eq!(expected, type_check_helper(good_test).unwrap());
+    }
+
+    let bad = ["(principal-destruct? 1)",
+               "(principal-destruct? 0x01)",
+               "(principal-destruct? tx-sender tx-sender)"];
+    let bad_expected = [
+        CheckErrors::TypeError(PrincipalType, IntType),
+        CheckErrors::TypeError(PrincipalType, TypeSignature::from("(buff 1)")),
+        CheckErrors::IncorrectArgumentCount(1, 2) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_principal_construct() {
+    let expected = TypeSignature::from("(response principal (tuple (error_code uint) (value (optional principal))))");
+
+    let good = ["(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce)",
+                "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce \"foo\")",
+                "(let ((parts (unwrap-panic (principal-destruct? tx-sender))))
+                   (principal-construct? (get version parts) (get hash-bytes parts)))"];
+    for good_test in good.iter() {
+        assert_eq!(expected, type_check_helper(good_test).unwrap());
+    }
+
+    let bad = ["(principal-construct? 0x1a1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce)",
+               "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2)",
+               "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce 1)",
+               "(principal-construct? 0x1a)",
+               "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce \"foo\" \"bar\")",
+               // contract names are at most 40 bytes
+               "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce \"a-contract-name-of-forty-one-bytes-length\")"];
+    let bad_expected = [
+        CheckErrors::ValueTooLarge,
+        CheckErrors::TypeError(buff_type(20), buff_type(19)),
+        CheckErrors::TypeError(buff_type(40), IntType),
+        CheckErrors::RequiresAtLeastArguments(2, 1),
+        CheckErrors::IncorrectArgumentCount(3, 4),
+        CheckErrors::ValueTooLarge ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_fetch_entry_mismatching_type_signatures() {
     let cases = [
@@ -1460,6 +2910,27 @@ fn test_fetch_entry_mismatching_type_signatures() {
     }
 }
 
+#[test]
+fn test_fetch_entry_single_field_key_shorthand() {
+    let good = "(define-map balances ((owner principal)) ((balance uint)))
+                (define-map nested ((key (tuple (a int)))) ((value int)))
+                (define-read-only (get-balance) (map-get? balances tx-sender))
+                (define-read-only (get-nested) (map-get? nested (tuple (a 1))))";
+    mem_type_check(good).unwrap();
+
+    let bad = ["(define-map balances ((owner principal)) ((balance uint)))
+                (map-get? balances u1)",
+               "(define-map allowances ((owner principal) (spender principal)) ((amount uint)))
+                (map-get? allowances tx-sender)"];
+    let bad_expected = [
+        CheckErrors::TypeError(TypeSignature::from("(tuple (owner principal))"), UIntType),
+        CheckErrors::TypeError(TypeSignature::from("(tuple (owner principal) (spender principal))"), PrincipalType) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_fetch_entry_unbound_variables() {
     let cases = [
@@ -1671,3 +3142,292 @@ fn test_set_entry_unbound_variables() {
         });
     }
 }
+
+#[test]
+fn test_incremental_definition_check() {
+    let contract = "(define-data-var counter int 0)
+                    (define-private (get-counter) (var-get counter))
+                    (define-public (incr) (ok (var-set counter (+ (get-counter) 1))))";
+    let (_, analysis) = mem_type_check(contract).unwrap();
+
+    let contract_identifier = QualifiedContractIdentifier::transient();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    // a no-op edit of a definition which others depend on
+    let edited = parse(&contract_identifier, "(define-private (get-counter) (var-get counter))").unwrap();
+    let result = db.execute(|db| TypeChecker::check_definition(&analysis, db, &edited[0])).unwrap();
+    let body = &edited[0].match_list().unwrap()[2];
+    assert_eq!(result.type_map.get_type(body), Some(&IntType));
+    assert!(result.diagnostics.is_empty());
+
+    // a new definition may use the existing definitions
+    let added = parse(&contract_identifier, "(define-read-only (peek) (+ (get-counter) 1))").unwrap();
+    let result = db.execute(|db| TypeChecker::check_definition(&analysis, db, &added[0])).unwrap();
+    let body = &added[0].match_list().unwrap()[2];
+    assert_eq!(result.type_map.get_type(body), Some(&IntType));
+
+    let broken = parse(&contract_identifier, "(define-private (get-counter) (var-get missing))").unwrap();
+    let err = db.execute(|db| TypeChecker::check_definition(&analysis, db, &broken[0])).unwrap_err();
+    assert_eq!(err.err, CheckErrors::NoSuchDataVariable("missing".to_string()));
+}
+
+fn check_special_some_of(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    if args.len() != 1 {
+        return Err(CheckErrors::IncorrectArgumentCount(1, args.len()).into())
+    }
+    let inner = checker.type_check(&args[0], context)?;
+    Ok(TypeSignature::new_option(inner)?)
+}
+
+#[test]
+fn test_custom_natives() {
+    let mut custom_natives = CustomNatives::default();
+    let is_positive = TypedNativeFunction::Simple(SimpleNativeFunction(
+        FunctionType::Fixed(FixedFunction {
+            args: vec![FunctionArg::new(IntType, "x".into())],
+            returns: BoolType })));
+    custom_natives.register("is-positive".into(), is_positive.clone(), true).unwrap();
+    custom_natives.register("some-of".into(), TypedNativeFunction::Special(SpecialNativeFunction(&check_special_some_of)), true).unwrap();
+    custom_natives.register("record".into(), is_positive.clone(), false).unwrap();
+    let options = AnalysisOptions { custom_natives, ..AnalysisOptions::default() };
+
+    let good = ["(is-positive 1)",
+                "(define-read-only (check (x int)) (is-positive x)) (check 2)",
+                "(some-of (some-of u1))"];
+    let expected = ["bool", "bool", "(optional (optional uint))"];
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check_with_options(good_test, options.clone()).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    assert_eq!(mem_type_check_with_options("(is-positive u1)", options.clone()).unwrap_err().err,
+               CheckErrors::TypeError(IntType, UIntType));
+    assert_eq!(mem_type_check_with_options("(some-of)", options.clone()).unwrap_err().err,
+               CheckErrors::IncorrectArgumentCount(1, 0));
+
+    // without registration, these are unknown functions
+    assert_eq!(mem_type_check("(is-positive 1)").unwrap_err().err,
+               CheckErrors::UnknownFunction("is-positive".to_string()));
+
+    // a custom native registered as writing may only be applied outside read-only functions
+    mem_type_check_with_options("(define-public (check (x int)) (ok (record x)))", options.clone()).unwrap();
+    assert_eq!(mem_type_check_with_options("(define-read-only (check (x int)) (record x))", options.clone()).unwrap_err().err,
+               CheckErrors::WriteAttemptedInReadOnly);
+
+    // built-in functions cannot be shadowed
+    let mut custom_natives = CustomNatives::default();
+    for name in ["+", "map-get?", "let", "define-public", "block-height"].iter() {
+        assert_eq!(custom_natives.register((*name).into(), is_positive.clone(), true).unwrap_err().err,
+                   CheckErrors::NameAlreadyUsed(name.to_string()));
+    }
+}
+
+#[test]
+fn test_optional_trailing_args() {
+    let mut custom_natives = CustomNatives::default();
+    custom_natives.register("scale".into(), TypedNativeFunction::Simple(SimpleNativeFunction(
+        FunctionType::FixedWithOptional(FixedFunction {
+            args: vec![FunctionArg::new(IntType, "value".into())],
+            returns: IntType },
+            vec![FunctionArg::new(IntType, "factor".into()),
+                 FunctionArg::new(BoolType, "round".into())]))), true).unwrap();
+    let options = AnalysisOptions { custom_natives, ..AnalysisOptions::default() };
+
+    let good = ["(scale 1)",
+                "(scale 1 2)",
+                "(scale 1 2 true)"];
+    let expected = ["int", "int", "int"];
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check_with_options(good_test, options.clone()).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    let bad = ["(scale)",
+               "(scale 1 u2)",
+               "(scale 1 2 3)",
+               "(scale 1 2 true 4)"];
+    let bad_expected = [
+        CheckErrors::RequiresAtLeastArguments(1, 0),
+        CheckErrors::TypeError(IntType, UIntType),
+        CheckErrors::TypeError(BoolType, IntType),
+        CheckErrors::IncorrectArgumentCount(3, 4) ];
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check_with_options(bad_test, options.clone()).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_sequence_final_expression_type() {
+    // the intermediate `var-set` returns bool, but only the final expression's type is returned
+    let contract = "(define-data-var x int 0)
+                    (define-constant my-tuple (tuple (field u5)))
+                    (define-private (with-begin) (begin (var-set x 1) (get field my-tuple)))
+                    (define-private (with-let) (let ((y 2)) (var-set x y) (get field my-tuple)))";
+    let (_, analysis) = mem_type_check(contract).unwrap();
+
+    for function_name in ["with-begin", "with-let"].iter() {
+        match analysis.get_private_function(function_name) {
+            Some(FunctionType::Fixed(function)) => assert_eq!(UIntType, function.returns),
+            _ => panic!("{} should be a fixed function", function_name)
+        }
+    }
+}
+
+#[test]
+fn test_function_kinds() {
+    let contract = "(define-private (helper (x int)) x)
+                    (define-public (transfer (x int)) (if (> x 0) (ok (helper x)) (err u1)))
+                    (define-read-only (balance) 1)";
+    let (_, analysis) = mem_type_check(contract).unwrap();
+
+    assert_eq!(analysis.get_function_kind("helper"), Some(FunctionKind::Private));
+    assert_eq!(analysis.get_function_kind("transfer"), Some(FunctionKind::Public));
+    assert_eq!(analysis.get_function_kind("balance"), Some(FunctionKind::ReadOnly));
+    assert_eq!(analysis.get_function_kind("missing"), None);
+
+    match analysis.get_function("transfer") {
+        Some((FunctionKind::Public, FunctionType::Fixed(function))) =>
+            assert_eq!("(response int uint)", &function.returns.to_string()),
+        _ => panic!("transfer should be a public fixed function")
+    }
+}
+
+#[test]
+fn test_trait_dispatched_contract_call() {
+    // the callee is only known at runtime, so arguments and the result are typed by the trait
+    let contract = "(define-trait getter ((get-value (uint) (response uint (buff 8)))))
+                    (define-private (read-from (source <getter>))
+                      (contract-call? source get-value u1))";
+    let (_, analysis) = mem_type_check(contract).unwrap();
+    match analysis.get_private_function("read-from") {
+        Some(FunctionType::Fixed(function)) =>
+            assert_eq!("(response uint (buff 8))", &function.returns.to_string()),
+        _ => panic!("read-from should be a fixed function")
+    }
+
+    let bad = ["(define-trait getter ((get-value (uint) (response uint (buff 8)))))
+                (define-private (read-from (source <getter>))
+                  (contract-call? source get-value 1))",
+               "(define-trait getter ((get-value (uint) (response uint (buff 8)))))
+                (define-private (read-from (source <getter>))
+                  (contract-call? source set-value u1))"];
+    let bad_expected = [CheckErrors::TypeError(UIntType, IntType),
+                        CheckErrors::TraitMethodUnknown("getter".to_string(), "set-value".to_string())];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_consensus_buff() {
+    // the serialization of an `int` is 17 bytes, its type prefix and 16 bytes of contents
+    let good = ["(to-consensus-buff? 1)",
+                "(to-consensus-buff? (tuple (a 1) (b 0x0102)))",
+                "(to-consensus-buff? (list u1 u2))",
+                "(from-consensus-buff? (tuple (a int) (b (buff 2))) 0x00)",
+                "(from-consensus-buff? int (unwrap-panic (to-consensus-buff? 1)))"];
+    let expected = ["(optional (buff 17))",
+                    "(optional (buff 33))",
+                    "(optional (buff 39))",
+                    "(optional (tuple (a int) (b (buff 2))))",
+                    "(optional int)"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        let type_sig = mem_type_check(good_test).unwrap().0.unwrap();
+        assert_eq!(expected, &type_sig.to_string());
+    }
+
+    let bad = ["(from-consensus-buff? int 1)",
+               "(from-consensus-buff? (foo int) 0x00)",
+               "(from-consensus-buff? int)",
+               "(to-consensus-buff? 1 2)"];
+    let bad_expected = [CheckErrors::TypeError(TypeSignature::max_buffer(), IntType),
+                        CheckErrors::InvalidTypeDescription,
+                        CheckErrors::IncorrectArgumentCount(2, 1),
+                        CheckErrors::IncorrectArgumentCount(1, 2)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_get_on_response() {
+    // responses are not unwrapped by `get`, but the error suggests how to do so
+    let err = mem_type_check("(get a (ok (tuple (a 1))))").unwrap_err();
+    assert_eq!(err.err,
+               CheckErrors::ExpectedTuple(TypeSignature::new_response(TypeSignature::from("(tuple (a int))"),
+                                                                      TypeSignature::NoType).unwrap()));
+    assert!(err.diagnostic.suggestion.unwrap().contains("unwrap"));
+
+    let err = mem_type_check("(get a 1)").unwrap_err();
+    assert_eq!(err.err, CheckErrors::ExpectedTuple(IntType));
+    assert_eq!(err.diagnostic.suggestion, None);
+
+    assert_eq!("int", &type_check_helper("(get a (unwrap-panic (ok (tuple (a 1)))))").unwrap().to_string());
+}
+
+#[test]
+fn test_tuple_field_punning() {
+    let good = ["(let ((a 1) (b u2)) (tuple (a) (b)))",
+                "(let ((a 1)) (tuple (a) (c true)))",
+                "(define-constant owner tx-sender) (tuple (owner) (balance u10))",
+                "(define-private (pack (amount uint)) (tuple (amount) (memo 0x00))) (pack u1)"];
+    let expected = ["(tuple (a int) (b uint))",
+                    "(tuple (a int) (c bool))",
+                    "(tuple (balance uint) (owner principal))",
+                    "(tuple (amount uint) (memo (buff 1)))"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", mem_type_check(good_test).unwrap().0.unwrap()));
+    }
+
+    let bad = ["(tuple (a))",
+               "(let ((a 1)) (tuple (a) (a)))"];
+    let bad_expected = [
+        CheckErrors::UndefinedVariable("a".to_string()),
+        CheckErrors::NameAlreadyUsed("a".to_string()) ];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &mem_type_check(bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_tuple_field_suggestion() {
+    let err = mem_type_check("(get balnce (tuple (balance u1) (owner tx-sender)))").unwrap_err();
+    assert_eq!(err.err,
+               CheckErrors::NoSuchTupleField("balnce".to_string(),
+                                             TupleTypeSignature::try_from(vec![
+                                                 ("balance".into(), UIntType),
+                                                 ("owner".into(), PrincipalType)]).unwrap()));
+    assert_eq!(err.diagnostic.suggestion, Some("did you mean 'balance'?".to_string()));
+
+    let err = mem_type_check("(get height (tuple (balance u1) (owner tx-sender)))").unwrap_err();
+    assert_eq!(err.diagnostic.suggestion, None);
+}
+
+#[test]
+fn test_type_trace() {
+    let options = AnalysisOptions { trace: true, ..AnalysisOptions::default() };
+    let (_, analysis) = mem_type_check_with_options("(+ 1 (- 2 1))", options).unwrap();
+
+    let trace: Vec<_> = analysis.type_trace.iter()
+        .map(|entry| (entry.expression.as_str(), entry.type_signature.to_string()))
+        .collect();
+    assert_eq!(trace, vec![("1", "int".to_string()),
+                           ("2", "int".to_string()),
+                           ("1", "int".to_string()),
+                           ("( - 2 1 )", "int".to_string()),
+                           ("( + 1 ( - 2 1 ) )", "int".to_string())]);
+
+    let outer = &analysis.type_trace[4].span;
+    assert_eq!((outer.start_line, outer.start_column, outer.end_line, outer.end_column), (1, 1, 1, 13));
+    let inner = &analysis.type_trace[3].span;
+    assert_eq!((inner.start_column, inner.end_column), (6, 12));
+
+    // tracing is off by default
+    assert!(mem_type_check("(+ 1 (- 2 1))").unwrap().1.type_trace.is_empty());
+}
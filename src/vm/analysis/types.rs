@@ -1,20 +1,202 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
-use vm::{SymbolicExpression, ClarityName};
-use vm::types::{TypeSignature, FunctionType, QualifiedContractIdentifier, TraitIdentifier};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use vm::{SymbolicExpression, ClarityName, is_reserved};
+use vm::representations::Span;
+use vm::diagnostic::Diagnostic;
+use vm::types::{TypeSignature, FunctionType, QualifiedContractIdentifier, TraitIdentifier, BlockInfoProperty};
 use vm::types::signatures::FunctionSignature;
 use vm::analysis::analysis_db::{AnalysisDatabase};
 use vm::analysis::errors::{CheckResult, CheckErrors};
 use vm::analysis::type_checker::contexts::TypeMap;
+use vm::analysis::type_checker::TypedNativeFunction;
 use vm::analysis::contract_interface_builder::ContractInterface;
+use vm::functions::NativeFunctions;
+use vm::functions::define::DefineFunctions;
 use vm::costs::{CostTracker, ExecutionCost, LimitedCostTracker};
 
 const DESERIALIZE_FAIL_MESSAGE: &str = "PANIC: Failed to deserialize bad database data in contract analysis.";
 const SERIALIZE_FAIL_MESSAGE: &str = "PANIC: Failed to deserialize bad database data in contract analysis.";
 
+/// How a function of a contract is defined: as a `define-private`, `define-public`,
+///   or `define-read-only`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FunctionKind {
+    Private,
+    Public,
+    ReadOnly,
+}
+
 pub trait AnalysisPass {
     fn run_pass(contract_analysis: &mut ContractAnalysis, analysis_db: &mut AnalysisDatabase) -> CheckResult<()>;
 }
 
+/// The default limit on the number of bindings in a single `let`.
+pub const DEFAULT_MAX_LET_BINDINGS: u32 = 256;
+
+/// Settings for the non-consensus behaviors of the analysis passes. The
+///   defaults match the behavior of contract publishing on the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisOptions {
+    /// run the opt-in lints, reporting their findings as warnings
+    ///   in `ContractAnalysis::diagnostics`
+    pub lint: bool,
+    /// reject applications of the integer-polymorphic arithmetic functions
+    ///   whose operands are all unprefixed integer literals, requiring the
+    ///   signedness to be stated by some operand
+    pub strict_numerics: bool,
+    /// the maximum number of bindings a single `let` may introduce
+    pub max_let_bindings: u32,
+    /// type checkers for functions which are not built into the language,
+    ///   consulted before the built-in natives
+    pub custom_natives: CustomNatives,
+    /// record the type inferred for every expression in `ContractAnalysis::type_trace`,
+    ///   to debug the type checker. This is slow, and not meant for production use.
+    pub trace: bool,
+    /// the `get-block-info?` properties known to the type checker, in addition to the built-in ones
+    pub block_info_properties: BlockInfoRegistry,
+    /// keep type checking the remaining top-level expressions after one fails, reporting each
+    ///   failure in `ContractAnalysis::diagnostics`. The analysis still fails with the first error.
+    pub continue_on_error: bool,
+    /// let a tuple type admit tuples with additional fields, e.g., pass a `(tuple (a int) (b int))`
+    ///   where a `(tuple (a int))` is expected. This only affects the type checker: the runtime still
+    ///   requires tuples to match exactly, so it is meant for tooling rather than contract publishing.
+    pub tuple_width_subtyping: bool,
+    /// the weights of the natives in the static cost of each defined function,
+    ///   see `ContractAnalysis::get_function_cost`
+    pub cost_table: CostTable,
+}
+
+/// A type inferred by the type checker, in the order the checker completed them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeTrace {
+    pub span: Span,
+    pub expression: String,
+    pub type_signature: TypeSignature,
+}
+
+/// A registry of type checkers for experimental natives. These are only known to the
+///   analysis passes: a contract applying them type checks, but cannot be executed.
+#[derive(Clone, Default)]
+pub struct CustomNatives {
+    checkers: HashMap<ClarityName, (TypedNativeFunction, bool)>
+}
+
+impl CustomNatives {
+    /// Registers the type checker of a custom native, and whether applying it leaves
+    ///   contract state unchanged, for the read-only checker. Custom natives are consulted
+    ///   before the built-in ones, so the names of built-in functions are rejected.
+    pub fn register(&mut self, name: ClarityName, checker: TypedNativeFunction, read_only: bool) -> CheckResult<()> {
+        if is_reserved(&name) || DefineFunctions::lookup_by_name(&name).is_some() {
+            return Err(CheckErrors::NameAlreadyUsed(name.to_string()).into())
+        }
+        self.checkers.insert(name, (checker, read_only));
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TypedNativeFunction> {
+        self.checkers.get(name).map(|(checker, _)| checker)
+    }
+
+    /// The name of each custom native, and whether it is read-only.
+    pub fn read_only_flags(&self) -> impl Iterator<Item = (&ClarityName, bool)> {
+        self.checkers.iter().map(|(name, (_, read_only))| (name, *read_only))
+    }
+}
+
+// checkers are closures, so registries are compared and printed by name alone.
+impl PartialEq for CustomNatives {
+    fn eq(&self, other: &CustomNatives) -> bool {
+        self.checkers.len() == other.checkers.len() &&
+            self.checkers.keys().all(|name| other.checkers.contains_key(name))
+    }
+}
+
+impl fmt::Debug for CustomNatives {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.checkers.keys()).finish()
+    }
+}
+
+/// A registry of `get-block-info?` properties which are not built into the language. As with
+///   custom natives, these are only known to the analysis passes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockInfoRegistry {
+    properties: HashMap<ClarityName, TypeSignature>
+}
+
+impl BlockInfoRegistry {
+    pub fn register(&mut self, name: ClarityName, value_type: TypeSignature) {
+        self.properties.insert(name, value_type);
+    }
+
+    /// Resolves a property name to the type of its value. The built-in properties
+    ///   cannot be overridden.
+    pub fn lookup(&self, name: &str) -> Option<TypeSignature> {
+        match BlockInfoProperty::lookup_by_name(name) {
+            Some(property) => Some(property.type_result()),
+            None => self.properties.get(name).cloned()
+        }
+    }
+}
+
+/// The weight of each native function in the static cost of the functions a contract defines.
+///   These weights only model fee schedules for tooling: they do not affect the costs
+///   charged when a contract is published or executed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostTable {
+    weights: HashMap<NativeFunctions, u64>
+}
+
+impl CostTable {
+    /// A table in which every native weighs nothing, to be filled in with `set_weight`.
+    pub fn empty() -> CostTable {
+        CostTable { weights: HashMap::new() }
+    }
+
+    pub fn set_weight(&mut self, function: NativeFunctions, weight: u64) {
+        self.weights.insert(function, weight);
+    }
+
+    pub fn weight(&self, function: &NativeFunctions) -> u64 {
+        self.weights.get(function).cloned().unwrap_or(0)
+    }
+}
+
+impl Default for CostTable {
+    /// Every native weighs 1, except those which read or write the contract's
+    ///   storage, and `contract-call?`, which runs another contract.
+    fn default() -> CostTable {
+        use vm::functions::NativeFunctions::*;
+        let mut table = CostTable::empty();
+        for function in NativeFunctions::ALL.iter() {
+            let weight = match function {
+                FetchVar | SetVar => 10,
+                FetchEntry | FetchContractEntry | SetEntry | InsertEntry | DeleteEntry | SetGetEntry => 20,
+                ContractCall => 100,
+                _ => 1
+            };
+            table.set_weight(*function, weight);
+        }
+        table
+    }
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> AnalysisOptions {
+        AnalysisOptions {
+            lint: false,
+            strict_numerics: false,
+            max_let_bindings: DEFAULT_MAX_LET_BINDINGS,
+            custom_natives: CustomNatives::default(),
+            trace: false,
+            block_info_properties: BlockInfoRegistry::default(),
+            continue_on_error: false,
+            tuple_width_subtyping: false,
+            cost_table: CostTable::default(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ContractAnalysis {
     pub contract_identifier: QualifiedContractIdentifier,
@@ -28,13 +210,32 @@ pub struct ContractAnalysis {
     pub non_fungible_tokens: BTreeMap<ClarityName, TypeSignature>,
     pub defined_traits: BTreeMap<ClarityName, BTreeMap<ClarityName, FunctionSignature>>,
     pub implemented_traits: BTreeSet<TraitIdentifier>,    
+    /// the topic and value type of each `print-event` in the contract, in the order checked
+    #[serde(default)]
+    pub print_events: Vec<(String, TypeSignature)>,
+    /// the other contracts this contract calls, reads from, or takes traits from
+    #[serde(default)]
+    pub dependencies: BTreeSet<QualifiedContractIdentifier>,
+    /// every native function applied in the contract, including those passed to
+    ///  `map`, `filter` and `fold`
+    #[serde(skip)]
+    pub used_natives: HashSet<NativeFunctions>,
+    /// the static cost of each defined function, weighted by `AnalysisOptions::cost_table`
+    #[serde(skip)]
+    pub function_costs: BTreeMap<ClarityName, u64>,
     pub contract_interface: Option<ContractInterface>,
     #[serde(skip)]
     pub expressions: Vec<SymbolicExpression>,
     #[serde(skip)]
     pub type_map: Option<TypeMap>,
     #[serde(skip)]
-    pub cost_track: Option<LimitedCostTracker>
+    pub cost_track: Option<LimitedCostTracker>,
+    #[serde(skip)]
+    pub analysis_options: AnalysisOptions,
+    #[serde(skip)]
+    pub diagnostics: Vec<Diagnostic>,
+    #[serde(skip)]
+    pub type_trace: Vec<TypeTrace>
 }
 
 impl ContractAnalysis {
@@ -52,9 +253,16 @@ impl ContractAnalysis {
             persisted_variable_types: BTreeMap::new(),
             defined_traits: BTreeMap::new(),
             implemented_traits: BTreeSet::new(),
+            print_events: Vec::new(),
+            dependencies: BTreeSet::new(),
+            used_natives: HashSet::new(),
+            function_costs: BTreeMap::new(),
             fungible_tokens: BTreeSet::new(),
             non_fungible_tokens: BTreeMap::new(),
-            cost_track: Some(cost_track)
+            cost_track: Some(cost_track),
+            analysis_options: AnalysisOptions::default(),
+            diagnostics: Vec::new(),
+            type_trace: Vec::new()
         }
     }
 
@@ -108,6 +316,45 @@ impl ContractAnalysis {
         self.implemented_traits.insert(trait_identifier);
     }
 
+    pub fn add_print_event(&mut self, topic: String, value_type: TypeSignature) {
+        self.print_events.push((topic, value_type));
+    }
+
+    pub fn add_dependency(&mut self, contract_identifier: QualifiedContractIdentifier) {
+        // a contract may use traits it defines itself, which is not a dependency.
+        if contract_identifier != self.contract_identifier {
+            self.dependencies.insert(contract_identifier);
+        }
+    }
+
+    pub fn add_used_native(&mut self, native_function: NativeFunctions) {
+        self.used_natives.insert(native_function);
+    }
+
+    /// Returns the native functions the contract uses, e.g., to audit whether it
+    ///   transfers assets or calls other contracts.
+    pub fn used_natives(&self) -> &HashSet<NativeFunctions> {
+        &self.used_natives
+    }
+
+    pub fn add_function_cost(&mut self, name: ClarityName, cost: u64) {
+        self.function_costs.insert(name, cost);
+    }
+
+    /// Returns the static cost of the function defined as `name`: the weights of the natives
+    ///   it applies, including those of the functions it calls, with the callbacks of `map`,
+    ///   `filter` and `fold` counted once for each element they may visit.
+    pub fn get_function_cost(&self, name: &str) -> Option<u64> {
+        self.function_costs.get(name).cloned()
+    }
+
+    /// Returns the type inferred for an expression of the contract, including the intermediate
+    ///   expressions of a `begin`, whose types do not otherwise reach the analysis output.
+    pub fn get_expression_type(&self, expr: &SymbolicExpression) -> Option<&TypeSignature> {
+        self.type_map.as_ref()
+            .and_then(|type_map| type_map.get_type(expr))
+    }
+
     pub fn get_public_function_type(&self, name: &str) -> Option<&FunctionType> {
         self.public_function_types.get(name)
     }
@@ -120,6 +367,22 @@ impl ContractAnalysis {
         self.private_function_types.get(name)
     }
 
+    /// Returns the kind and type of the function defined as `name`, whichever kind it is.
+    pub fn get_function(&self, name: &str) -> Option<(FunctionKind, &FunctionType)> {
+        if let Some(function_type) = self.get_private_function(name) {
+            Some((FunctionKind::Private, function_type))
+        } else if let Some(function_type) = self.get_public_function_type(name) {
+            Some((FunctionKind::Public, function_type))
+        } else {
+            self.get_read_only_function_type(name)
+                .map(|function_type| (FunctionKind::ReadOnly, function_type))
+        }
+    }
+
+    pub fn get_function_kind(&self, name: &str) -> Option<FunctionKind> {
+        self.get_function(name).map(|(kind, _)| kind)
+    }
+
     pub fn get_map_type(&self, name: &str) -> Option<&(TypeSignature, TypeSignature)> {
         self.map_types.get(name)
     }
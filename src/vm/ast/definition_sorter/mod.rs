@@ -157,7 +157,7 @@ impl <'a> DefinitionSorter {
                                     }
                                     return Ok(());
                                 }, 
-                                NativeFunctions::SetEntry | NativeFunctions::InsertEntry => {
+                                NativeFunctions::SetEntry | NativeFunctions::InsertEntry | NativeFunctions::SetGetEntry => {
                                     // Args: [map-name, tuple-keys, tuple-values]: handle tuple-keys and tuple-values as tuples
                                     if function_args.len() == 3 {
                                         self.probe_for_dependencies(&function_args[0], tle_index)?;
@@ -166,6 +166,13 @@ impl <'a> DefinitionSorter {
                                     }
                                     return Ok(());
                                 }, 
+                                NativeFunctions::FetchContractEntry => {
+                                    // Args: [contract-name, map-name, tuple-predicate]: ignore contract-name, map-name, handle tuple-predicate as tuple
+                                    if function_args.len() == 3 {
+                                        self.probe_for_dependencies_in_tuple(&function_args[2], tle_index)?;
+                                    }
+                                    return Ok(());
+                                },
                                 NativeFunctions::ContractCall => {
                                     // Args: [contract-name, function-name, ...]: ignore contract-name, function-name, handle rest
                                     if function_args.len() > 2 {
@@ -199,6 +206,21 @@ impl <'a> DefinitionSorter {
                                     }
                                     return Ok(());
                                 },
+                                NativeFunctions::TupleGetIn => {
+                                    // Args: [expr, (key-name-0 key-name-1 ...)]: ignore key-names
+                                    if function_args.len() == 2 {
+                                        self.probe_for_dependencies(&function_args[0], tle_index)?;
+                                    }
+                                    return Ok(());
+                                },
+                                NativeFunctions::TupleGetAt => {
+                                    // Args: [list-expr, index-expr, key-name]: ignore key-name
+                                    if function_args.len() == 3 {
+                                        self.probe_for_dependencies(&function_args[0], tle_index)?;
+                                        self.probe_for_dependencies(&function_args[1], tle_index)?;
+                                    }
+                                    return Ok(());
+                                },
                                 NativeFunctions::TupleCons => {
                                     // Args: [(key-name A), (key-name-2 B), ...]: handle as a tuple
                                     self.probe_for_dependencies_in_tuple_list(function_args, tle_index)?;
@@ -254,6 +276,9 @@ impl <'a> DefinitionSorter {
                 if let Some(pair) = pair.match_list() {
                     if pair.len() == 2 {
                         self.probe_for_dependencies(&pair[1], tle_index)?;
+                    } else if pair.len() == 1 {
+                        // a punned field, `(a)`, may refer to a constant `a`
+                        self.probe_for_dependencies(&pair[0], tle_index)?;
                     }
                 }
             }
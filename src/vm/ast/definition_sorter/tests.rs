@@ -68,6 +68,27 @@ fn should_raise_dependency_cycle_case_1() {
     assert!(match err.err { ParseErrors::CircularReference(_) => true, _ => false });
 }
 
+#[test]
+fn should_raise_dependency_cycle_mutual_recursion() {
+    use vm::diagnostic::DiagnosableError;
+
+    let contract = r#"
+        (define-private (is-even (x uint)) (if (is-eq x u0) true (is-odd (- x u1))))
+        (define-private (is-odd (x uint)) (if (is-eq x u0) false (is-even (- x u1))))
+    "#;
+
+    let err = run_scoped_parsing_helper(contract).unwrap_err();
+    match err.err {
+        ParseErrors::CircularReference(ref function_names) => {
+            let mut function_names = function_names.clone();
+            function_names.sort();
+            assert_eq!(function_names, vec!["is-even".to_string(), "is-odd".to_string()]);
+        },
+        _ => panic!("expected a circular reference")
+    }
+    assert!(err.err.message().contains("may never return"));
+}
+
 #[test]
 fn should_raise_dependency_cycle_case_2() {
     let contract = r#"
@@ -221,6 +242,30 @@ fn should_raise_dependency_cycle_case_insert_entry() {
     assert!(match err.err { ParseErrors::CircularReference(_) => true, _ => false})
 }
 
+#[test]
+fn should_not_raise_dependency_cycle_case_set_get_entry() {
+    let contract = r#"
+        (define-private (foo (x int)) (begin (bar 1) 1))
+        (define-private (bar (x int)) (map-set-get kv-store ((foo 1)) ((bar 3))))
+        (define-map kv-store ((foo int)) ((bar int)))
+    "#;
+
+    run_scoped_parsing_helper(contract).unwrap();
+    run_analysis_helper(contract).unwrap();
+}
+
+#[test]
+fn should_raise_dependency_cycle_case_set_get_entry() {
+    let contract = r#"
+        (define-private (foo (x int)) (+ (bar x) x))
+        (define-private (bar (x int)) (map-set-get kv-store ((foo 1)) ((bar (foo 1)))))
+        (define-map kv-store ((foo int)) ((bar int)))
+    "#;
+
+    let err = run_scoped_parsing_helper(contract).unwrap_err();
+    assert!(match err.err { ParseErrors::CircularReference(_) => true, _ => false})
+}
+
 #[test]
 fn should_raise_dependency_cycle_case_fetch_contract_entry() {
     let contract = r#"
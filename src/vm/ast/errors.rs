@@ -147,7 +147,7 @@ impl DiagnosableError for ParseErrors {
             ParseErrors::TupleColonExpected(i) => format!("Tuple literal construction expects a colon at index {}", i),
             ParseErrors::TupleCommaExpected(i) => format!("Tuple literal construction expects a comma at index {}", i),
             ParseErrors::TupleItemExpected(i) => format!("Tuple literal construction expects a key or value at index {}", i),
-            ParseErrors::CircularReference(function_names) => format!("detected interdependent functions ({})", function_names.join(", ")),
+            ParseErrors::CircularReference(function_names) => format!("detected interdependent functions ({}): recursion is not allowed, since a cycle of calls may never return", function_names.join(", ")),
             ParseErrors::NameAlreadyUsed(name) => format!("defining '{}' conflicts with previous value", name),
             ParseErrors::ImportTraitBadSignature => format!("(use-trait ...) expects a trait name and a trait identifier"),
             ParseErrors::DefineTraitBadSignature => format!("(define-trait ...) expects a trait name and a trait definition"),
@@ -46,6 +46,8 @@ mod tests {
     use vm::costs::LimitedCostTracker;
     use vm::representations::depth_traverse;
     use std::collections::HashMap;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
     use super::*;
 
     fn dependency_edge_counting_runtime(iters: usize) -> u64 {
@@ -75,6 +77,30 @@ mod tests {
         assert!(ratio_8_16 > ratio_4_8);
     }
 
+    #[test]
+    fn test_structural_equality() {
+        let contract_id = QualifiedContractIdentifier::transient();
+        let first = parse(&contract_id, "(define-constant a 1) (+ a (* 2 a))").unwrap();
+        let second = parse(&contract_id, "(define-constant a 1)\n\n  (+ a\n     (* 2 a))").unwrap();
+
+        // the expressions are positioned differently, but have the same syntax
+        assert_ne!(first[1].span, second[1].span);
+        assert_eq!(first, second);
+
+        let mut first_hasher = DefaultHasher::new();
+        let mut second_hasher = DefaultHasher::new();
+        first[1].hash(&mut first_hasher);
+        second[1].hash(&mut second_hasher);
+        assert_eq!(first_hasher.finish(), second_hasher.finish());
+
+        // a sub-expression compares equal to the same syntax at the top level
+        let product = parse(&contract_id, "(* 2 a)").unwrap();
+        assert_eq!(&first[1].match_list().unwrap()[2], &product[0]);
+
+        let different = parse(&contract_id, "(define-constant a 1) (+ a (* 3 a))").unwrap();
+        assert_ne!(first, different);
+    }
+
     #[test]
     fn test_expression_identification_tuples() {
         let progn = "{ a: (+ 1 2 3),
@@ -726,8 +726,12 @@ impl <'a,'b> Environment <'a,'b> {
     }
 
     pub fn register_print_event(&mut self, value: Value) -> Result<()> {
+        self.register_topic_print_event("print".to_string(), value)
+    }
+
+    pub fn register_topic_print_event(&mut self, topic: String, value: Value) -> Result<()> {
         let print_event = SmartContractEventData {
-            key: (self.contract_context.contract_identifier.clone(), "print".to_string()),
+            key: (self.contract_context.contract_identifier.clone(), topic),
             value,
         };
         
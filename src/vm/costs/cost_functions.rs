@@ -27,6 +27,7 @@ def_runtime_cost!(ANALYSIS_CHECK_TUPLE_GET { LogN(1, 1) });
 def_runtime_cost!(ANALYSIS_CHECK_TUPLE_CONS { NLogN(1, 1) });
 def_runtime_cost!(ANALYSIS_TUPLE_ITEMS_CHECK { Linear(1, 1) });
 def_runtime_cost!(ANALYSIS_CHECK_LET { Linear(1, 1) });
+def_runtime_cost!(ANALYSIS_CONSTANT_FOLD { Linear(1, 1) });
 
 def_runtime_cost!(ANALYSIS_LOOKUP_FUNCTION { Constant(1) });
 def_runtime_cost!(ANALYSIS_LOOKUP_FUNCTION_TYPES { Linear(1, 1) });
@@ -112,7 +113,16 @@ def_runtime_cost!(SHA256 { Constant(1) });
 def_runtime_cost!(SHA512 { Constant(1) });
 def_runtime_cost!(SHA512T256 { Constant(1) });
 def_runtime_cost!(KECCAK256 { Constant(1) });
+def_runtime_cost!(RIPEMD160 { Constant(1) });
+def_runtime_cost!(HASH_PAIR { Constant(1) });
+def_runtime_cost!(SECP256K1RECOVER { Constant(1) });
+def_runtime_cost!(SECP256K1VERIFY { Constant(1) });
+def_runtime_cost!(TO_CONSENSUS_BUFF { Linear(1, 1) });
+def_runtime_cost!(FROM_CONSENSUS_BUFF { Linear(1, 1) });
+def_runtime_cost!(PRINCIPAL_DESTRUCT { Constant(1) });
+def_runtime_cost!(PRINCIPAL_CONSTRUCT { Constant(1) });
 def_runtime_cost!(PRINT { Linear(1, 1) });
+def_runtime_cost!(TEST_ASSERT { Linear(1, 1) });
 def_runtime_cost!(SOME_CONS { Constant(1) });
 def_runtime_cost!(OK_CONS { Constant(1) });
 def_runtime_cost!(ERR_CONS { Constant(1) });
@@ -122,6 +132,11 @@ def_runtime_cost!(UNWRAP_ERR_OR_RET { Constant(1) });
 def_runtime_cost!(IS_OKAY { Constant(1) });
 def_runtime_cost!(IS_NONE { Constant(1) });
 def_runtime_cost!(IS_ERR { Constant(1) });
+def_runtime_cost!(GET_OK { Constant(1) });
+def_runtime_cost!(GET_ERR { Constant(1) });
+def_runtime_cost!(FLATTEN { Constant(1) });
+def_runtime_cost!(BUFF_TO_UINT_LE { Constant(1) });
+def_runtime_cost!(BUFF_TO_UINT_BE { Constant(1) });
 def_runtime_cost!(IS_SOME { Constant(1) });
 def_runtime_cost!(UNWRAP { Constant(1) });
 def_runtime_cost!(UNWRAP_ERR { Constant(1) });
@@ -200,6 +215,14 @@ pub const SET_ENTRY: SimpleCostSpecification = SimpleCostSpecification {
     read_length: Constant(0)
 };
 
+pub const SET_GET_ENTRY: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Linear(1, 1),
+    write_count: Constant(1),
+    runtime: Linear(1, 1),
+    read_count: Constant(1),
+    read_length: Linear(1, 1)
+};
+
 pub const FETCH_VAR: SimpleCostSpecification = SimpleCostSpecification {
     write_length: Constant(0),
     write_count: Constant(0),
@@ -237,6 +260,13 @@ pub const STX_TRANSFER: SimpleCostSpecification = SimpleCostSpecification {
     read_count: Constant(1),
     read_length: Constant(1) };
 
+pub const STX_ACCOUNT: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Constant(0),
+    write_count: Constant(0),
+    runtime: Constant(1),
+    read_count: Constant(1),
+    read_length: Constant(1) };
+
 pub const FT_MINT: SimpleCostSpecification = SimpleCostSpecification {
     write_length: Constant(1),
     write_count: Constant(2),
@@ -258,6 +288,13 @@ pub const FT_BALANCE: SimpleCostSpecification = SimpleCostSpecification {
     read_count: Constant(1),
     read_length: Constant(1) };
 
+pub const FT_SUPPLY: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Constant(0),
+    write_count: Constant(0),
+    runtime: Constant(1),
+    read_count: Constant(1),
+    read_length: Constant(1) };
+
 pub const NFT_MINT: SimpleCostSpecification = SimpleCostSpecification {
     write_length: Constant(1),
     write_count: Constant(1),
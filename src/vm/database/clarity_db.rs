@@ -429,6 +429,13 @@ impl <'a> ClarityDatabase <'a> {
         self.inner_set_entry(contract_identifier, map_name, key, value, true)
     }
 
+    /// Like `set_entry`, but returns the value previously associated with the key, or `none`.
+    pub fn set_get_entry(&mut self, contract_identifier: &QualifiedContractIdentifier, map_name: &str, key: Value, value: Value) -> Result<Value> {
+        let previous = self.fetch_entry(contract_identifier, map_name, &key)?;
+        self.inner_set_entry(contract_identifier, map_name, key, value, false)?;
+        Ok(previous)
+    }
+
     fn data_map_entry_exists(&mut self, key: &str, expected_value: &TypeSignature) -> Result<bool> {
         match self.get_value(key, expected_value) {
             None => Ok(false),
@@ -518,22 +525,37 @@ impl <'a> ClarityDatabase <'a> {
     pub fn checked_increase_token_supply(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str, amount: u128) -> Result<()> {
         let descriptor = self.load_ft(contract_identifier, token_name)?;
 
-        if let Some(total_supply) = descriptor.total_supply {
-            let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
-            let current_supply: u128 = self.get(&key)
-                .expect("ERROR: Clarity VM failed to track token supply.");
- 
-            let new_supply = current_supply.checked_add(amount)
-                .ok_or(RuntimeErrorType::ArithmeticOverflow)?;
+        let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
+        let current_supply = self.get_circulating_supply(&key, descriptor.total_supply.is_some());
+
+        let new_supply = current_supply.checked_add(amount)
+            .ok_or(RuntimeErrorType::ArithmeticOverflow)?;
 
+        if let Some(total_supply) = descriptor.total_supply {
             if new_supply > total_supply {
-                Err(RuntimeErrorType::SupplyOverflow(new_supply, total_supply).into())
-            } else {
-                self.put(&key, &new_supply);
-                Ok(())
+                return Err(RuntimeErrorType::SupplyOverflow(new_supply, total_supply).into())
             }
+        }
+
+        self.put(&key, &new_supply);
+        Ok(())
+    }
+
+    pub fn get_ft_supply(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str) -> Result<u128> {
+        let descriptor = self.load_ft(contract_identifier, token_name)?;
+
+        let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
+        Ok(self.get_circulating_supply(&key, descriptor.total_supply.is_some()))
+    }
+
+    /// The circulating supply of a token is stored when a token with a total supply is defined,
+    ///   but only once a token without one is first minted.
+    fn get_circulating_supply(&mut self, key: &str, is_bounded: bool) -> u128 {
+        if is_bounded {
+            self.get(key)
+                .expect("ERROR: Clarity VM failed to track token supply.")
         } else {
-            Ok(())
+            self.get(key).unwrap_or(0)
         }
     }
 
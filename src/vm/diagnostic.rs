@@ -3,11 +3,13 @@ use std::fmt;
 
 /// In a near future, we can go further in our static analysis and provide different levels 
 /// of diagnostics, such as warnings, hints, best practices, etc.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[derive(Serialize, Deserialize)]
 #[derive(PartialEq)]
 pub enum Level {
     Error,
+    Warning,
+    Note,
 }
 
 pub trait DiagnosableError {
@@ -15,7 +17,7 @@ pub trait DiagnosableError {
     fn suggestion(&self) -> Option<String>;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[derive(Serialize, Deserialize)]
 #[derive(PartialEq)]
 pub struct Diagnostic {
@@ -35,6 +37,31 @@ impl Diagnostic {
             suggestion: error.suggestion(),
         }
     }
+
+    pub fn warn(warning: &dyn DiagnosableError) -> Diagnostic {
+        Diagnostic {
+            spans: vec![],
+            level: Level::Warning,
+            message: warning.message(),
+            suggestion: warning.suggestion(),
+        }
+    }
+
+    pub fn note(note: &dyn DiagnosableError) -> Diagnostic {
+        Diagnostic {
+            spans: vec![],
+            level: Level::Note,
+            message: note.message(),
+            suggestion: note.suggestion(),
+        }
+    }
+}
+
+/// Serializes diagnostics as a JSON array, for tools (e.g., CI jobs) which consume them.
+///   Each diagnostic is an object with its `level`, `message`, `spans` and `suggestion`.
+pub fn diagnostics_to_json(diagnostics: &[Diagnostic]) -> String {
+    serde_json::to_string(diagnostics)
+        .expect("FAIL: diagnostics failed to serialize")
 }
 
 impl fmt::Display for Diagnostic {
@@ -162,6 +162,39 @@ const MUL_API: SimpleFunctionAPI = SimpleFunctionAPI {
 "
 };
 
+const CHECKED_ADD_API: SpecialAPI = SpecialAPI {
+    input_type: "int, int | uint, uint",
+    output_type: "(response int uint) | (response uint uint)",
+    signature: "(+! i1 i2)",
+    description: "Adds two integer inputs, returning the result wrapped in an `ok`. In the event of an _overflow_,
+rather than throwing a runtime error, returns `(err u1)`.",
+    example: "(+! 1 2) ;; Returns (ok 3)
+(+! u340282366920938463463374607431768211455 u1) ;; Returns (err u1)
+"
+};
+
+const CHECKED_SUB_API: SpecialAPI = SpecialAPI {
+    input_type: "int, int | uint, uint",
+    output_type: "(response int uint) | (response uint uint)",
+    signature: "(-! i1 i2)",
+    description: "Subtracts the second integer input from the first, returning the result wrapped in an `ok`.
+In the event of an _overflow_ or _underflow_, rather than throwing a runtime error, returns `(err u1)`.",
+    example: "(-! 2 3) ;; Returns (ok -1)
+(-! u2 u3) ;; Returns (err u1)
+"
+};
+
+const CHECKED_MUL_API: SpecialAPI = SpecialAPI {
+    input_type: "int, int | uint, uint",
+    output_type: "(response int uint) | (response uint uint)",
+    signature: "(*! i1 i2)",
+    description: "Multiplies two integer inputs, returning the result wrapped in an `ok`. In the event of an
+_overflow_, rather than throwing a runtime error, returns `(err u1)`.",
+    example: "(*! 2 3) ;; Returns (ok 6)
+(*! u340282366920938463463374607431768211455 u2) ;; Returns (err u1)
+"
+};
+
 const MOD_API: SimpleFunctionAPI = SimpleFunctionAPI {
     name: None,
     signature: "(mod i1 i2)",
@@ -268,6 +301,12 @@ fn make_for_simple_native(api: &SimpleFunctionAPI, function: &NativeFunctions, n
                     let in_types: Vec<String> = args.iter().map(|x| format!("{}", x.signature)).collect();
                     in_types.join(", ")
                 },
+                FunctionType::FixedWithOptional(FixedFunction{ ref args, .. }, ref optional_args) => {
+                    let in_types: Vec<String> = args.iter().map(|x| format!("{}", x.signature))
+                        .chain(optional_args.iter().map(|x| format!("[{}]", x.signature)))
+                        .collect();
+                    in_types.join(", ")
+                },
                 FunctionType::UnionArgs(ref in_types, _) => {
                     let in_types: Vec<String> = in_types.iter().map(|x| format!("{}", x)).collect();
                     in_types.join(" | ")
@@ -277,7 +316,8 @@ fn make_for_simple_native(api: &SimpleFunctionAPI, function: &NativeFunctions, n
             };
             let output_type = match function_type {
                 FunctionType::Variadic(_, ref out_type) => format!("{}", out_type),
-                FunctionType::Fixed(FixedFunction{ ref returns, .. }) => format!("{}", returns),
+                FunctionType::Fixed(FixedFunction{ ref returns, .. }) |
+                FunctionType::FixedWithOptional(FixedFunction{ ref returns, .. }, _) => format!("{}", returns),
                 FunctionType::UnionArgs(_, ref out_type) => format!("{}", out_type),
                 FunctionType::ArithmeticVariadic | FunctionType::ArithmeticBinary => "int | uint".to_string(),
                 FunctionType::ArithmeticComparison => "bool".to_string(),
@@ -316,9 +356,16 @@ const IF_API: SpecialAPI = SpecialAPI {
     description: "The `if` function admits a boolean argument and two expressions
 which must return the same type. In the case that the boolean input is `true`, the
 `if` function evaluates and returns `expr1`. If the boolean input is `false`, the
-`if` function evaluates and returns `expr2`.",
+`if` function evaluates and returns `expr2`. The condition may also be an `(optional bool)`,
+in which case `none` is treated as `false`.
+
+If the boolean argument is `(is-some x)` or `(is-none x)`, where `x` is an optional bound by
+`let` or a function argument, then the type checker types `x` as the optional's inner type in
+the branch where `x` must be `some`. This only affects type checking: the value bound to `x`
+is not changed.",
     example: "(if true 1 2) ;; Returns 1
-(if (> 1 2) 1 2) ;; Returns 2"
+(if (> 1 2) 1 2) ;; Returns 2
+(if (some true) 1 2) ;; Returns 1"
 };
 
 const LET_API: SpecialAPI = SpecialAPI {
@@ -354,8 +401,10 @@ const MAP_API: SpecialAPI = SpecialAPI {
     output_type: "(list B)",
     signature: "(map func list)",
     description: "The `map` function applies the input function `func` to each element of the
-input list, and outputs a list containing the _outputs_ from those function applications.",
-    example: "(map not (list true false true false)) ;; Returns false true false true"
+input list, and outputs a list containing the _outputs_ from those function applications.
+`func` may also be an inline lambda, e.g., `(lambda (x) (+ x 1))`.",
+    example: "(map not (list true false true false)) ;; Returns false true false true
+(map (lambda (x) (* x 2)) (list 1 2 3)) ;; Returns 2 4 6"
 };
 
 const FILTER_API: SpecialAPI = SpecialAPI {
@@ -375,12 +424,14 @@ const FOLD_API: SpecialAPI = SpecialAPI {
 input list _and_ the output of the previous application of the `fold` function. When invoked on
 the first list element, it uses the `initial-value` as the second input. `fold` returns the last
 value returned by the successive applications. Note that the first argument is not evaluated thus
-has to be a literal function name.",
+has to be a literal function name, or an inline lambda such as `(lambda (item acc) (+ item acc))`.
+A lambda's accumulator has the type of `initial-value`.",
     example: "(fold * (list 2 2 2) 1) ;; Returns 8
 (fold * (list 2 2 2) 0) ;; Returns 0
 (fold - (list 3 7 11) 2) ;; Returns 5 by calculating (- 11 (- 7 (- 3 2)))
 (fold concat \"cdef\" \"ab\")   ;; Returns \"fedcab\"
-(fold concat (list \"cd\" \"ef\") \"ab\")   ;; Returns \"efcdab\""
+(fold concat (list \"cd\" \"ef\") \"ab\")   ;; Returns \"efcdab\"
+(fold (lambda (x acc) (+ acc (* x x))) (list 1 2 3) 0) ;; Returns 14"
 };
 
 const CONCAT_API: SpecialAPI = SpecialAPI {
@@ -448,6 +499,29 @@ nodes configured for development (as opposed to production mining nodes), this f
     example: "(print (+ 1 2 3)) ;; Returns 6",
 };
 
+const PRINT_EVENT_API: SpecialAPI = SpecialAPI {
+    input_type: "string literal, A",
+    output_type: "A",
+    signature: "(print-event topic expr)",
+    description: "The `print-event` function evaluates and returns its input expression, emitting it as
+a contract event under the given topic, so that event observers may subscribe to a single kind of event.
+The topic must be a string literal of printable ascii characters. The topic and type of each
+`print-event` are also recorded during contract analysis.",
+    example: "(print-event \"deposit\" (+ 1 2 3)) ;; Returns 6",
+};
+
+const TEST_ASSERT_API: SpecialAPI = SpecialAPI {
+    input_type: "bool, buff",
+    output_type: "bool",
+    signature: "(test-assert condition message)",
+    description: "The `test-assert` function evaluates and returns `condition`. If `condition` is `false`,
+`message` is emitted as a contract event under the `test-assert` topic, without aborting the transaction,
+so that a contract's tests can report each failed assertion. The type of `message` is recorded during
+contract analysis, as for `print-event`.",
+    example: "(test-assert (is-eq (+ 1 2) 3) \"addition\") ;; Returns true
+(test-assert (> 1 2) \"comparison\") ;; Returns false, emitting \"comparison\"",
+};
+
 const FETCH_ENTRY_API: SpecialAPI = SpecialAPI {
     input_type: "MapName, tuple",
     output_type: "(optional (tuple))",
@@ -455,9 +529,25 @@ const FETCH_ENTRY_API: SpecialAPI = SpecialAPI {
     description: "The `map-get?` function looks up and returns an entry from a contract's data map.
 The value is looked up using `key-tuple`.
 If there is no value associated with that key in the data map, the function returns a `none` option. Otherwise,
-it returns `(some value)`.",
+it returns `(some value)`. If the map's keys are tuples with a single field, the value of that field may be
+passed in place of `key-tuple`.",
     example: "(unwrap-panic (map-get? names-map (tuple (name \"blockstack\")))) ;; Returns (tuple (id 1337))
 (unwrap-panic (map-get? names-map ((name \"blockstack\")))) ;; Same command, using a shorthand for constructing the tuple
+(unwrap-panic (map-get? names-map \"blockstack\")) ;; Same command, passing the only field of the key
+",
+};
+
+const FETCH_CONTRACT_ENTRY_API: SpecialAPI = SpecialAPI {
+    input_type: "ContractName, MapName, tuple",
+    output_type: "(optional (tuple))",
+    signature: "(contract-map-get? .contract-name map-name key-tuple)",
+    description: "The `contract-map-get?` function looks up and returns an entry from a
+data map of another, already published, contract. The value is looked up using `key-tuple`.
+If there is no value associated with that key in the data map, the function returns a `none` option. Otherwise,
+it returns `(some value)`. As with `map-get?`, if the map's keys are tuples with a single field, the value of
+that field may be passed in place of `key-tuple`.",
+    example: "(unwrap-panic (contract-map-get? .names-contract names-map (tuple (name \"blockstack\")))) ;; Returns (tuple (id 1337))
+(unwrap-panic (contract-map-get? .names-contract names-map ((name \"blockstack\")))) ;; Same command, using a shorthand for constructing the tuple
 ",
 };
 
@@ -493,6 +583,18 @@ and therefore the maximum size of a value that may be inserted into a map is MAX
 ",
 };
 
+const SET_GET_ENTRY_API: SpecialAPI = SpecialAPI {
+    input_type: "MapName, tuple_A, tuple_B",
+    output_type: "(optional (tuple))",
+    signature: "(map-set-get map-name key-tuple value-tuple)",
+    description: "The `map-set-get` function sets the value associated with the input key to the
+inputted value, like `map-set`, and returns the value which was previously associated with the key.
+If there was no such value, this function returns `none`.",
+    example: "(map-set-get names-map (tuple (name \"blockstack\")) (tuple (id 1337))) ;; Returns none
+(map-set-get names-map (tuple (name \"blockstack\")) (tuple (id 1338))) ;; Returns (some (tuple (id 1337)))
+",
+};
+
 const DELETE_ENTRY_API: SpecialAPI = SpecialAPI {
     input_type: "MapName, tuple",
     output_type: "bool",
@@ -530,6 +632,32 @@ the tuple. If the supplied option is a `(none)` option, get returns `(none)`.",
 "
 };
 
+const TUPLE_GET_IN_API: SpecialAPI = SpecialAPI {
+    input_type: "(tuple) | (optional (tuple)), (KeyName ...)",
+    output_type: "A",
+    signature: "(get-in tuple (key-name-0 key-name-1 ...))",
+    description: "The `get-in` function fetches the value at a path of keys through nested tuples,
+as if applying `get` once for each key in the path. If an `Optional` tuple is encountered at any
+step of the path, `get-in` returns an `Optional` type of the final key. If that option is a `(none)` option,
+`get-in` returns `(none)`.",
+    example: "(get-in (tuple (user (tuple (name \"blockstack\") (id 1337)))) (user id)) ;; Returns 1337
+(get-in (map-get? users-map (tuple (account tx-sender))) (profile id)) ;; Returns (some 1337)
+"
+};
+
+const TUPLE_GET_AT_API: SpecialAPI = SpecialAPI {
+    input_type: "(list (tuple)), int, KeyName",
+    output_type: "(optional A)",
+    signature: "(get-at list index key-name)",
+    description: "The `get-at` function fetches the value associated with a given key from the tuple at
+the given (zero-based) index of a list of tuples, as if applying `get` to that item. Since the index may
+be out of bounds, `get-at` returns an `Optional` type of the specified key, which is `(none)` if the
+list has no item at that index.",
+    example: "(get-at (list (tuple (id 1)) (tuple (id 2))) 1 id) ;; Returns (some 2)
+(get-at (list (tuple (id 1)) (tuple (id 2))) 2 id) ;; Returns none
+"
+};
+
 const HASH160_API: SpecialAPI = SpecialAPI {
     input_type: "buff|uint|int",
     output_type: "(buff 20)",
@@ -581,6 +709,100 @@ is supplied the hash is computed over the little-endian representation of the in
     example: "(keccak256 0) ;; Returns 0xf490de2920c8a35fabeb13208852aa28c76f9be9b03a4dd2b3c075f7a26923b4"
 };
 
+const RIPEMD160_API: SpecialAPI = SpecialAPI {
+    input_type: "buff|uint|int",
+    output_type: "(buff 20)",
+    signature: "(ripemd160 value)",
+    description: "The `ripemd160` function computes `RIPEMD160(value)` of the inputted value.
+Unlike `hash160`, the value is not hashed with `SHA256` first. If an integer (128 bit)
+is supplied the hash is computed over the little-endian representation of the integer.",
+    example: "(ripemd160 0) ;; Returns 0xf2760c89487a4bf0d47f6ccca8d68915311a80d6"
+};
+
+const HASH_PAIR_API: SpecialAPI = SpecialAPI {
+    input_type: "(buff 32), (buff 32)",
+    output_type: "(buff 32)",
+    signature: "(hash-pair left right)",
+    description: "The `hash-pair` function computes `SHA256(SHA256(left || right))`, the parent of two
+nodes in a merkle tree, as used by Bitcoin's transaction merkle trees. Both inputs must be exactly
+32 bytes long. This may be folded over a merkle proof to check a leaf's inclusion under a known root.",
+    example: "(hash-pair 0x0000000000000000000000000000000000000000000000000000000000000000 0x0101010101010101010101010101010101010101010101010101010101010101) ;; Returns 0x705ede9d42476fc3e5a978b042ce790a193678f46d19f47ec4ab46539c47b76d"
+};
+
+const SECP256K1_RECOVER_API: SpecialAPI = SpecialAPI {
+    input_type: "(buff 32), (buff 65)",
+    output_type: "(response (buff 33) uint)",
+    signature: "(secp256k1-recover? message-hash signature)",
+    description: "The `secp256k1-recover?` function recovers the public key used to sign the message
+whose SHA256 hash is `message-hash` with the given `signature`. The signature is 65 bytes: the recovery
+id, followed by the 64-byte compact signature. If the key can be recovered, it is returned in
+its 33-byte compressed form, wrapped in `(ok ...)`. Otherwise, this returns `(err u1)`.",
+    example: "(secp256k1-recover? 0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9
+ 0x00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6) ;; Returns (ok 0x0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219)"
+};
+
+const SECP256K1_VERIFY_API: SpecialAPI = SpecialAPI {
+    input_type: "(buff 32), (buff 65), (buff 33)",
+    output_type: "bool",
+    signature: "(secp256k1-verify message-hash signature public-key)",
+    description: "The `secp256k1-verify` function checks that `signature` was produced by signing
+`message-hash` with the private key of the 33-byte compressed `public-key`. The signature is 65 bytes:
+the recovery id, followed by the 64-byte compact signature. Malformed keys and signatures return `false`.",
+    example: "(secp256k1-verify 0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9
+ 0x00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6
+ 0x0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219) ;; Returns true"
+};
+
+const TO_CONSENSUS_BUFF_API: SpecialAPI = SpecialAPI {
+    input_type: "any",
+    output_type: "(optional buff)",
+    signature: "(to-consensus-buff? value)",
+    description: "The `to-consensus-buff?` function serializes any Clarity value into a buffer, using the
+consensus serialization with which values are stored in the chainstate. The buffer type's length is the
+largest serialization of a value of the input's type. If the serialization is too large to be held in a
+buffer, this returns `none`.",
+    example: "(to-consensus-buff? 1) ;; Returns (some 0x0000000000000000000000000000000001)
+(to-consensus-buff? (tuple (a u1))) ;; Returns (some 0x0c0000000101610100000000000000000000000000000001)"
+};
+
+const FROM_CONSENSUS_BUFF_API: SpecialAPI = SpecialAPI {
+    input_type: "type-signature(t), buff",
+    output_type: "(optional t)",
+    signature: "(from-consensus-buff? type-signature buffer)",
+    description: "The `from-consensus-buff?` function deserializes a value of the given type from its
+consensus serialization, as produced by `to-consensus-buff?`. If the buffer is not exactly the
+serialization of a value of that type, this returns `none`.",
+    example: "(from-consensus-buff? int 0x0000000000000000000000000000000001) ;; Returns (some 1)
+(from-consensus-buff? uint 0x0000000000000000000000000000000001) ;; Returns none"
+};
+
+const PRINCIPAL_DESTRUCT_API: SpecialAPI = SpecialAPI {
+    input_type: "principal",
+    output_type: "(response (tuple (version (buff 1)) (hash-bytes (buff 20)) (name (optional (buff 40)))) (tuple (version (buff 1)) (hash-bytes (buff 20)) (name (optional (buff 40)))))",
+    signature: "(principal-destruct? principal)",
+    description: "The `principal-destruct?` function decomposes a principal into its version byte, the 20
+hash bytes of its address, and, for a contract principal, the contract name. For a standard principal,
+`name` is `none`. The parts are returned as `(ok ...)` if the version byte is that of a valid address
+(less than 32), and as `(err ...)` otherwise.",
+    example: "(principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; Returns (ok (tuple (hash-bytes 0xa46ff88886c2ef9762d970b4d2c63678835bd39d) (name none) (version 0x1f)))
+(principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.foo) ;; Returns (ok (tuple (hash-bytes 0xa46ff88886c2ef9762d970b4d2c63678835bd39d) (name (some 0x666f6f)) (version 0x1f)))"
+};
+
+const PRINCIPAL_CONSTRUCT_API: SpecialAPI = SpecialAPI {
+    input_type: "(buff 1), (buff 20), [(buff 40)]",
+    output_type: "(response principal (tuple (error_code uint) (value (optional principal))))",
+    signature: "(principal-construct? version-byte hash-bytes [contract-name])",
+    description: "The `principal-construct?` function builds a principal from a 1-byte version, the 20
+hash bytes of an address, and an optional contract name. It is the inverse of `principal-destruct?`.
+A version or hash buffer of the wrong length is a type error. If the principal is built and its version
+is that of a valid address (less than 32), it is returned as `(ok principal)`. If the version is not valid,
+the function returns `(err (tuple (error_code u0) (value (some principal))))`. If the parts cannot form a
+principal at all (a buffer is shorter than expected, or the name is not a valid contract name), the function
+returns `(err (tuple (error_code u1) (value none)))`.",
+    example: "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce) ;; Returns (ok ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM)
+(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce \"foo\") ;; Returns (ok ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM.foo)"
+};
+
 const CONTRACT_CALL_API: SpecialAPI = SpecialAPI {
     input_type: "ContractName, PublicFunctionName, Arg0, ...",
     output_type: "(response A B)",
@@ -795,6 +1017,60 @@ and `false` if it was an `err`.",
 (is-ok (err 1)) ;; Returns false",
 };
 
+const GET_OK_API: SpecialAPI = SpecialAPI {
+    input_type: "(response A B)",
+    output_type: "(optional A)",
+    signature: "(get-ok value)",
+    description: "`get-ok` projects the `ok` value out of a supplied response, returning `(some A)` if the
+response was `ok`, and `none` if it was an `err`.",
+    example: "(get-ok (ok 1)) ;; Returns (some 1)
+(get-ok (err u1)) ;; Returns none",
+};
+
+const GET_ERR_API: SpecialAPI = SpecialAPI {
+    input_type: "(response A B)",
+    output_type: "(optional B)",
+    signature: "(get-err value)",
+    description: "`get-err` projects the `err` value out of a supplied response, returning `(some B)` if the
+response was an `err`, and `none` if it was `ok`.",
+    example: "(get-err (err u1)) ;; Returns (some u1)
+(get-err (ok 1)) ;; Returns none",
+};
+
+const FLATTEN_API: SpecialAPI = SpecialAPI {
+    input_type: "(optional (optional A))",
+    output_type: "(optional A)",
+    signature: "(flatten value)",
+    description: "`flatten` collapses a nested optional, such as the result of `get` on an optional tuple
+whose field is itself optional. It returns `(some A)` if the supplied value is `(some (some A))`, and `none`
+if either the outer or the inner optional is `none`.",
+    example: "(flatten (some (some 1))) ;; Returns (some 1)
+(flatten (some none)) ;; Returns none
+(flatten none) ;; Returns none",
+};
+
+const BUFF_TO_UINT_LE_API: SpecialAPI = SpecialAPI {
+    input_type: "buff",
+    output_type: "uint",
+    signature: "(buff-to-uint-le buffer)",
+    description: "`buff-to-uint-le` converts a buffer of at most 16 bytes to a `uint`, reading the buffer
+in little-endian byte order. Shorter buffers are treated as if they were padded with zero bytes, and
+buffers of more than 16 bytes are rejected by the type checker.",
+    example: "(buff-to-uint-le 0x01) ;; Returns u1
+(buff-to-uint-le 0x0001) ;; Returns u256",
+};
+
+const BUFF_TO_UINT_BE_API: SpecialAPI = SpecialAPI {
+    input_type: "buff",
+    output_type: "uint",
+    signature: "(buff-to-uint-be buffer)",
+    description: "`buff-to-uint-be` converts a buffer of at most 16 bytes to a `uint`, reading the buffer
+in big-endian byte order. Shorter buffers are treated as if they were padded with zero bytes, and
+buffers of more than 16 bytes are rejected by the type checker.",
+    example: "(buff-to-uint-be 0x01) ;; Returns u1
+(buff-to-uint-be 0x0100) ;; Returns u256",
+};
+
 const IS_NONE_API: SpecialAPI = SpecialAPI {
     input_type: "(optional A)",
     output_type: "bool",
@@ -1134,6 +1410,19 @@ The token type must have been defined using `define-fungible-token`.",
 "
 };
 
+const GET_SUPPLY: SpecialAPI = SpecialAPI {
+    input_type: "TokenName",
+    output_type: "uint",
+    signature: "(ft-get-supply token-name)",
+    description: "`ft-get-supply` returns the circulating supply of `token-name`, i.e., the total amount
+of the token minted so far. The token type must have been defined using `define-fungible-token`.",
+    example: "
+(define-fungible-token stackaroos)
+(ft-mint? stackaroos u100 tx-sender)
+(ft-get-supply stackaroos) ;; Returns u100
+"
+};
+
 const TOKEN_TRANSFER: SpecialAPI = SpecialAPI {
     input_type: "TokenName, uint, principal, principal",
     output_type: "(response bool uint)",
@@ -1219,6 +1508,19 @@ one of the following error codes:
 "
 };
 
+const STX_GET_ACCOUNT: SpecialAPI = SpecialAPI {
+    input_type: "principal",
+    output_type: "(tuple (locked uint) (unlock-height uint) (unlocked uint))",
+    signature: "(stx-account owner)",
+    description: "`stx-account` returns the STX account of the `owner` principal as a tuple. The
+`unlocked` field holds the STX the principal can spend, while `locked` holds the STX that are locked until
+the burnchain block `unlock-height`. STX are never locked on this chain, so `locked` and `unlock-height`
+are always `u0`.",
+    example: "
+(stx-account tx-sender)
+"
+};
+
 fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
     use vm::functions::NativeFunctions::*;
     let name = function.get_name();
@@ -1228,6 +1530,9 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         ToInt => make_for_simple_native(&TO_INT_API, &ToInt, name),
         Subtract => make_for_simple_native(&SUB_API, &Subtract, name),
         Multiply => make_for_simple_native(&MUL_API, &Multiply, name),
+        CheckedAdd => make_for_special(&CHECKED_ADD_API, name),
+        CheckedSub => make_for_special(&CHECKED_SUB_API, name),
+        CheckedMul => make_for_special(&CHECKED_MUL_API, name),
         Divide => make_for_simple_native(&DIV_API, &Divide, name),
         CmpGeq => make_for_simple_native(&GEQ_API, &CmpGeq, name),
         CmpLeq => make_for_simple_native(&LEQ_API, &CmpLeq, name),
@@ -1253,18 +1558,32 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         Len => make_for_special(&LEN_API, name),
         ListCons => make_for_special(&LIST_API, name),
         FetchEntry => make_for_special(&FETCH_ENTRY_API, name),
+        FetchContractEntry => make_for_special(&FETCH_CONTRACT_ENTRY_API, name),
         SetEntry => make_for_special(&SET_ENTRY_API, name),
         InsertEntry => make_for_special(&INSERT_ENTRY_API, name),
         DeleteEntry => make_for_special(&DELETE_ENTRY_API, name),
+        SetGetEntry => make_for_special(&SET_GET_ENTRY_API, name),
         TupleCons => make_for_special(&TUPLE_CONS_API, name),
         TupleGet => make_for_special(&TUPLE_GET_API, name),
+        TupleGetIn => make_for_special(&TUPLE_GET_IN_API, name),
+        TupleGetAt => make_for_special(&TUPLE_GET_AT_API, name),
         Begin => make_for_special(&BEGIN_API, name),
         Hash160 => make_for_special(&HASH160_API, name),
         Sha256 => make_for_special(&SHA256_API, name),
         Sha512 => make_for_special(&SHA512_API, name),
         Sha512Trunc256 => make_for_special(&SHA512T256_API, name),
         Keccak256 => make_for_special(&KECCAK256_API, name),
+        Ripemd160 => make_for_special(&RIPEMD160_API, name),
+        HashPair => make_for_special(&HASH_PAIR_API, name),
+        Secp256k1Recover => make_for_special(&SECP256K1_RECOVER_API, name),
+        Secp256k1Verify => make_for_special(&SECP256K1_VERIFY_API, name),
+        ToConsensusBuff => make_for_special(&TO_CONSENSUS_BUFF_API, name),
+        FromConsensusBuff => make_for_special(&FROM_CONSENSUS_BUFF_API, name),
+        PrincipalDestruct => make_for_special(&PRINCIPAL_DESTRUCT_API, name),
+        PrincipalConstruct => make_for_special(&PRINCIPAL_CONSTRUCT_API, name),
         Print => make_for_special(&PRINT_API, name),
+        PrintEvent => make_for_special(&PRINT_EVENT_API, name),
+        TestAssert => make_for_special(&TEST_ASSERT_API, name),
         ContractCall => make_for_special(&CONTRACT_CALL_API, name),
         AsContract => make_for_special(&AS_CONTRACT_API, name),
         GetBlockInfo => make_for_special(&GET_BLOCK_INFO_API, name),
@@ -1280,18 +1599,25 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         Match => make_for_special(&MATCH_API, name),
         TryRet =>  make_for_special(&TRY_API, name),
         IsOkay => make_for_special(&IS_OK_API, name),
+        GetOk => make_for_special(&GET_OK_API, name),
+        GetErr => make_for_special(&GET_ERR_API, name),
+        Flatten => make_for_special(&FLATTEN_API, name),
+        BuffToUIntLe => make_for_special(&BUFF_TO_UINT_LE_API, name),
+        BuffToUIntBe => make_for_special(&BUFF_TO_UINT_BE_API, name),
         IsNone => make_for_special(&IS_NONE_API, name),
         IsErr => make_for_special(&IS_ERR_API, name),
         IsSome => make_for_special(&IS_SOME_API, name),
         MintAsset => make_for_special(&MINT_ASSET, name),
         MintToken => make_for_special(&MINT_TOKEN, name),
         GetTokenBalance => make_for_special(&GET_BALANCE, name),
+        GetTokenSupply => make_for_special(&GET_SUPPLY, name),
         GetAssetOwner => make_for_special(&GET_OWNER, name),
         TransferToken => make_for_special(&TOKEN_TRANSFER, name),
         TransferAsset => make_for_special(&ASSET_TRANSFER, name),
         AtBlock => make_for_special(&AT_BLOCK, name),
         StxTransfer => make_for_simple_native(&STX_TRANSFER, &StxTransfer, name),
         StxBurn => make_for_simple_native(&STX_BURN, &StxBurn, name),
+        StxGetAccount => make_for_special(&STX_GET_ACCOUNT, name),
     }
 }
 
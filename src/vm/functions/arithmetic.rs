@@ -1,10 +1,14 @@
 use std::convert::TryFrom;
-use vm::types::{Value, TypeSignature};
+use vm::types::{Value, TypeSignature, BuffData, BUFF_16, MAX_UINT_BUFFER_LEN};
 use vm::errors::{CheckErrors, RuntimeErrorType, InterpreterResult, check_argument_count};
 
 struct U128Ops();
 struct I128Ops();
 
+/// The error code returned by the checked arithmetic functions (`+!`, `-!`, `*!`)
+///   if the result overflows or underflows.
+pub const CHECKED_ARITHMETIC_ERR_CODE: u128 = 1;
+
 impl U128Ops {
     fn make_value(x: u128) -> InterpreterResult<Value> {
         Ok(Value::UInt(x))
@@ -111,6 +115,21 @@ macro_rules! make_arithmetic_ops { ($struct_name: ident, $type:ty) => {
                 .ok_or(RuntimeErrorType::ArithmeticOverflow)?;
             Self::make_value(result)
         }
+        fn make_checked_value(result: Option<$type>) -> InterpreterResult<Value> {
+            match result {
+                Some(x) => Value::okay(Self::make_value(x)?),
+                None => Value::error(Value::UInt(CHECKED_ARITHMETIC_ERR_CODE))
+            }
+        }
+        fn checked_add(x: $type, y: $type) -> InterpreterResult<Value> {
+            Self::make_checked_value(x.checked_add(y))
+        }
+        fn checked_sub(x: $type, y: $type) -> InterpreterResult<Value> {
+            Self::make_checked_value(x.checked_sub(y))
+        }
+        fn checked_mul(x: $type, y: $type) -> InterpreterResult<Value> {
+            Self::make_checked_value(x.checked_mul(y))
+        }
         fn div(args: &[$type]) -> InterpreterResult<Value> {
             let (first, rest) = args.split_first()
                 .ok_or(CheckErrors::IncorrectArgumentCount(1, 0))?;
@@ -166,6 +185,15 @@ pub fn native_sub(mut args: Vec<Value>) -> InterpreterResult<Value> {
 pub fn native_mul(mut args: Vec<Value>) -> InterpreterResult<Value> {
     type_force_variadic_arithmetic!(mul, args)
 }
+pub fn native_checked_add(a: Value, b: Value) -> InterpreterResult<Value> {
+    type_force_binary_arithmetic!(checked_add, a, b)
+}
+pub fn native_checked_sub(a: Value, b: Value) -> InterpreterResult<Value> {
+    type_force_binary_arithmetic!(checked_sub, a, b)
+}
+pub fn native_checked_mul(a: Value, b: Value) -> InterpreterResult<Value> {
+    type_force_binary_arithmetic!(checked_mul, a, b)
+}
 pub fn native_div(mut args: Vec<Value>) -> InterpreterResult<Value> {
     type_force_variadic_arithmetic!(div, args)
 }
@@ -195,3 +223,30 @@ pub fn native_to_int(input: Value) -> InterpreterResult<Value> {
         Err(CheckErrors::TypeValueError(TypeSignature::UIntType, input).into())
     }
 }
+
+fn buff_to_uint_bytes(input: Value) -> InterpreterResult<Vec<u8>> {
+    match input {
+        Value::Buffer(BuffData { data }) => {
+            if data.len() > MAX_UINT_BUFFER_LEN {
+                Err(CheckErrors::BufferTooLargeForInt(data.len() as u32).into())
+            } else {
+                Ok(data)
+            }
+        },
+        _ => Err(CheckErrors::TypeValueError(BUFF_16, input).into())
+    }
+}
+
+pub fn native_buff_to_uint_le(input: Value) -> InterpreterResult<Value> {
+    let data = buff_to_uint_bytes(input)?;
+    let mut bytes = [0u8; MAX_UINT_BUFFER_LEN];
+    bytes[..data.len()].copy_from_slice(&data);
+    Ok(Value::UInt(u128::from_le_bytes(bytes)))
+}
+
+pub fn native_buff_to_uint_be(input: Value) -> InterpreterResult<Value> {
+    let data = buff_to_uint_bytes(input)?;
+    let mut bytes = [0u8; MAX_UINT_BUFFER_LEN];
+    bytes[MAX_UINT_BUFFER_LEN - data.len()..].copy_from_slice(&data);
+    Ok(Value::UInt(u128::from_be_bytes(bytes)))
+}
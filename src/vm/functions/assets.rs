@@ -1,7 +1,7 @@
 use vm::functions::tuples;
 use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
 
-use vm::types::{Value, OptionalData, BuffData, PrincipalData, BlockInfoProperty, TypeSignature, AssetIdentifier};
+use vm::types::{Value, OptionalData, BuffData, PrincipalData, BlockInfoProperty, TypeSignature, AssetIdentifier, TupleData};
 use vm::representations::{SymbolicExpression};
 use vm::errors::{Error, InterpreterError, CheckErrors, RuntimeErrorType, InterpreterResult as Result, check_argument_count};
 use vm::{eval, LocalContext, Environment};
@@ -72,6 +72,28 @@ pub fn special_stx_transfer(args: &[SymbolicExpression],
     }
 }
 
+pub fn special_stx_account(args: &[SymbolicExpression],
+                           env: &mut Environment,
+                           context: &LocalContext) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    runtime_cost!(cost_functions::STX_ACCOUNT, env, 0)?;
+
+    let owner = eval(&args[0], env, context)?;
+
+    if let Value::Principal(ref principal) = owner {
+        // STX are never locked in this chain, so the whole balance is unlocked.
+        let balance = env.global_context.database.get_account_stx_balance(principal);
+        TupleData::from_data(vec![
+            ("locked".into(), Value::UInt(0)),
+            ("unlock-height".into(), Value::UInt(0)),
+            ("unlocked".into(), Value::UInt(balance))])
+            .map(Value::from)
+    } else {
+        Err(CheckErrors::TypeValueError(TypeSignature::PrincipalType, owner).into())
+    }
+}
+
 pub fn special_stx_burn(args: &[SymbolicExpression],
                         env: &mut Environment,
                         context: &LocalContext) -> Result<Value> {
@@ -341,6 +363,20 @@ pub fn special_get_balance(args: &[SymbolicExpression],
 
 }
 
+pub fn special_get_supply(args: &[SymbolicExpression],
+                          env: &mut Environment,
+                          _context: &LocalContext) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    runtime_cost!(cost_functions::FT_SUPPLY, env, 0)?;
+
+    let token_name = args[0].match_atom()
+        .ok_or(CheckErrors::BadTokenName)?;
+
+    let supply = env.global_context.database.get_ft_supply(&env.contract_context.contract_identifier, token_name)?;
+    Ok(Value::UInt(supply))
+}
+
 pub fn special_get_owner(args: &[SymbolicExpression],
                          env: &mut Environment,
                          context: &LocalContext) -> Result<Value> {
@@ -0,0 +1,45 @@
+use vm::errors::{CheckErrors, InterpreterResult as Result, check_argument_count};
+use vm::types::{Value, TypeSignature};
+use vm::representations::SymbolicExpression;
+use vm::costs::cost_functions;
+use vm::{LocalContext, Environment, eval};
+
+/// Serializes a value into its consensus representation, as stored in the database.
+///   If the serialization is too large to be a buffer, this returns `none`.
+pub fn special_to_consensus_buff(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    let value = eval(&args[0], env, context)?;
+
+    runtime_cost!(cost_functions::TO_CONSENSUS_BUFF, env, value.size())?;
+
+    let mut bytes = Vec::new();
+    value.serialize_write(&mut bytes)
+        .expect("IOError filling byte buffer.");
+
+    match Value::buff_from(bytes).and_then(Value::some) {
+        Ok(serialized) => Ok(serialized),
+        Err(_) => Ok(Value::none())
+    }
+}
+
+/// Deserializes a value of the given type from its consensus representation. Returns
+///   `none` if the buffer is not exactly the serialization of a value of that type.
+pub fn special_from_consensus_buff(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(2, args)?;
+
+    let expected_type = TypeSignature::parse_type_repr(&args[0], env)?;
+    let input = eval(&args[1], env, context)?;
+    let bytes = match input {
+        Value::Buffer(buff_data) => buff_data.data,
+        _ => return Err(CheckErrors::TypeValueError(TypeSignature::max_buffer(), input).into())
+    };
+
+    runtime_cost!(cost_functions::FROM_CONSENSUS_BUFF, env, bytes.len())?;
+
+    let mut remaining = bytes.as_slice();
+    match Value::deserialize_read(&mut remaining, Some(&expected_type)) {
+        Ok(value) if remaining.is_empty() => Value::some(value),
+        _ => Ok(Value::none())
+    }
+}
@@ -0,0 +1,52 @@
+use vm::errors::{CheckErrors, InterpreterResult as Result, check_argument_count};
+use vm::types::{Value, TypeSignature, BUFF_32, BUFF_33, BUFF_65};
+use util::secp256k1::{Secp256k1PublicKey, MessageSignature};
+use burnchains::PublicKey;
+
+/// The error code returned by `secp256k1-recover?` when no public key can be
+///   recovered from the signature.
+const RECOVER_FAILED_CODE: u128 = 1;
+
+fn expect_exact_buffer(value: Value, expected_type: &TypeSignature, length: usize) -> Result<Vec<u8>> {
+    match value {
+        Value::Buffer(buff_data) if buff_data.data.len() == length => Ok(buff_data.data),
+        _ => Err(CheckErrors::TypeValueError(expected_type.clone(), value).into())
+    }
+}
+
+/// Recovers the compressed public key which produced a signature over a 32-byte message hash.
+///   The signature is 65 bytes: the recovery id, followed by the 64-byte compact signature.
+pub fn native_secp256k1_recover(message_hash: Value, signature: Value) -> Result<Value> {
+    let message_hash = expect_exact_buffer(message_hash, &BUFF_32, 32)?;
+    let signature = expect_exact_buffer(signature, &BUFF_65, 65)?;
+
+    let signature = match MessageSignature::from_bytes(&signature) {
+        Some(signature) => signature,
+        None => return Value::error(Value::UInt(RECOVER_FAILED_CODE))
+    };
+
+    match Secp256k1PublicKey::recover_to_pubkey(&message_hash, &signature) {
+        Ok(pubkey) => Value::okay(Value::buff_from(pubkey.to_bytes_compressed())?),
+        Err(_) => Value::error(Value::UInt(RECOVER_FAILED_CODE))
+    }
+}
+
+/// Checks that a signature over a 32-byte message hash was produced by the given compressed
+///   public key. Malformed keys and signatures do not verify.
+pub fn native_secp256k1_verify(mut args: Vec<Value>) -> Result<Value> {
+    check_argument_count(3, &args)?;
+    let pubkey = expect_exact_buffer(args.pop().unwrap(), &BUFF_33, 33)?;
+    let signature = expect_exact_buffer(args.pop().unwrap(), &BUFF_65, 65)?;
+    let message_hash = expect_exact_buffer(args.pop().unwrap(), &BUFF_32, 32)?;
+
+    let pubkey = match Secp256k1PublicKey::from_slice(&pubkey) {
+        Ok(pubkey) => pubkey,
+        Err(_) => return Ok(Value::Bool(false))
+    };
+    let signature = match MessageSignature::from_bytes(&signature) {
+        Some(signature) => signature,
+        None => return Ok(Value::Bool(false))
+    };
+
+    Ok(Value::Bool(pubkey.verify(&message_hash, &signature).unwrap_or(false)))
+}
@@ -1,10 +1,11 @@
 use std::convert::{TryFrom, TryInto};
 use std::cmp;
 
-use vm::functions::tuples;
+use vm::functions::{tuples, single_field_key_type};
 use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
 
-use vm::types::{Value, OptionalData, BuffData, PrincipalData, BlockInfoProperty, TypeSignature, BUFF_32};
+use vm::types::{Value, OptionalData, BuffData, PrincipalData, BlockInfoProperty, TypeSignature, TupleData,
+                QualifiedContractIdentifier, BUFF_32};
 use vm::representations::{SymbolicExpression, SymbolicExpressionType};
 use vm::errors::{CheckErrors, InterpreterError, RuntimeErrorType, InterpreterResult as Result,
                  check_argument_count, check_arguments_at_least};
@@ -172,8 +173,35 @@ pub fn special_fetch_entry(args: &[SymbolicExpression],
         Explicit => eval(&args[1], env, &context)?
     };
 
-    let contract = &env.contract_context.contract_identifier;
+    let contract = env.contract_context.contract_identifier.clone();
+
+    fetch_entry(&contract, map_name, key, env)
+}
 
+/// Reads an entry from the map of another contract. Unlike a contract call, this does
+///   not execute any of the other contract's code.
+pub fn special_fetch_contract_entry(args: &[SymbolicExpression],
+                                    env: &mut Environment,
+                                    context: &LocalContext) -> Result<Value> {
+    check_argument_count(3, args)?;
+
+    let contract_identifier = match args[0].expr {
+        SymbolicExpressionType::LiteralValue(Value::Principal(PrincipalData::Contract(ref contract_identifier))) => contract_identifier,
+        _ => return Err(CheckErrors::ContractCallExpectName.into())
+    };
+
+    let map_name = args[1].match_atom()
+        .ok_or(CheckErrors::ExpectedName)?;
+
+    let key = match tuples::get_definition_type_of_tuple_argument(&args[2]) {
+        Implicit(ref expr) => tuples::tuple_cons(expr, env, context)?,
+        Explicit => eval(&args[2], env, &context)?
+    };
+
+    fetch_entry(contract_identifier, map_name, key, env)
+}
+
+fn fetch_entry(contract: &QualifiedContractIdentifier, map_name: &str, key: Value, env: &mut Environment) -> Result<Value> {
     // optimization todo: db metadata like this should just get stored
     //   in the contract object, so that it gets loaded in when the contract
     //   is loaded from the db.
@@ -181,6 +209,12 @@ pub fn special_fetch_entry(args: &[SymbolicExpression],
     runtime_cost!(cost_functions::FETCH_ENTRY, env,
                   data_types.value_type.size() + data_types.key_type.size())?;
 
+    let key = match single_field_key_type(&data_types.key_type) {
+        Some((field_name, _)) if !data_types.key_type.admits(&key) =>
+            Value::from(TupleData::from_data(vec![(field_name.clone(), key)])?),
+        _ => key
+    };
+
     env.global_context.database.fetch_entry(contract, map_name, &key)
 }
 
@@ -246,6 +280,40 @@ pub fn special_set_entry(args: &[SymbolicExpression],
     env.global_context.database.set_entry(contract, map_name, key, value)
 }
 
+pub fn special_set_get_entry(args: &[SymbolicExpression],
+                             env: &mut Environment,
+                             context: &LocalContext) -> Result<Value> {
+    if env.global_context.is_read_only() {
+        return Err(CheckErrors::WriteAttemptedInReadOnly.into())
+    }
+
+    check_argument_count(3, args)?;
+
+    let key = match tuples::get_definition_type_of_tuple_argument(&args[1]) {
+        Implicit(ref expr) => tuples::tuple_cons(expr, env, context)?,
+        Explicit => eval(&args[1], env, &context)?
+    };
+
+    let value = match tuples::get_definition_type_of_tuple_argument(&args[2]) {
+        Implicit(ref expr) => tuples::tuple_cons(expr, env, context)?,
+        Explicit => eval(&args[2], env, &context)?
+    };
+
+    let map_name = args[0].match_atom()
+        .ok_or(CheckErrors::ExpectedName)?;
+
+    let contract = &env.contract_context.contract_identifier;
+
+    let data_types = env.global_context.database.load_map(contract, map_name)?;
+    runtime_cost!(cost_functions::SET_GET_ENTRY, env,
+                  data_types.value_type.size() + data_types.key_type.size())?;
+
+    env.add_memory(key.get_memory_use())?;
+    env.add_memory(value.get_memory_use())?;
+
+    env.global_context.database.set_get_entry(contract, map_name, key, value)
+}
+
 pub fn special_insert_entry(args: &[SymbolicExpression],
                             env: &mut Environment,
                             context: &LocalContext) -> Result<Value> {
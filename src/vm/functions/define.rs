@@ -170,6 +170,20 @@ impl DefineFunctions {
 }
 
 impl <'a> DefineFunctionsParsed <'a> {
+    /// The name bound by this define-statement, if any.
+    pub fn defined_name(&self) -> Option<&'a ClarityName> {
+        use self::DefineFunctionsParsed::*;
+        match *self {
+            Constant { name, .. } | NonFungibleToken { name, .. } | BoundedFungibleToken { name, .. } |
+            UnboundedFungibleToken { name } | Map { name, .. } | PersistedVariable { name, .. } |
+            Trait { name, .. } => Some(name),
+            UseTrait { trait_identifier, .. } => Some(&trait_identifier.name),
+            PrivateFunction { signature, .. } | ReadOnlyFunction { signature, .. } |
+            PublicFunction { signature, .. } => signature.get(0).and_then(|name| name.match_atom()),
+            ImplTrait { .. } => None,
+        }
+    }
+
     /// Try to parse a Top-Level Expression (e.g., (define-private (foo) 1)) as
     /// a define-statement, returns None if the supplied expression is not a define.
     pub fn try_parse(expression: &'a SymbolicExpression) -> std::result::Result<Option<DefineFunctionsParsed<'a>>, CheckErrors> {
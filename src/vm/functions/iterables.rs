@@ -1,8 +1,9 @@
 use vm::costs::{cost_functions, CostOverflowingMath};
 use vm::errors::{CheckErrors, RuntimeErrorType, InterpreterResult as Result, check_argument_count};
-use vm::types::{Value, ListData, signatures::ListTypeData, TypeSignature::BoolType, TypeSignature};
-use vm::representations::{SymbolicExpression, SymbolicExpressionType};
-use vm::{LocalContext, Environment, eval, apply, lookup_function};
+use vm::types::{Value, ListData, OptionalData, signatures::ListTypeData, TypeSignature::BoolType, TypeSignature};
+use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName};
+use vm::callables::CallableType;
+use vm::{LocalContext, Environment, eval, apply, lookup_function, is_reserved};
 use std::convert::TryInto;
 use std::cmp;
 
@@ -21,30 +22,119 @@ pub fn list_cons(args: &[SymbolicExpression], env: &mut Environment, context: &L
     Value::list_from(args)
 }
 
+/// An inline function passed to `map`, `filter` or `fold` in place of a function name:
+///   `(lambda (x) (+ x 1))`. Its parameters are untyped: they take the types of the
+///   sequence items (and, for `fold`, the accumulator) the lambda is applied to.
+pub struct Lambda <'a> {
+    pub parameters: Vec<&'a ClarityName>,
+    pub body: &'a SymbolicExpression
+}
+
+impl <'a> Lambda <'a> {
+    /// Returns `None` if the expression is not a lambda, e.g., if it is a function name.
+    pub fn parse(expr: &'a SymbolicExpression) -> std::result::Result<Option<Lambda<'a>>, CheckErrors> {
+        let (keyword, rest) = match expr.match_list().and_then(|list| list.split_first()) {
+            Some(split) => split,
+            None => return Ok(None)
+        };
+        if keyword.match_atom().map(|name| name.as_str()) != Some("lambda") {
+            return Ok(None)
+        }
+
+        if rest.len() != 2 {
+            return Err(CheckErrors::BadLambdaSyntax)
+        }
+        let parameters = rest[0].match_list()
+            .ok_or(CheckErrors::BadLambdaSyntax)?
+            .iter()
+            .map(|parameter| parameter.match_atom().ok_or(CheckErrors::BadLambdaSyntax))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(Some(Lambda { parameters, body: &rest[1] }))
+    }
+
+    fn apply(&self, args: Vec<Value>, env: &mut Environment, context: &LocalContext) -> Result<Value> {
+        if args.len() != self.parameters.len() {
+            return Err(CheckErrors::IncorrectArgumentCount(self.parameters.len(), args.len()).into())
+        }
+
+        runtime_cost!(cost_functions::LET, env, args.len())?;
+
+        let mut inner_context = context.extend()?;
+        for (name, value) in self.parameters.iter().zip(args) {
+            if is_reserved(name) ||
+                env.contract_context.lookup_function(name).is_some() ||
+                inner_context.lookup_variable(name).is_some() {
+                    return Err(CheckErrors::NameAlreadyUsed(name.to_string()).into())
+                }
+            inner_context.variables.insert((*name).clone(), value);
+        }
+
+        eval(self.body, env, &inner_context)
+    }
+}
+
+/// The function applied by `map`, `filter` and `fold`: a named function, or an inline lambda.
+enum IterableFunction <'a> {
+    Named(CallableType),
+    Lambda(Lambda<'a>)
+}
+
+impl <'a> IterableFunction <'a> {
+    fn resolve(expr: &'a SymbolicExpression, env: &mut Environment) -> Result<IterableFunction<'a>> {
+        if let Some(lambda) = Lambda::parse(expr)? {
+            return Ok(IterableFunction::Lambda(lambda))
+        }
+
+        let function_name = expr.match_atom()
+            .ok_or(CheckErrors::ExpectedName)?;
+        lookup_function(&function_name, env)
+            .map(IterableFunction::Named)
+    }
+
+    fn apply(&self, args: Vec<Value>, env: &mut Environment, context: &LocalContext) -> Result<Value> {
+        match self {
+            IterableFunction::Named(function) => {
+                let arguments: Vec<_> = args.into_iter()
+                    .map(SymbolicExpression::atom_value)
+                    .collect();
+                apply(function, &arguments, env, context)
+            },
+            IterableFunction::Lambda(lambda) => lambda.apply(args, env, context)
+        }
+    }
+}
+
+/// Interprets the result of a `filter` predicate, which is either a `bool`, or
+///   an `(optional bool)` where `none` excludes the item.
+fn filter_includes(filter_eval: Value) -> Result<bool> {
+    match filter_eval {
+        Value::Bool(include) => Ok(include),
+        Value::Optional(OptionalData { data: None }) => Ok(false),
+        Value::Optional(OptionalData { data: Some(inner) }) => match *inner {
+            Value::Bool(include) => Ok(include),
+            inner => Err(CheckErrors::TypeValueError(BoolType, inner).into())
+        },
+        _ => Err(CheckErrors::TypeValueError(BoolType, filter_eval).into())
+    }
+}
+
 pub fn special_filter(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
     check_argument_count(2, args)?;
 
     runtime_cost!(cost_functions::FILTER, env, 0)?;
 
-    let function_name = args[0].match_atom()
-        .ok_or(CheckErrors::ExpectedName)?;
-
-    let function = lookup_function(&function_name, env)?;
+    let function = IterableFunction::resolve(&args[0], env)?;
     let iterable = eval(&args[1], env, context)?;
 
     match iterable {
         Value::List(mut list) => {
             let mut filtered_vec = Vec::new();
             for x in list.data.drain(..) {
-                let argument = [ SymbolicExpression::atom_value(x.clone()) ];
-                let filter_eval = apply(&function, &argument, env, context)?;
-                if let Value::Bool(include) = filter_eval {
-                    if include {
-                        filtered_vec.push(x);
-                    } // else, filter out.
-                } else {
-                    return Err(CheckErrors::TypeValueError(BoolType, filter_eval).into())
-                }
+                let filter_eval = function.apply(vec![x.clone()], env, context)?;
+                if filter_includes(filter_eval)? {
+                    filtered_vec.push(x);
+                } // else, filter out.
             }
             Value::list_with_type(filtered_vec, list.type_signature)
         },
@@ -52,15 +142,10 @@ pub fn special_filter(args: &[SymbolicExpression], env: &mut Environment, contex
             let mut filtered_vec = Vec::new();
             for x in buff.data.drain(..) {
                 let v = Value::buff_from(vec![x.clone()])?;
-                let argument = [ SymbolicExpression::atom_value(v) ];
-                let filter_eval = apply(&function, &argument, env, context)?;
-                if let Value::Bool(include) = filter_eval {
-                    if include {
-                        filtered_vec.push(x);
-                    } // else, filter out.
-                } else {
-                    return Err(CheckErrors::TypeValueError(BoolType, filter_eval).into())
-                }
+                let filter_eval = function.apply(vec![v], env, context)?;
+                if filter_includes(filter_eval)? {
+                    filtered_vec.push(x);
+                } // else, filter out.
             }
             Value::buff_from(filtered_vec)
         },
@@ -73,28 +158,19 @@ pub fn special_fold(args: &[SymbolicExpression], env: &mut Environment, context:
 
     runtime_cost!(cost_functions::FILTER, env, 0)?;
 
-    let function_name = args[0].match_atom()
-        .ok_or(CheckErrors::ExpectedName)?;
-
-    let function = lookup_function(&function_name, env)?;
+    let function = IterableFunction::resolve(&args[0], env)?;
     let iterable = eval(&args[1], env, context)?;
     let initial = eval(&args[2], env, context)?;
 
     let mapped_args: Vec<_> = match iterable {
-        Value::List(mut list) => {
-            list.data.drain(..).map(|x| {
-                SymbolicExpression::atom_value(x)
-            }).collect()
-        },
+        Value::List(list) => list.data,
         Value::Buffer(mut buff) => {
-            buff.data.drain(..).map(|x| {
-                SymbolicExpression::atom_value(Value::buff_from_byte(x))
-            }).collect()
+            buff.data.drain(..).map(Value::buff_from_byte).collect()
         },
         _ => return Err(CheckErrors::ExpectedListOrBuffer(TypeSignature::type_of(&iterable)).into())
     };
-    mapped_args.iter().try_fold(initial, |acc, x| {
-        apply(&function, &[x.clone(), SymbolicExpression::atom_value(acc)], env, context)
+    mapped_args.into_iter().try_fold(initial, |acc, x| {
+        function.apply(vec![x, acc], env, context)
     })
 }
 
@@ -103,26 +179,18 @@ pub fn special_map(args: &[SymbolicExpression], env: &mut Environment, context:
 
     runtime_cost!(cost_functions::MAP, env, 0)?;
 
-    let function_name = args[0].match_atom()
-        .ok_or(CheckErrors::ExpectedName)?;
     let iterable = eval(&args[1], env, context)?;
-    let function = lookup_function(&function_name, env)?;
+    let function = IterableFunction::resolve(&args[0], env)?;
 
     let mapped_args: Vec<_> = match iterable {
-        Value::List(mut list) => {
-            list.data.drain(..).map(|x| {
-                vec![SymbolicExpression::atom_value(x)]
-            }).collect()
-        },
+        Value::List(list) => list.data,
         Value::Buffer(mut buff) => {
-            buff.data.drain(..).map(|x| {
-                vec![SymbolicExpression::atom_value(Value::buff_from_byte(x))]
-            }).collect()
+            buff.data.drain(..).map(Value::buff_from_byte).collect()
         },
         _ => return Err(CheckErrors::ExpectedListOrBuffer(TypeSignature::type_of(&iterable)).into())
     };
     let mapped_vec: Result<Vec<_>> =
-        mapped_args.iter().map(|argument| apply(&function, &argument, env, context)).collect();
+        mapped_args.into_iter().map(|argument| function.apply(vec![argument], env, context)).collect();
     Value::list_from(mapped_vec?)
 }
 
@@ -207,6 +275,9 @@ pub fn special_as_max_len(args: &[SymbolicExpression], env: &mut Environment, co
     }
 }
 
+/// `len` reads the stored length of the sequence, and never iterates over it:
+///  it is charged a constant cost (`cost_functions::LEN`) regardless of the
+///  sequence's size.
 pub fn native_len(iterable: Value) -> Result<Value> {
     match iterable {
         Value::List(list) => Ok(Value::UInt(list.data.len() as u128)),
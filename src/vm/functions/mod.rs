@@ -1,14 +1,17 @@
 pub mod define;
 pub mod tuples;
-mod iterables;
+pub mod iterables;
 mod arithmetic;
 mod boolean;
 mod database;
 mod options;
 mod assets;
+mod crypto;
+mod consensus_buff;
+mod principals;
 
 use vm::errors::{Error, CheckErrors, RuntimeErrorType, ShortReturnType, InterpreterResult as Result, check_argument_count, check_arguments_at_least};
-use vm::types::{Value, PrincipalData, ResponseData, TypeSignature};
+use vm::types::{Value, PrincipalData, ResponseData, OptionalData, TypeSignature, BUFF_32};
 use vm::callables::{CallableType, NativeHandle};
 use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName};
 use vm::representations::SymbolicExpressionType::{List, Atom};
@@ -20,6 +23,9 @@ define_named_enum!(NativeFunctions {
     Add("+"),
     Subtract("-"),
     Multiply("*"),
+    CheckedAdd("+!"),
+    CheckedSub("-!"),
+    CheckedMul("*!"),
     Divide("/"),
     CmpGeq(">="),
     CmpLeq("<="),
@@ -27,6 +33,8 @@ define_named_enum!(NativeFunctions {
     CmpGreater(">"),
     ToInt("to-int"),
     ToUInt("to-uint"),
+    BuffToUIntLe("buff-to-uint-le"),
+    BuffToUIntBe("buff-to-uint-be"),
     Modulo("mod"),
     Power("pow"),
     BitwiseXOR("xor"),
@@ -46,18 +54,35 @@ define_named_enum!(NativeFunctions {
     FetchVar("var-get"),
     SetVar("var-set"),
     FetchEntry("map-get?"),
+    FetchContractEntry("contract-map-get?"),
     SetEntry("map-set"),
     InsertEntry("map-insert"),
     DeleteEntry("map-delete"),
+    SetGetEntry("map-set-get"),
     TupleCons("tuple"),
     TupleGet("get"),
+    TupleGetIn("get-in"),
+    TupleGetAt("get-at"),
     Begin("begin"),
     Hash160("hash160"),
     Sha256("sha256"),
     Sha512("sha512"),
     Sha512Trunc256("sha512/256"),
     Keccak256("keccak256"),
+    Ripemd160("ripemd160"),
+    HashPair("hash-pair"),
+    Secp256k1Recover("secp256k1-recover?"),
+    Secp256k1Verify("secp256k1-verify"),
+    ToConsensusBuff("to-consensus-buff?"),
+    FromConsensusBuff("from-consensus-buff?"),
+    PrincipalDestruct("principal-destruct?"),
+    PrincipalConstruct("principal-construct?"),
+    GetOk("get-ok"),
+    GetErr("get-err"),
+    Flatten("flatten"),
     Print("print"),
+    PrintEvent("print-event"),
+    TestAssert("test-assert"),
     ContractCall("contract-call?"),
     AsContract("as-contract"),
     AtBlock("at-block"),
@@ -79,6 +104,7 @@ define_named_enum!(NativeFunctions {
     IsSome("is-some"),
     Filter("filter"),
     GetTokenBalance("ft-get-balance"),
+    GetTokenSupply("ft-get-supply"),
     GetAssetOwner("nft-get-owner?"),
     TransferToken("ft-transfer?"),
     TransferAsset("nft-transfer?"),
@@ -86,6 +112,7 @@ define_named_enum!(NativeFunctions {
     MintToken("ft-mint?"),
     StxTransfer("stx-transfer?"),
     StxBurn("stx-burn?"),
+    StxGetAccount("stx-account"),
 });
 
 pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
@@ -96,6 +123,9 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             Add => NativeFunction("native_add", NativeHandle::MoreArg(&arithmetic::native_add), cost_functions::ADD),
             Subtract => NativeFunction("native_sub", NativeHandle::MoreArg(&arithmetic::native_sub), cost_functions::SUB),
             Multiply => NativeFunction("native_mul", NativeHandle::MoreArg(&arithmetic::native_mul), cost_functions::MUL),
+            CheckedAdd => NativeFunction("native_checked_add", NativeHandle::DoubleArg(&arithmetic::native_checked_add), cost_functions::ADD),
+            CheckedSub => NativeFunction("native_checked_sub", NativeHandle::DoubleArg(&arithmetic::native_checked_sub), cost_functions::SUB),
+            CheckedMul => NativeFunction("native_checked_mul", NativeHandle::DoubleArg(&arithmetic::native_checked_mul), cost_functions::MUL),
             Divide => NativeFunction("native_div", NativeHandle::MoreArg(&arithmetic::native_div), cost_functions::DIV),
             CmpGeq => NativeFunction("native_geq", NativeHandle::DoubleArg(&arithmetic::native_geq), cost_functions::GEQ),
             CmpLeq => NativeFunction("native_leq", NativeHandle::DoubleArg(&arithmetic::native_leq), cost_functions::LEQ),
@@ -103,6 +133,8 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             CmpGreater => NativeFunction("native_ge", NativeHandle::DoubleArg(&arithmetic::native_ge), cost_functions::GE),
             ToUInt => NativeFunction("native_to_uint", NativeHandle::SingleArg(&arithmetic::native_to_uint), cost_functions::INT_CAST),
             ToInt => NativeFunction("native_to_int", NativeHandle::SingleArg(&arithmetic::native_to_int), cost_functions::INT_CAST),
+            BuffToUIntLe => NativeFunction("native_buff_to_uint_le", NativeHandle::SingleArg(&arithmetic::native_buff_to_uint_le), cost_functions::BUFF_TO_UINT_LE),
+            BuffToUIntBe => NativeFunction("native_buff_to_uint_be", NativeHandle::SingleArg(&arithmetic::native_buff_to_uint_be), cost_functions::BUFF_TO_UINT_BE),
             Modulo => NativeFunction("native_mod", NativeHandle::DoubleArg(&arithmetic::native_mod), cost_functions::MOD),
             Power => NativeFunction("native_pow", NativeHandle::DoubleArg(&arithmetic::native_pow), cost_functions::POW),
             BitwiseXOR => NativeFunction("native_xor", NativeHandle::DoubleArg(&arithmetic::native_xor), cost_functions::XOR),
@@ -123,18 +155,32 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             Len => NativeFunction("native_len", NativeHandle::SingleArg(&iterables::native_len), cost_functions::LEN),
             ListCons => SpecialFunction("special_list_cons", &iterables::list_cons),
             FetchEntry => SpecialFunction("special_map-get?", &database::special_fetch_entry),
+            FetchContractEntry => SpecialFunction("special_contract-map-get?", &database::special_fetch_contract_entry),
             SetEntry => SpecialFunction("special_set-entry", &database::special_set_entry),
             InsertEntry => SpecialFunction("special_insert-entry", &database::special_insert_entry),
             DeleteEntry => SpecialFunction("special_delete-entry", &database::special_delete_entry),
+            SetGetEntry => SpecialFunction("special_set-get-entry", &database::special_set_get_entry),
             TupleCons => SpecialFunction("special_tuple", &tuples::tuple_cons),
             TupleGet => SpecialFunction("special_get-tuple", &tuples::tuple_get),
+            TupleGetIn => SpecialFunction("special_get-in-tuple", &tuples::tuple_get_in),
+            TupleGetAt => SpecialFunction("special_get-at-tuple", &tuples::tuple_get_at),
             Begin => NativeFunction("native_begin", NativeHandle::MoreArg(&native_begin), cost_functions::BEGIN),
             Hash160 => NativeFunction("native_hash160", NativeHandle::SingleArg(&native_hash160), cost_functions::HASH160),
             Sha256 => NativeFunction("native_sha256", NativeHandle::SingleArg(&native_sha256), cost_functions::SHA256),
             Sha512 => NativeFunction("native_sha512", NativeHandle::SingleArg(&native_sha512), cost_functions::SHA512),
             Sha512Trunc256 => NativeFunction("native_sha512trunc256", NativeHandle::SingleArg(&native_sha512trunc256), cost_functions::SHA512T256),
             Keccak256 => NativeFunction("native_keccak256", NativeHandle::SingleArg(&native_keccak256), cost_functions::KECCAK256),
+            Ripemd160 => NativeFunction("native_ripemd160", NativeHandle::SingleArg(&native_ripemd160), cost_functions::RIPEMD160),
+            HashPair => NativeFunction("native_hash_pair", NativeHandle::DoubleArg(&native_hash_pair), cost_functions::HASH_PAIR),
+            Secp256k1Recover => NativeFunction("native_secp256k1_recover", NativeHandle::DoubleArg(&crypto::native_secp256k1_recover), cost_functions::SECP256K1RECOVER),
+            Secp256k1Verify => NativeFunction("native_secp256k1_verify", NativeHandle::MoreArg(&crypto::native_secp256k1_verify), cost_functions::SECP256K1VERIFY),
+            ToConsensusBuff => SpecialFunction("special_to_consensus_buff", &consensus_buff::special_to_consensus_buff),
+            FromConsensusBuff => SpecialFunction("special_from_consensus_buff", &consensus_buff::special_from_consensus_buff),
+            PrincipalDestruct => NativeFunction("native_principal_destruct", NativeHandle::SingleArg(&principals::native_principal_destruct), cost_functions::PRINCIPAL_DESTRUCT),
+            PrincipalConstruct => NativeFunction("native_principal_construct", NativeHandle::MoreArg(&principals::native_principal_construct), cost_functions::PRINCIPAL_CONSTRUCT),
             Print => SpecialFunction("special_print", &special_print),
+            PrintEvent => SpecialFunction("special_print_event", &special_print_event),
+            TestAssert => SpecialFunction("special_test_assert", &special_test_assert),
             ContractCall => SpecialFunction("special_contract-call", &database::special_contract_call),
             AsContract => SpecialFunction("special_as-contract", &special_as_contract),
             GetBlockInfo => SpecialFunction("special_get_block_info", &database::special_get_block_info),
@@ -149,6 +195,9 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             IsNone => NativeFunction("native_is_none", NativeHandle::SingleArg(&options::native_is_none), cost_functions::IS_NONE),
             IsErr => NativeFunction("native_is_err", NativeHandle::SingleArg(&options::native_is_err), cost_functions::IS_ERR),
             IsSome => NativeFunction("native_is_some", NativeHandle::SingleArg(&options::native_is_some), cost_functions::IS_SOME),
+            GetOk => NativeFunction("native_get_ok", NativeHandle::SingleArg(&options::native_get_ok), cost_functions::GET_OK),
+            GetErr => NativeFunction("native_get_err", NativeHandle::SingleArg(&options::native_get_err), cost_functions::GET_ERR),
+            Flatten => NativeFunction("native_flatten", NativeHandle::SingleArg(&options::native_flatten), cost_functions::FLATTEN),
             Unwrap => NativeFunction("native_unwrap", NativeHandle::SingleArg(&options::native_unwrap), cost_functions::UNWRAP),
             UnwrapErr => NativeFunction("native_unwrap_err", NativeHandle::SingleArg(&options::native_unwrap_err), cost_functions::UNWRAP_ERR),
             Match => SpecialFunction("special_match", &options::special_match),
@@ -158,10 +207,12 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             TransferAsset => SpecialFunction("special_transfer_asset", &assets::special_transfer_asset),
             TransferToken => SpecialFunction("special_transfer_token", &assets::special_transfer_token),
             GetTokenBalance => SpecialFunction("special_get_balance", &assets::special_get_balance),
+            GetTokenSupply => SpecialFunction("special_get_supply", &assets::special_get_supply),
             GetAssetOwner => SpecialFunction("special_get_owner", &assets::special_get_owner),
             AtBlock => SpecialFunction("special_at_block", &database::special_at_block),
             StxTransfer => SpecialFunction("special_stx_transfer", &assets::special_stx_transfer),
             StxBurn => SpecialFunction("special_stx_burn", &assets::special_stx_burn),
+            StxGetAccount => SpecialFunction("special_stx_account", &assets::special_stx_account),
         };
         Some(callable)
     } else {
@@ -170,9 +221,8 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
 }
 
 fn native_eq(args: Vec<Value>) -> Result<Value> {
-    // TODO: this currently uses the derived equality checks of Value,
-    //   however, that's probably not how we want to implement equality
-    //   checks on the ::ListTypes
+    // lists compare element-wise (see `PartialEq for ListData`), so two lists
+    //   of differing max lengths are equal if their contents are.
 
     if args.len() < 2 {
         Ok(Value::Bool(true))
@@ -210,6 +260,21 @@ native_hash_func!(native_sha256, hash::Sha256Sum);
 native_hash_func!(native_sha512, hash::Sha512Sum);
 native_hash_func!(native_sha512trunc256, hash::Sha512Trunc256Sum);
 native_hash_func!(native_keccak256, hash::Keccak256Hash);
+native_hash_func!(native_ripemd160, hash::Ripemd160Sum);
+
+/// Hashes two 32-byte nodes of a merkle tree into their parent, as `SHA256(SHA256(left || right))`.
+///   This matches the interior nodes of Bitcoin's transaction merkle trees.
+fn native_hash_pair(left: Value, right: Value) -> Result<Value> {
+    let mut bytes = Vec::with_capacity(64);
+    for node in [left, right].iter() {
+        match node {
+            Value::Buffer(ref buff_data) if buff_data.data.len() == 32 => bytes.extend_from_slice(&buff_data.data),
+            _ => return Err(CheckErrors::TypeValueError(BUFF_32.clone(), node.clone()).into())
+        }
+    }
+    let hash = hash::DoubleSha256::from_data(&bytes);
+    Value::buff_from(hash.as_bytes().to_vec())
+}
 
 fn native_begin(mut args: Vec<Value>) -> Result<Value> {
     match args.pop() {
@@ -231,21 +296,99 @@ fn special_print(args: &[SymbolicExpression], env: &mut Environment, context: &L
     Ok(input)
 }
 
+/// Reads the topic of a `print-event`, which must be given as a string literal
+///   of printable ascii characters.
+pub fn parse_event_topic(topic: &SymbolicExpression) -> std::result::Result<String, CheckErrors> {
+    match topic.match_literal_value() {
+        Some(Value::Buffer(buff_data)) => {
+            if buff_data.data.iter().all(|c| *c == b' ' || c.is_ascii_graphic()) {
+                String::from_utf8(buff_data.data.clone())
+                    .map_err(|_| CheckErrors::ExpectedLiteral)
+            } else {
+                Err(CheckErrors::ExpectedLiteral)
+            }
+        },
+        _ => Err(CheckErrors::ExpectedLiteral)
+    }
+}
+
+fn special_print_event(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(2, args)?;
+
+    let topic = parse_event_topic(&args[0])?;
+    let input = eval(&args[1], env, context)?;
+
+    runtime_cost!(cost_functions::PRINT, env, input.size())?;
+
+    if cfg!(feature = "developer-mode") {
+        eprintln!("{}: {}", &topic, &input);
+    }
+
+    env.register_topic_print_event(topic, input.clone())?;
+    Ok(input)
+}
+
+/// The topic of the event emitted by a failed `test-assert`.
+pub const TEST_ASSERT_EVENT_TOPIC: &str = "test-assert";
+
+fn special_test_assert(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(2, args)?;
+
+    let condition = eval(&args[0], env, context)?;
+    let message = eval(&args[1], env, context)?;
+
+    runtime_cost!(cost_functions::TEST_ASSERT, env, message.size())?;
+
+    match condition {
+        Value::Bool(true) => {},
+        Value::Bool(false) => {
+            if cfg!(feature = "developer-mode") {
+                eprintln!("{}: {}", TEST_ASSERT_EVENT_TOPIC, &message);
+            }
+            env.register_topic_print_event(TEST_ASSERT_EVENT_TOPIC.to_string(), message)?;
+        },
+        _ => return Err(CheckErrors::TypeValueError(TypeSignature::BoolType, condition).into())
+    }
+
+    Ok(condition)
+}
+
 fn special_if(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
     check_argument_count(3, args)?;
 
     runtime_cost!(cost_functions::IF, env, 0)?;
     // handle the conditional clause.
     let conditional = eval(&args[0], env, context)?;
-    match conditional {
-        Value::Bool(result) => {
+    let condition = match conditional {
+        Value::Bool(result) => Some(result),
+        // an `(optional bool)` condition counts as `false` when it is `none`.
+        Value::Optional(OptionalData { data: Some(ref inner) }) => match **inner {
+            Value::Bool(result) => Some(result),
+            _ => None
+        },
+        Value::Optional(OptionalData { data: None }) => Some(false),
+        _ => None
+    };
+    match condition {
+        Some(result) => {
             if result {
                 eval(&args[1], env, context)
             } else {
                 eval(&args[2], env, context)
             }
         },
-        _ => Err(CheckErrors::TypeValueError(TypeSignature::BoolType, conditional).into())
+        None => Err(CheckErrors::TypeValueError(TypeSignature::BoolType, conditional).into())
+    }
+}
+
+/// Returns the name and type of the only field of a single-field map key tuple. `map-get?`
+///   accepts the value of that field in place of the key tuple, e.g., `(map-get? balances tx-sender)`
+///   for a map keyed by `(tuple (owner principal))`.
+pub fn single_field_key_type(key_type: &TypeSignature) -> Option<(&ClarityName, &TypeSignature)> {
+    match key_type {
+        TypeSignature::TupleType(tuple_type) if tuple_type.get_type_map().len() == 1 =>
+            tuple_type.get_type_map().iter().next(),
+        _ => None
     }
 }
 
@@ -288,10 +431,86 @@ where F: FnMut(&ClarityName, &SymbolicExpression) -> std::result::Result<(), E>,
     Ok(())
 }
 
+/// Handles the fields of a tuple construction, `(tuple (a 1) (b 2))`. A field may be
+///   punned as `(a)`, short for `(a a)`: its value is the variable of the same name.
+pub fn handle_tuple_field_list <F, E> (fields: &[SymbolicExpression], mut handler: F) -> std::result::Result<(), E>
+where F: FnMut(&ClarityName, &SymbolicExpression) -> std::result::Result<(), E>,
+      E: From<CheckErrors>
+{
+    for field in fields.iter() {
+        let field_expression = field.match_list()
+            .ok_or(CheckErrors::BadSyntaxBinding)?;
+        let (field_name, value_sexp) = match field_expression {
+            [name] => (name, name),
+            [name, value] => (name, value),
+            _ => return Err(CheckErrors::BadSyntaxBinding.into())
+        };
+        let field_name = field_name.match_atom()
+            .ok_or(CheckErrors::BadSyntaxBinding)?;
+
+        handler(field_name, value_sexp)?;
+    }
+    Ok(())
+}
+
+/// The target of a single `let` binding.
+pub enum LetBindingTarget<'a> {
+    Name(&'a ClarityName),
+    /// a tuple pattern, `{ field-1: name-1, field-2: name-2 }`, which binds each
+    ///   listed field of the bound tuple to a name.
+    Tuple(Vec<(&'a ClarityName, &'a ClarityName)>),
+}
+
+fn parse_let_binding_target(target: &SymbolicExpression) -> std::result::Result<LetBindingTarget, CheckErrors> {
+    if let Some(name) = target.match_atom() {
+        return Ok(LetBindingTarget::Name(name))
+    }
+
+    // the parser expands `{ a: x }` to `(tuple (a x))`
+    let (constructor, fields) = target.match_list()
+        .and_then(|pattern| pattern.split_first())
+        .ok_or(CheckErrors::BadSyntaxBinding)?;
+    match constructor.match_atom().and_then(|name| NativeFunctions::lookup_by_name(name)) {
+        Some(NativeFunctions::TupleCons) => {},
+        _ => return Err(CheckErrors::BadSyntaxBinding)
+    }
+
+    let mut bound_fields = Vec::with_capacity(fields.len());
+    for field in fields.iter() {
+        match field.match_list() {
+            Some(ref pair) if pair.len() == 2 => {
+                let field_name = pair[0].match_atom().ok_or(CheckErrors::BadSyntaxBinding)?;
+                let var_name = pair[1].match_atom().ok_or(CheckErrors::BadSyntaxBinding)?;
+                bound_fields.push((field_name, var_name));
+            },
+            _ => return Err(CheckErrors::BadSyntaxBinding)
+        }
+    }
+    Ok(LetBindingTarget::Tuple(bound_fields))
+}
+
+/// Like `handle_binding_list`, but each binding of a `let` may bind a tuple pattern
+///   rather than a single name.
+pub fn handle_let_binding_list <F, E> (bindings: &[SymbolicExpression], mut handler: F) -> std::result::Result<(), E>
+where F: FnMut(LetBindingTarget, &SymbolicExpression) -> std::result::Result<(), E>,
+      E: From<CheckErrors>
+{
+    for binding in bindings.iter() {
+        let binding_expression = binding.match_list()
+            .ok_or(CheckErrors::BadSyntaxBinding)?;
+        if binding_expression.len() != 2 {
+            return Err(CheckErrors::BadSyntaxBinding.into());
+        }
+        let target = parse_let_binding_target(&binding_expression[0])?;
+        handler(target, &binding_expression[1])?;
+    }
+    Ok(())
+}
+
 pub fn parse_eval_bindings(bindings: &[SymbolicExpression],
                        env: &mut Environment, context: &LocalContext)-> Result<Vec<(ClarityName, Value)>> {
     let mut result = Vec::new();
-    handle_binding_list(bindings, |var_name, var_sexp| {
+    handle_tuple_field_list(bindings, |var_name, var_sexp| {
         eval(var_sexp, env, context)
             .and_then(|value| {
                 result.push((var_name.clone(), value));
@@ -321,19 +540,43 @@ fn special_let(args: &[SymbolicExpression], env: &mut Environment, context: &Loc
     let mut memory_use = 0;
 
     finally_drop_memory!( env, memory_use; {
-        handle_binding_list::<_, Error>(bindings, |binding_name, var_sexp| {
-            if is_reserved(binding_name) ||
-                env.contract_context.lookup_function(binding_name).is_some() ||
-                inner_context.lookup_variable(binding_name).is_some() {
-                    return Err(CheckErrors::NameAlreadyUsed(binding_name.clone().into()).into())
-                }
+        handle_let_binding_list::<_, Error>(bindings, |target, var_sexp| {
+            let binding_names: Vec<&ClarityName> = match &target {
+                LetBindingTarget::Name(binding_name) => vec![*binding_name],
+                LetBindingTarget::Tuple(fields) => fields.iter().map(|(_, binding_name)| *binding_name).collect()
+            };
+            for (index, binding_name) in binding_names.iter().enumerate() {
+                if is_reserved(binding_name) ||
+                    env.contract_context.lookup_function(binding_name).is_some() ||
+                    inner_context.lookup_variable(binding_name).is_some() ||
+                    binding_names[..index].contains(binding_name) {
+                        return Err(CheckErrors::NameAlreadyUsed(binding_name.to_string()).into())
+                    }
+            }
 
             let binding_value = eval(var_sexp, env, context)?;
 
-            let bind_mem_use = binding_value.get_memory_use();
-            env.add_memory(bind_mem_use)?;
-            memory_use += bind_mem_use; // no check needed, b/c it's done in add_memory.
-            inner_context.variables.insert(binding_name.clone(), binding_value);
+            let named_values = match target {
+                LetBindingTarget::Name(binding_name) => vec![(binding_name, binding_value)],
+                LetBindingTarget::Tuple(fields) => {
+                    let tuple_data = match binding_value {
+                        Value::Tuple(tuple_data) => tuple_data,
+                        _ => return Err(CheckErrors::ExpectedTuple(TypeSignature::type_of(&binding_value)).into())
+                    };
+                    let mut named_values = Vec::with_capacity(fields.len());
+                    for (field_name, binding_name) in fields.into_iter() {
+                        named_values.push((binding_name, tuple_data.get(field_name)?.clone()));
+                    }
+                    named_values
+                }
+            };
+
+            for (binding_name, binding_value) in named_values.into_iter() {
+                let bind_mem_use = binding_value.get_memory_use();
+                env.add_memory(bind_mem_use)?;
+                memory_use += bind_mem_use; // no check needed, b/c it's done in add_memory.
+                inner_context.variables.insert(binding_name.clone(), binding_value);
+            }
             Ok(())
         })?;
 
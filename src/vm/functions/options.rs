@@ -221,6 +221,45 @@ pub fn native_is_err(input: Value) -> Result<Value> {
         .map(|is_ok| { Value::Bool(!is_ok) })
 }
 
+pub fn native_get_ok(input: Value) -> Result<Value> {
+    match input {
+        Value::Response(data) => {
+            if data.committed {
+                Ok(Value::some(*data.data)?)
+            } else {
+                Ok(Value::none())
+            }
+        },
+        _ => Err(CheckErrors::ExpectedResponseValue(input).into())
+    }
+}
+
+pub fn native_get_err(input: Value) -> Result<Value> {
+    match input {
+        Value::Response(data) => {
+            if data.committed {
+                Ok(Value::none())
+            } else {
+                Ok(Value::some(*data.data)?)
+            }
+        },
+        _ => Err(CheckErrors::ExpectedResponseValue(input).into())
+    }
+}
+
+pub fn native_flatten(input: Value) -> Result<Value> {
+    match input {
+        Value::Optional(OptionalData { data: Some(inner) }) => {
+            match *inner {
+                Value::Optional(inner_data) => Ok(Value::Optional(inner_data)),
+                other => Err(CheckErrors::ExpectedOptionalValue(other).into())
+            }
+        },
+        Value::Optional(OptionalData { data: None }) => Ok(Value::none()),
+        _ => Err(CheckErrors::ExpectedOptionalValue(input).into())
+    }
+}
+
 pub fn native_okay(input: Value) -> Result<Value> {
     Ok(Value::okay(input)?)
 }
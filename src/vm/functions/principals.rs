@@ -0,0 +1,103 @@
+use std::convert::TryFrom;
+use vm::errors::{CheckErrors, InterpreterResult as Result};
+use vm::representations::ContractName;
+use vm::types::{Value, TypeSignature, TupleData, PrincipalData, StandardPrincipalData,
+                QualifiedContractIdentifier, BuffData, BUFF_1, BUFF_20};
+
+/// c32 addresses encode their version as a single character, so only versions below 32
+///   can be the version of a valid address.
+pub const MAX_ADDRESS_VERSION: u8 = 31;
+
+/// Decomposes a principal into its version byte, hash bytes and (for a contract principal)
+///   contract name. The parts are returned as `(ok ...)` if the version is that of a valid
+///   address, and as `(err ...)` otherwise.
+pub fn native_principal_destruct(principal: Value) -> Result<Value> {
+    let (issuer, name) = match principal {
+        Value::Principal(PrincipalData::Standard(issuer)) => (issuer, None),
+        Value::Principal(PrincipalData::Contract(QualifiedContractIdentifier { issuer, name })) => (issuer, Some(name)),
+        _ => return Err(CheckErrors::TypeValueError(TypeSignature::PrincipalType, principal).into())
+    };
+    let StandardPrincipalData(version, hash_bytes) = issuer;
+
+    let name = match name {
+        Some(name) => Value::some(Value::buff_from(name.as_str().as_bytes().to_vec())?)?,
+        None => Value::none()
+    };
+    let parts = Value::from(TupleData::from_data(vec![
+        ("version".into(), Value::buff_from_byte(version)),
+        ("hash-bytes".into(), Value::buff_from(hash_bytes.to_vec())?),
+        ("name".into(), name)])?);
+
+    if version <= MAX_ADDRESS_VERSION {
+        Value::okay(parts)
+    } else {
+        Value::error(parts)
+    }
+}
+
+/// Error code returned by `principal-construct?` when the principal was built, but its version
+///   is not that of a valid address.
+pub const PRINCIPAL_CONSTRUCT_INVALID_VERSION: u128 = 0;
+/// Error code returned by `principal-construct?` when the parts cannot form a principal.
+pub const PRINCIPAL_CONSTRUCT_INVALID_PARTS: u128 = 1;
+
+fn principal_construct_error(error_code: u128, value: Option<PrincipalData>) -> Result<Value> {
+    let value = match value {
+        Some(principal) => Value::some(Value::from(principal))?,
+        None => Value::none()
+    };
+    Value::error(Value::from(TupleData::from_data(vec![
+        ("error_code".into(), Value::UInt(error_code)),
+        ("value".into(), value)])?))
+}
+
+/// Builds a principal from a version byte, hash bytes and an optional contract name. This is
+///   the inverse of `principal-destruct?`.
+pub fn native_principal_construct(args: Vec<Value>) -> Result<Value> {
+    if args.len() < 2 {
+        return Err(CheckErrors::RequiresAtLeastArguments(2, args.len()).into())
+    } else if args.len() > 3 {
+        return Err(CheckErrors::IncorrectArgumentCount(3, args.len()).into())
+    }
+    let mut args = args.into_iter();
+    let version = match args.next() {
+        Some(Value::Buffer(BuffData { data })) => data,
+        Some(x) => return Err(CheckErrors::TypeValueError(BUFF_1, x).into()),
+        None => unreachable!()
+    };
+    let hash_bytes = match args.next() {
+        Some(Value::Buffer(BuffData { data })) => data,
+        Some(x) => return Err(CheckErrors::TypeValueError(BUFF_20, x).into()),
+        None => unreachable!()
+    };
+    let name = match args.next() {
+        Some(Value::Buffer(BuffData { data })) => Some(data),
+        Some(x) => return Err(CheckErrors::TypeValueError(TypeSignature::max_buffer(), x).into()),
+        None => None
+    };
+
+    // buffers typed as (buff 1) and (buff 20) may still hold fewer bytes
+    if version.len() != 1 || hash_bytes.len() != 20 {
+        return principal_construct_error(PRINCIPAL_CONSTRUCT_INVALID_PARTS, None)
+    }
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&hash_bytes);
+    let issuer = StandardPrincipalData(version[0], hash);
+
+    let principal = match name {
+        Some(name) => {
+            let name = match String::from_utf8(name).ok().and_then(|name| ContractName::try_from(name).ok()) {
+                Some(name) => name,
+                None => return principal_construct_error(PRINCIPAL_CONSTRUCT_INVALID_PARTS, None)
+            };
+            PrincipalData::Contract(QualifiedContractIdentifier::new(issuer, name))
+        },
+        None => PrincipalData::Standard(issuer)
+    };
+
+    if version[0] <= MAX_ADDRESS_VERSION {
+        Value::okay(Value::from(principal))
+    } else {
+        principal_construct_error(PRINCIPAL_CONSTRUCT_INVALID_VERSION, Some(principal))
+    }
+}
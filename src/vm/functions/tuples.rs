@@ -4,6 +4,7 @@ use vm::representations::{SymbolicExpression,SymbolicExpressionType};
 use vm::representations::SymbolicExpressionType::{List};
 use vm::{LocalContext, Environment, eval};
 use vm::costs::cost_functions;
+use std::convert::TryFrom;
 
 pub fn tuple_cons(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
     //    (tuple (arg-name value)
@@ -51,6 +52,85 @@ pub fn tuple_get(args: &[SymbolicExpression], env: &mut Environment, context: &L
     }
 }
 
+pub fn tuple_get_in(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    // (get-in (tuple ...) (arg-name-0 arg-name-1 ...))
+    //    walks the path of field names through nested tuples. if the tuple at any level
+    //    is an option type, then return option(final-field), or none if that option is none.
+    check_argument_count(2, args)?;
+
+    let path = args[1].match_list()
+        .ok_or(CheckErrors::ExpectedName)?;
+    check_arguments_at_least(1, path)?;
+
+    let mut value = eval(&args[0], env, context)?;
+    let mut is_optional = false;
+
+    for segment in path.iter() {
+        let arg_name = segment.match_atom()
+            .ok_or(CheckErrors::ExpectedName)?;
+
+        let tuple_data = match value {
+            Value::Optional(opt_data) => {
+                is_optional = true;
+                match opt_data.data {
+                    Some(data) => {
+                        if let Value::Tuple(tuple_data) = *data {
+                            tuple_data
+                        } else {
+                            return Err(CheckErrors::ExpectedTuple(TypeSignature::type_of(&data)).into())
+                        }
+                    },
+                    None => return Ok(Value::none()) // just pass through none-types.
+                }
+            },
+            Value::Tuple(tuple_data) => tuple_data,
+            _ => return Err(CheckErrors::ExpectedTuple(TypeSignature::type_of(&value)).into())
+        };
+
+        runtime_cost!(cost_functions::TUPLE_GET, env, tuple_data.len())?;
+        value = tuple_data.get_owned(arg_name)?;
+    }
+
+    if is_optional {
+        Value::some(value)
+    } else {
+        Ok(value)
+    }
+}
+
+pub fn tuple_get_at(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    // (get-at (list (tuple ...) ...) index arg-name)
+    //    reads a field of the tuple at the given index of the list, returning option(field-name),
+    //    or none if the index is out of bounds.
+    check_argument_count(3, args)?;
+
+    let list = eval(&args[0], env, context)?;
+    let index = eval(&args[1], env, context)?;
+    let arg_name = args[2].match_atom()
+        .ok_or(CheckErrors::ExpectedName)?;
+
+    let list_data = match list {
+        Value::List(list_data) => list_data,
+        _ => return Err(CheckErrors::ExpectedListApplication.into())
+    };
+    let index = match index {
+        Value::Int(index) => index,
+        _ => return Err(CheckErrors::TypeValueError(TypeSignature::IntType, index).into())
+    };
+
+    let item = match usize::try_from(index).ok().and_then(|index| list_data.data.into_iter().nth(index)) {
+        Some(item) => item,
+        None => return Ok(Value::none())
+    };
+
+    if let Value::Tuple(tuple_data) = item {
+        runtime_cost!(cost_functions::TUPLE_GET, env, tuple_data.len())?;
+        Value::some(tuple_data.get_owned(arg_name)?)
+    } else {
+        Err(CheckErrors::ExpectedTuple(TypeSignature::type_of(&item)).into())
+    }
+}
+
 pub enum TupleDefinitionType {
     Implicit(Box<[SymbolicExpression]>),
     Explicit,
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::borrow::Borrow;
 use std::ops::Deref;
 use std::convert::TryFrom;
@@ -66,7 +67,7 @@ macro_rules! guarded_string {
     }
 }
 
-guarded_string!(ClarityName, "ClarityName", Regex::new("^[a-zA-Z]([a-zA-Z0-9]|[-_!?+<>=/*])*$|^[-+=/*]$|^[<>]=?$"));
+guarded_string!(ClarityName, "ClarityName", Regex::new("^[a-zA-Z]([a-zA-Z0-9]|[-_!?+<>=/*])*$|^[-+=/*]$|^[-+*]!$|^[<>]=?$"));
 guarded_string!(ContractName, "ContractName", Regex::new("^[a-zA-Z]([a-zA-Z0-9]|[-_])*$|^__transient$"));
 guarded_string!(UrlString, "UrlString", Regex::new(r#"^[a-zA-Z0-9._~:/?#\[\]@!$&'()*+,;%=-]*$"#));
 
@@ -253,7 +254,7 @@ impl PreSymbolicExpression {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum SymbolicExpressionType {
     AtomValue(Value),
     Atom(ClarityName),
@@ -263,7 +264,7 @@ pub enum SymbolicExpressionType {
     TraitReference(ClarityName, TraitDefinition),
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum TraitDefinition {
     Defined(TraitIdentifier),
     Imported(TraitIdentifier)
@@ -286,7 +287,7 @@ where F: FnMut(&SymbolicExpression) -> Result<T, E> {
     Ok(last.unwrap())
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SymbolicExpression {
     pub expr: SymbolicExpressionType,
     // this id field is used by compiler passes to store information in
@@ -428,6 +429,23 @@ impl SymbolicExpression {
     }
 }
 
+/// Expressions are compared and hashed by their syntax alone: the ids and spans
+///   assigned by the parser are ignored, so the same code parsed from different
+///   positions (or sources) compares equal.
+impl PartialEq for SymbolicExpression {
+    fn eq(&self, other: &SymbolicExpression) -> bool {
+        self.expr == other.expr
+    }
+}
+
+impl Eq for SymbolicExpression {}
+
+impl Hash for SymbolicExpression {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.expr.hash(state)
+    }
+}
+
 impl fmt::Display for SymbolicExpression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.expr {
@@ -12,6 +12,8 @@ use vm::tests::{with_memory_environment, with_marfed_environment, symbols_from_v
 const FIRST_CLASS_TOKENS: &str = "(define-fungible-token stackaroos)
          (define-read-only (my-ft-get-balance (account principal))
             (ft-get-balance stackaroos account))
+         (define-read-only (my-ft-get-supply)
+            (ft-get-supply stackaroos))
          (define-public (my-token-transfer (to principal) (amount uint))
             (ft-transfer? stackaroos amount tx-sender to))
          (define-public (faucet)
@@ -106,6 +108,7 @@ fn execute_transaction(env: &mut OwnedEnvironment, issuer: Value, contract_ident
 fn test_native_stx_ops(owned_env: &mut OwnedEnvironment) {
     let contract = "(define-public (burn-stx (amount uint) (p principal)) (stx-burn? amount p))
                     (define-public (xfer-stx (amount uint) (p principal) (t principal)) (stx-transfer? amount p t))
+                    (define-read-only (account (p principal)) (stx-account p))
                     (define-public (to-contract (amount uint) (p principal))
                       (let ((contract-principal (as-contract tx-sender)))
                         (stx-transfer? amount p contract-principal)))
@@ -145,6 +148,13 @@ fn test_native_stx_ops(owned_env: &mut OwnedEnvironment) {
     owned_env.stx_faucet(&(p1_principal.clone().into()), u128::max_value() - 1);
     owned_env.stx_faucet(&p2_principal, 1000);
 
+    let (result, asset_map, _events) = execute_transaction(
+        owned_env, p1.clone(), &token_contract_id, "account",
+        &symbols_from_values(vec![p2.clone()])).unwrap();
+
+    assert_eq!(result, execute("(tuple (locked u0) (unlock-height u0) (unlocked u1000))"));
+    assert_eq!(asset_map.to_table().len(), 0);
+
     // test 1: send 0
 
     let (result, asset_map, _events) = execute_transaction(
@@ -423,6 +433,14 @@ fn test_simple_token_system(owned_env: &mut OwnedEnvironment) {
         result,
         Value::UInt(1003));
 
+    let (result, asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id.clone(), "my-ft-get-supply", &vec![]).unwrap();
+
+    assert_eq!(
+        result,
+        Value::UInt(10204));
+    assert_eq!(asset_map.to_table().len(), 0);
+
     let (result, asset_map, _events) = execute_transaction(owned_env,
         p1.clone(), &token_contract_id.clone(), "mint-after", &symbols_from_values(vec![Value::UInt(25)])).unwrap();
 
@@ -438,6 +456,8 @@ fn total_supply(owned_env: &mut OwnedEnvironment) {
     let contract = "(define-fungible-token stackaroos u5)
          (define-read-only (get-balance (account principal))
             (ft-get-balance stackaroos account))
+         (define-read-only (get-supply)
+            (ft-get-supply stackaroos))
          (define-public (transfer (to principal) (amount uint))
             (ft-transfer? stackaroos amount tx-sender to))
          (define-public (faucet)
@@ -491,6 +511,11 @@ fn total_supply(owned_env: &mut OwnedEnvironment) {
         Error::Runtime(RuntimeErrorType::SupplyOverflow(x, y), _) => (x, y) == (6, 5),
         _ => false
     });
+
+    // only the committed mints count towards the supply
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id.clone(), "get-supply", &vec![]).unwrap();
+    assert_eq!(result, Value::UInt(4));
 }
 
 fn test_overlapping_nfts(owned_env: &mut OwnedEnvironment) {
@@ -695,6 +720,14 @@ fn test_simple_naming_system(owned_env: &mut OwnedEnvironment) {
     assert!(is_err_code(&result, 5));
 }
 
+#[test]
+fn test_unminted_token_supply() {
+    // tokens without a total supply have no supply stored until first minted
+    assert_eq!(vm_execute("(define-fungible-token stackaroos) (ft-get-supply stackaroos)").unwrap(),
+               Some(Value::UInt(0)));
+    assert_eq!(vm_execute("(define-fungible-token stackaroos u10) (ft-get-supply stackaroos)").unwrap(),
+               Some(Value::UInt(0)));
+}
 
 #[test]
 fn test_all() {
@@ -3,7 +3,7 @@ use vm::execute as vm_execute;
 use chainstate::burn::BlockHeaderHash;
 use vm::errors::{Error, CheckErrors, RuntimeErrorType};
 use vm::types::{Value, OptionalData, StandardPrincipalData, ResponseData,
-                TypeSignature, PrincipalData, QualifiedContractIdentifier};
+                TypeSignature, PrincipalData, QualifiedContractIdentifier, TupleData};
 use vm::ast::errors::ParseErrors;
 use vm::contexts::{OwnedEnvironment,GlobalContext, Environment};
 use vm::representations::SymbolicExpression;
@@ -341,6 +341,33 @@ fn test_fully_qualified_contract_call(owned_env: &mut OwnedEnvironment) {
     }
 }
 
+fn test_contract_map_get(owned_env: &mut OwnedEnvironment) {
+    let contract_a =
+        "(define-map store ((key int)) ((value uint)))
+         (map-set store ((key 1)) ((value u10)))";
+    let contract_b =
+        "(define-read-only (lookup (key int))
+           (contract-map-get? .contract-a store ((key key))))";
+
+    {
+        let mut env = owned_env.get_exec_environment(None);
+        env.initialize_contract(QualifiedContractIdentifier::local("contract-a").unwrap(), contract_a).unwrap();
+        env.initialize_contract(QualifiedContractIdentifier::local("contract-b").unwrap(), contract_b).unwrap();
+    }
+
+    {
+        let mut env = owned_env.get_exec_environment(Some(get_principal()));
+        let contract_b_id = QualifiedContractIdentifier::local("contract-b").unwrap();
+        let stored = Value::Tuple(TupleData::from_data(vec![("value".into(), Value::UInt(10))]).unwrap());
+        assert_eq!(
+            env.execute_contract(&contract_b_id, "lookup", &symbols_from_values(vec![Value::Int(1)]), false).unwrap(),
+            Value::some(stored).unwrap());
+        assert_eq!(
+            env.execute_contract(&contract_b_id, "lookup", &symbols_from_values(vec![Value::Int(2)]), false).unwrap(),
+            Value::none());
+    }
+}
+
 fn test_simple_naming_system(owned_env: &mut OwnedEnvironment) {
     let tokens_contract = SIMPLE_TOKENS;
 
@@ -789,6 +816,7 @@ fn test_all() {
                     test_aborts,
                     test_contract_caller,
                     test_fully_qualified_contract_call,
+                    test_contract_map_get,
                     test_simple_naming_system,
                     test_simple_contract_call ];
     for test in to_test.iter() {
@@ -3,6 +3,7 @@ use vm::errors::{Error, CheckErrors, RuntimeErrorType};
 use vm::types::{Value, PrincipalData, ResponseData, QualifiedContractIdentifier, AssetIdentifier};
 use vm::contexts::{OwnedEnvironment, GlobalContext, AssetMap, AssetMapEntry};
 use vm::functions::NativeFunctions;
+use vm::functions::iterables::native_len;
 use vm::representations::SymbolicExpression;
 use vm::contracts::Contract;
 use util::hash::hex_bytes;
@@ -10,7 +11,7 @@ use vm::tests::{with_memory_environment, with_marfed_environment, symbols_from_v
                 execute, is_err_code, is_committed};
 
 use vm::contexts::{Environment};
-use vm::costs::{ExecutionCost};
+use vm::costs::{ExecutionCost, cost_functions};
 use vm::database::{ClarityDatabase, MarfedKV, MemoryBackingStore,
                    NULL_HEADER_DB};
 use chainstate::stacks::events::StacksTransactionEvent;
@@ -25,6 +26,9 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         ToInt => "(to-int u1)",
         Subtract => "(- 1 1)",
         Multiply => "(* 1 1)",
+        CheckedAdd => "(+! 1 1)",
+        CheckedSub => "(-! 1 1)",
+        CheckedMul => "(*! 1 1)",
         Divide => "(/ 1 1)",
         CmpGeq => "(>= 2 1)",
         CmpLeq => "(<= 2 1)",
@@ -50,18 +54,32 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         Len => "(len list-bar)",
         ListCons => "(list 1 2 3 4)",
         FetchEntry => "(map-get? map-foo {a: 1})",
+        FetchContractEntry => "(contract-map-get? .contract-other map-foo {a: 1})",
         SetEntry => "(map-set map-foo {a: 1} {b: 2})",
         InsertEntry => "(map-insert map-foo {a: 2} {b: 2})",
         DeleteEntry => "(map-delete map-foo {a: 1})",
+        SetGetEntry => "(map-set-get map-foo {a: 1} {b: 2})",
         TupleCons => "(tuple (a 1))",
         TupleGet => "(get a tuple-foo)",
+        TupleGetIn => "(get-in tuple-foo (a))",
+        TupleGetAt => "(get-at (list tuple-foo) 0 a)",
         Begin => "(begin 1)",
         Hash160 => "(hash160 1)",
         Sha256 => "(sha256 1)",
         Sha512 => "(sha512 1)",
         Sha512Trunc256 => "(sha512/256 1)",
         Keccak256 => "(keccak256 1)",
+        Ripemd160 => "(ripemd160 1)",
+        HashPair => "(hash-pair 0x0000000000000000000000000000000000000000000000000000000000000000 0x0000000000000000000000000000000000000000000000000000000000000000)",
+        Secp256k1Recover => "(secp256k1-recover? 0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9 0x00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6)",
+        Secp256k1Verify => "(secp256k1-verify 0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9 0x00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6 0x0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219)",
+        ToConsensusBuff => "(to-consensus-buff? 1)",
+        FromConsensusBuff => "(from-consensus-buff? int 0x0000000000000000000000000000000001)",
+        PrincipalDestruct => "(principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        PrincipalConstruct => "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce)",
         Print => "(print 1)",
+        PrintEvent => "(print-event \"topic\" 1)",
+        TestAssert => "(test-assert true \"message\")",
         ContractCall => "(contract-call? .contract-other foo-exec 1)",
         AsContract => "(as-contract 1)",
         GetBlockInfo => "(get-block-info? time u1)",
@@ -77,18 +95,25 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         Match => "(match (some 1) x (+ x 1) 1)",
         TryRet => "(try! (if true (ok 1) (err 1)))",
         IsOkay => "(is-ok (ok 1))",
+        GetOk => "(get-ok (ok 1))",
+        GetErr => "(get-err (err 1))",
+        Flatten => "(flatten (some (some 1)))",
+        BuffToUIntLe => "(buff-to-uint-le 0x0100)",
+        BuffToUIntBe => "(buff-to-uint-be 0x0100)",
         IsNone => "(is-none none)",
         IsErr => "(is-err (err 1))",
         IsSome => "(is-some (some 1))",
         MintAsset => "(ft-mint? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         MintToken => "(nft-mint? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         GetTokenBalance => "(ft-get-balance ft-foo 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        GetTokenSupply => "(ft-get-supply ft-foo)",
         GetAssetOwner => "(nft-get-owner? nft-foo 1)",
         TransferToken => "(ft-transfer? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         TransferAsset => "(nft-transfer? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         AtBlock => "(at-block 0x0000000000000000000000000000000000000000000000000000000000000000 1)",
         StxTransfer => "(stx-transfer? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         StxBurn => "(stx-burn? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        StxGetAccount => "(stx-account 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
     }
 }
 
@@ -157,3 +182,16 @@ fn test_all() {
         assert!(cost.exceeds(&baseline));
     }
 }
+
+#[test]
+fn test_len_cost_independent_of_size() {
+    // `len` is charged per application, so a million-byte buffer must cost the same
+    //   as a single byte.
+    assert_eq!(cost_functions::LEN.compute_cost(1).unwrap(),
+               cost_functions::LEN.compute_cost(1_000_000).unwrap());
+
+    let large = Value::buff_from(vec![0; 1_000_000]).unwrap();
+    assert_eq!(native_len(large).unwrap(), Value::UInt(1_000_000));
+    let list = Value::list_from((0..1000).map(Value::Int).collect()).unwrap();
+    assert_eq!(native_len(list).unwrap(), Value::UInt(1000));
+}
@@ -58,6 +58,24 @@ fn test_simple_tea_shop() {
     assert_executes(expected, test1);
 }
 
+#[test]
+fn test_single_field_key_shorthand() {
+    let test =
+        "(define-map balances ((owner int)) ((balance int)))
+         (define-map nested ((key (tuple (a int)))) ((value int)))
+         (map-set balances (tuple (owner 1)) (tuple (balance 10)))
+         (map-set nested (tuple (key (tuple (a 1)))) (tuple (value 20)))
+         (list (default-to 0 (get balance (map-get? balances 1)))
+               (default-to 0 (get balance (map-get? balances (tuple (owner 1)))))
+               (default-to 0 (get balance (map-get? balances 2)))
+               (default-to 0 (get value (map-get? nested (tuple (a 1))))))";
+
+    let expected = Value::list_from(vec![
+        Value::Int(10), Value::Int(10), Value::Int(0), Value::Int(20)]);
+
+    assert_executes(expected, test);
+}
+
 #[test]
 fn test_bound_tuple() {
     let test =
@@ -99,6 +117,40 @@ fn test_bound_tuple() {
     assert_executes(expected, &test_get);
 }
 
+#[test]
+fn test_get_in_nested_tuple() {
+    let test =
+        "(define-map users ((id int)) ((profile (tuple (name (buff 10)) (age int)))))
+         (map-insert users ((id 1)) ((profile (tuple (name \"alice\") (age 30)))))
+         (define-constant nested (tuple (a (tuple (b (tuple (c 1)))))))";
+
+    assert_executes(Ok(Value::Int(1)), &format!("{} (get-in nested (a b c))", test));
+    assert_executes(Ok(Value::some(Value::Int(30)).unwrap()),
+                    &format!("{} (get-in (map-get? users ((id 1))) (profile age))", test));
+    assert_executes(Ok(Value::none()),
+                    &format!("{} (get-in (map-get? users ((id 2))) (profile age))", test));
+}
+
+#[test]
+fn test_get_at_list_of_tuples() {
+    let test = "(define-constant items (list (tuple (id 1) (active true)) (tuple (id 2) (active false))))";
+
+    assert_executes(Ok(Value::some(Value::Int(2)).unwrap()), &format!("{} (get-at items 1 id)", test));
+    assert_executes(Ok(Value::none()), &format!("{} (get-at items 2 id)", test));
+    assert_executes(Ok(Value::none()), &format!("{} (get-at items -1 id)", test));
+}
+
+#[test]
+fn test_tuple_field_punning() {
+    let expected = Value::from(TupleData::from_data(vec![
+        ("a".into(), Value::Int(1)),
+        ("b".into(), Value::UInt(2)),
+        ("c".into(), Value::Bool(true))]).unwrap());
+
+    assert_executes(Ok(expected.clone()), "(let ((a 1) (b u2)) (tuple (a) (b) (c true)))");
+    assert_executes(Ok(expected), "(define-constant b u2) (let ((a 1)) (tuple (a) (c true) (b)))");
+}
+
 #[test]
 fn test_explicit_syntax_tuple() {
     let test =
@@ -175,6 +227,22 @@ fn test_implicit_syntax_tuple() {
     assert_executes(expected, &test_get);
 }
 
+#[test]
+fn test_set_get_entry() {
+    let contract_src = "(define-map kv-store ((key int)) ((value int)))
+                        (list (map-set-get kv-store ((key 1)) ((value 10)))
+                              (map-set-get kv-store ((key 1)) ((value 20)))
+                              (map-get? kv-store ((key 1))))";
+
+    let entry = |value| Value::some(Value::from(TupleData::from_data(
+        vec![("value".into(), Value::Int(value))]).unwrap())).unwrap();
+    let expected = Value::list_from(vec![
+        Value::none(),
+        entry(10),
+        entry(20)]);
+    assert_executes(expected, contract_src);
+}
+
 #[test]
 fn test_set_int_variable() {
         let contract_src = r#"
@@ -247,6 +315,30 @@ fn test_set_tuple_variable() {
     assert_executes(expected, &contract_src);
 }
 
+#[test]
+fn test_set_nested_tuple_variable() {
+    let contract_src = r#"
+        (define-data-var v (tuple (a int) (b (optional (list 3 int)))) (tuple (a 0) (b none)))
+        (define-private (get-v)
+            (var-get v))
+        (define-private (set-v (value (tuple (a int) (b (optional (list 3 int))))))
+            (if (var-set v value)
+                value
+                (get-v)))
+    "#;
+    let mut contract_src = contract_src.to_string();
+    contract_src.push_str("(list (get-v) (set-v (tuple (a 1) (b (some (list 1 2))))) (get-v))");
+
+    let initial = Value::Tuple(TupleData::from_data(vec![
+        ("a".into(), Value::Int(0)), ("b".into(), Value::none())]).unwrap());
+    let updated = Value::Tuple(TupleData::from_data(vec![
+        ("a".into(), Value::Int(1)),
+        ("b".into(), Value::some(Value::list_from(vec![Value::Int(1), Value::Int(2)]).unwrap()).unwrap())]).unwrap());
+
+    let expected = Value::list_from(vec![initial, updated.clone(), updated]);
+    assert_executes(expected, &contract_src);
+}
+
 #[test]
 fn test_set_response_variable() {
     let contract_src = r#"
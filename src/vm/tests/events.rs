@@ -62,6 +62,50 @@ fn test_emit_print_nok() {
     assert_eq!(events.len(), 0);
 }
 
+#[test]
+fn test_emit_print_event_ok() {
+    let contract =
+        "(define-public (emit-event-ok)
+            (begin
+                (print-event \"deposit\" u10)
+                (ok u1)))";
+
+    let (value, mut events) = helper_execute(contract, "emit-event-ok");
+    assert_eq!(value, Value::okay(Value::UInt(1)).unwrap());
+    assert_eq!(events.len(), 1);
+    match events.pop() {
+        Some(StacksTransactionEvent::SmartContractEvent(data)) => {
+            let contract_id = QualifiedContractIdentifier::local("contract").unwrap();
+            assert_eq!(data.key, (contract_id, "deposit".to_string()));
+            assert_eq!(data.value, Value::UInt(10));
+        },
+        _ => panic!("assertion failed")
+    };
+}
+
+#[test]
+fn test_emit_test_assert() {
+    let contract =
+        "(define-public (emit-event-ok)
+            (begin
+                (test-assert (is-eq (+ 1 1) 2) \"passes\")
+                (test-assert (is-eq (+ 1 1) 3) \"sum is wrong\")
+                (ok u1)))";
+
+    let (value, mut events) = helper_execute(contract, "emit-event-ok");
+    assert_eq!(value, Value::okay(Value::UInt(1)).unwrap());
+    // only the failed assertion emits its message
+    assert_eq!(events.len(), 1);
+    match events.pop() {
+        Some(StacksTransactionEvent::SmartContractEvent(data)) => {
+            let contract_id = QualifiedContractIdentifier::local("contract").unwrap();
+            assert_eq!(data.key, (contract_id, "test-assert".to_string()));
+            assert_eq!(data.value, Value::buff_from(b"sum is wrong".to_vec()).unwrap());
+        },
+        _ => panic!("assertion failed")
+    };
+}
+
 #[test]
 fn test_emit_stx_transfer_ok() {
     let contract =
@@ -61,6 +61,39 @@ fn test_simple_map_list() {
     assert_eq!(Value::list_from(vec![]).unwrap(), execute(test2).unwrap().unwrap());
 }
 
+#[test]
+fn test_inline_lambdas() {
+    let tests = ["(map (lambda (x) (* x x)) (list 1 2 3 4))",
+                 "(let ((base 10)) (map (lambda (x) (- x base)) (list 11 14 19 26)))",
+                 "(filter (lambda (x) (> x 1)) (list 1 4 9 16))",
+                 "(fold (lambda (x acc) (+ acc x)) (list 1 2 3 4) 0)",
+                 "(map (lambda (b) (is-eq b 0x01)) 0x0102)"];
+
+    let squares = Value::list_from(vec![
+        Value::Int(1),
+        Value::Int(4),
+        Value::Int(9),
+        Value::Int(16)]).unwrap();
+    let expected = [
+        squares.clone(),
+        squares,
+        Value::list_from(vec![Value::Int(4), Value::Int(9), Value::Int(16)]).unwrap(),
+        Value::Int(10),
+        Value::list_from(vec![Value::Bool(true), Value::Bool(false)]).unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected, &execute(test).unwrap().unwrap());
+    }
+
+    // (expected, actual)
+    assert_eq!(
+        execute("(map (lambda (x y) (+ x y)) (list 1 2 3))").unwrap_err(),
+        CheckErrors::IncorrectArgumentCount(2, 1).into());
+    assert_eq!(
+        execute("(fold (lambda (x) x) (list 1 2 3) 0)").unwrap_err(),
+        CheckErrors::IncorrectArgumentCount(1, 2).into());
+}
+
 #[test]
 fn test_simple_map_append() {
     let tests = [
@@ -251,6 +284,18 @@ fn test_simple_filter_list() {
     }
 }
 
+#[test]
+fn test_filter_optional_predicate() {
+    let test1 = "(define-private (positive (x int)) (if (is-eq x 0) none (some (> x 0))))
+                 (filter positive (list 1 0 -1 2))";
+
+    let expected = Value::list_from(vec![
+        Value::Int(1),
+        Value::Int(2)]).unwrap();
+
+    assert_eq!(expected, execute(test1).unwrap().unwrap());
+}
+
 #[test]
 fn test_simple_filter_buffer() {
     let test1 = "(define-private (test (x (buff 1))) (not (is-eq x \"0\")))
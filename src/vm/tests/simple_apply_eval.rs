@@ -4,11 +4,13 @@ use vm::errors::{CheckErrors, ShortReturnType, RuntimeErrorType, Error};
 use vm::{Value, LocalContext, ContractContext, GlobalContext, Environment, CallStack};
 use vm::contexts::{OwnedEnvironment};
 use vm::callables::DefinedFunction;
-use vm::types::{TypeSignature, BuffData, QualifiedContractIdentifier};
+use vm::types::{TypeSignature, BuffData, TupleData, QualifiedContractIdentifier, PrincipalData,
+                StandardPrincipalData, BUFF_16};
 use vm::ast::parse;
 use vm::costs::LimitedCostTracker;
 use util::hash::{hex_bytes, to_hex};
 use std::collections::HashMap;
+use std::convert::TryInto;
 use vm::tests::{execute};
 
 #[test]
@@ -124,6 +126,182 @@ fn test_sha512trunc256() {
         .for_each(|(program, expectation)| assert_eq!(expectation, &p_to_hex(execute(program))));
 }
 
+#[test]
+fn test_hash_output_lengths() {
+    let hashes = [("hash160", 20), ("sha256", 32), ("sha512", 64), ("sha512/256", 32), ("keccak256", 32), ("ripemd160", 20)];
+    let inputs = ["\"abc\"", "1", "u1"];
+
+    for (hash, length) in hashes.iter() {
+        for input in inputs.iter() {
+            match execute(&format!("({} {})", hash, input)) {
+                Value::Buffer(BuffData { data }) => assert_eq!(*length, data.len()),
+                _ => panic!("Expected a buffer from {}", hash)
+            }
+        }
+    }
+
+    fn to_buffer(hex: &str) -> Value {
+        return Value::Buffer(BuffData { data: hex_bytes(hex).unwrap() });
+    }
+
+    // the FIPS 180-4 test vectors for the message "abc"
+    assert_eq!(to_buffer("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+               execute("(sha512 \"abc\")"));
+    assert_eq!(to_buffer("53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+               execute("(sha512/256 \"abc\")"));
+}
+
+#[test]
+fn test_ripemd160() {
+    let ripemd160_evals = [
+        "(ripemd160 \"\")",
+        "(ripemd160 0)",
+        "(ripemd160 \"abc\")",
+    ];
+
+    fn to_buffer(hex: &str) -> Value {
+        return Value::Buffer(BuffData { data: hex_bytes(hex).unwrap() });
+    }
+
+    let expectations = [
+        "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+        "f2760c89487a4bf0d47f6ccca8d68915311a80d6",
+        "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+    ];
+
+    ripemd160_evals.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(to_buffer(expectation), execute(program)));
+}
+
+#[test]
+fn test_principal_destruct() {
+    let principal_destruct_evals = [
+        "(principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        "(principal-destruct? 'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM.foo)",
+    ];
+
+    fn parts(version: u8, hash_bytes: &str, name: Option<&str>) -> Value {
+        let name = match name {
+            Some(name) => Value::some(Value::buff_from(name.as_bytes().to_vec()).unwrap()).unwrap(),
+            None => Value::none()
+        };
+        Value::okay(Value::from(TupleData::from_data(vec![
+            ("version".into(), Value::buff_from_byte(version)),
+            ("hash-bytes".into(), Value::buff_from(hex_bytes(hash_bytes).unwrap()).unwrap()),
+            ("name".into(), name)]).unwrap())).unwrap()
+    }
+
+    let expectations = [
+        parts(31, "a46ff88886c2ef9762d970b4d2c63678835bd39d", None),
+        parts(26, "6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce", Some("foo")),
+    ];
+
+    principal_destruct_evals.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(*expectation, execute(program)));
+}
+
+#[test]
+fn test_principal_construct() {
+    let principal_construct_evals = [
+        "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce)",
+        "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce \"foo\")",
+        "(principal-construct? 0x20 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce)",
+        "(principal-construct? 0x1a 0x6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce \"1foo\")",
+        "(unwrap-panic (principal-construct? (get version (unwrap-panic (principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.bar)))
+                                             (get hash-bytes (unwrap-panic (principal-destruct? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.bar)))
+                                             \"bar\"))",
+    ];
+
+    fn construct_error(error_code: u128, value: Option<PrincipalData>) -> Value {
+        let value = match value {
+            Some(principal) => Value::some(Value::from(principal)).unwrap(),
+            None => Value::none()
+        };
+        Value::error(Value::from(TupleData::from_data(vec![
+            ("error_code".into(), Value::UInt(error_code)),
+            ("value".into(), value)]).unwrap())).unwrap()
+    }
+
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&hex_bytes("6d78de7b0625dfbfc16c3a8a5735f6dc3dc3f2ce").unwrap());
+
+    let expectations = [
+        Value::okay(execute("'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM")).unwrap(),
+        Value::okay(execute("'ST1PQHQKV0RJXZFY1DGX8MNSNYVE3VGZJSRTPGZGM.foo")).unwrap(),
+        construct_error(0, Some(PrincipalData::Standard(StandardPrincipalData(32, hash)))),
+        construct_error(1, None),
+        execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.bar"),
+    ];
+
+    principal_construct_evals.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(*expectation, execute(program)));
+}
+
+#[test]
+fn test_hash_pair() {
+    let left = "0x0000000000000000000000000000000000000000000000000000000000000000";
+    let right = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    let expected = hex_bytes("705ede9d42476fc3e5a978b042ce790a193678f46d19f47ec4ab46539c47b76d").unwrap();
+    assert_eq!(Value::buff_from(expected).unwrap(),
+               execute(&format!("(hash-pair {} {})", left, right)));
+
+    // a short buffer is rejected at runtime, even without analysis
+    let short = vm_execute(&format!("(hash-pair {} 0x0102)", left)).unwrap_err();
+    assert_eq!(short, CheckErrors::TypeValueError(TypeSignature::BufferType(32_u32.try_into().unwrap()),
+                                                  Value::buff_from(vec![1, 2]).unwrap()).into());
+}
+
+#[test]
+fn test_secp256k1() {
+    let hash = "0xb94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+    let signature = "0x00354445a1dc98a1bd27984dbe69979a5cd77886b4d9134af5c40e634d96e1cb445b97de5b632582d31704f86706a780886e6e381bfed65228267358262d203fe6";
+    let pubkey = "0x0385f2e2867524289d6047d0d9c5e764c5d413729fc32291ad2c353fbc396a4219";
+
+    let pubkey_value = Value::buff_from(hex_bytes(&pubkey[2..]).unwrap()).unwrap();
+    assert_eq!(Value::okay(pubkey_value).unwrap(),
+               execute(&format!("(secp256k1-recover? {} {})", hash, signature)));
+    assert_eq!(Value::Bool(true),
+               execute(&format!("(secp256k1-verify {} {} {})", hash, signature, pubkey)));
+
+    // signed a different message
+    let other_hash = "0xca3704aa0b06f5954c79ee837faa152d84d6b2d42838f0637a15eda8337dbdce";
+    assert_eq!(Value::Bool(false),
+               execute(&format!("(secp256k1-verify {} {} {})", other_hash, signature, pubkey)));
+
+    // an invalid recovery id
+    let bad_signature = format!("0x03{}", &signature[4..]);
+    assert_eq!(Value::error(Value::UInt(1)).unwrap(),
+               execute(&format!("(secp256k1-recover? {} {})", hash, bad_signature)));
+    assert_eq!(Value::Bool(false),
+               execute(&format!("(secp256k1-verify {} {} {})", hash, bad_signature, pubkey)));
+
+    // a buffer of the wrong length is rejected at runtime, even without analysis
+    let short = vm_execute(&format!("(secp256k1-verify {} {} 0x0102)", hash, signature)).unwrap_err();
+    assert_eq!(short, CheckErrors::TypeValueError(TypeSignature::BufferType(33_u32.try_into().unwrap()),
+                                                  Value::buff_from(vec![1, 2]).unwrap()).into());
+}
+
+#[test]
+fn test_consensus_buff_round_trip() {
+    let tuple = "(tuple (a 1) (b 0x0102) (c (list u1 u2)))";
+    let tuple_type = "(tuple (a int) (b (buff 2)) (c (list 2 uint)))";
+
+    assert_eq!(execute(&format!("(some {})", tuple)),
+               execute(&format!("(from-consensus-buff? {} (unwrap-panic (to-consensus-buff? {})))", tuple_type, tuple)));
+
+    assert_eq!(Value::some(Value::buff_from(hex_bytes("0000000000000000000000000000000001").unwrap()).unwrap()).unwrap(),
+               execute("(to-consensus-buff? 1)"));
+
+    // the wrong type, trailing bytes, and truncated input all fail to deserialize
+    let bad = ["(from-consensus-buff? uint 0x0000000000000000000000000000000001)",
+               "(from-consensus-buff? int 0x000000000000000000000000000000000100)",
+               "(from-consensus-buff? int 0x00000000000000000000000000000001)"];
+    for program in bad.iter() {
+        assert_eq!(Value::none(), execute(program));
+    }
+}
+
 #[test]
 fn test_keccak256() {
     let keccak256_evals = [
@@ -163,6 +341,25 @@ fn test_buffer_equality() {
         .for_each(|(program, expectation)| assert_eq!(expectation.clone(), execute(program)));
 }
 
+#[test]
+fn test_list_equality() {
+    let tests = [
+        "(is-eq (list 1 2 3) (list 1 2 3))",
+        "(is-eq (list 1 2) (list 1 2 3))",
+        "(is-eq (list (list 1) (list 2 3)) (list (list 1) (list 2 3)))",
+        "(is-eq (list (list 1) (list 2 3)) (list (list 1) (list 3 2)))",
+        "(is-eq (list) (list))"];
+    let expectations = [
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::Bool(true)];
+
+    tests.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(expectation.clone(), execute(program)));
+}
+
 #[test]
 fn test_principal_equality() {
     let tests = [
@@ -387,6 +584,32 @@ fn test_unsigned_arithmetic() {
     }
 }
 
+#[test]
+fn test_checked_arithmetic() {
+    let tests = [
+        "(+! 1 2)",
+        "(-! u10 u11)",
+        "(*! 2 -3)",
+        "(+! u340282366920938463463374607431768211455 u1)",
+        "(-! -170141183460469231731687303715884105728 1)",
+        "(*! 170141183460469231731687303715884105727 2)",
+    ];
+
+    let overflow = Value::error(Value::UInt(1)).unwrap();
+    let expectations = [
+        Value::okay(Value::Int(3)).unwrap(),
+        overflow.clone(),
+        Value::okay(Value::Int(-6)).unwrap(),
+        overflow.clone(),
+        overflow.clone(),
+        overflow,
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).unwrap().unwrap());
+    }
+}
+
 #[test]
 fn test_options_errors() {
     let tests = [
@@ -485,6 +708,51 @@ fn test_some() {
 }
 
 
+#[test]
+fn test_optional_guard_not_refined() {
+    let tests = [
+        "(let ((x (some 1))) (if (is-some x) (+ (unwrap-panic x) 1) 0))",
+        "(let ((x (some 1))) (if (is-none x) 0 (+ (unwrap-panic x) 1)))",
+        "(let ((x (some 1))) (if (is-some x) x none))",
+        "(define-private (inc-or-zero (x (optional int)))
+           (if (is-some x) (+ (unwrap-panic x) 1) 0))
+         (+ (inc-or-zero (some 2)) (inc-or-zero none))",
+    ];
+
+    let expectations = [
+        Value::Int(2),
+        Value::Int(2),
+        Value::some(Value::Int(1)).unwrap(),
+        Value::Int(3),
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).unwrap().unwrap());
+    }
+}
+
+#[test]
+fn test_optional_bool_condition() {
+    let tests = [
+        "(if (some true) 1 2)",
+        "(if (some false) 1 2)",
+        "(if none 1 2)",
+        "(define-private (check (x (optional bool))) (if x 1 2))
+         (+ (check (some true)) (check none))",
+    ];
+
+    let expectations = [
+        Value::Int(1),
+        Value::Int(2),
+        Value::Int(2),
+        Value::Int(3),
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).unwrap().unwrap());
+    }
+}
+
 #[test]
 fn test_option_destructs() {
     let tests = [
@@ -545,6 +813,78 @@ fn test_option_destructs() {
     }
 }
 
+#[test]
+fn test_response_projections() {
+    let tests = [
+        "(get-ok (ok 1))",
+        "(get-ok (err u1))",
+        "(get-err (err u1))",
+        "(get-err (ok 1))",
+        "(get-ok (some 1))",
+        "(get-err 1)",
+        ];
+
+    let expectations: &[Result<Value, Error>] = &[
+        Ok(Value::some(Value::Int(1)).unwrap()),
+        Ok(Value::none()),
+        Ok(Value::some(Value::UInt(1)).unwrap()),
+        Ok(Value::none()),
+        Err(CheckErrors::ExpectedResponseValue(Value::some(Value::Int(1)).unwrap()).into()),
+        Err(CheckErrors::ExpectedResponseValue(Value::Int(1)).into()),
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).map(|x| x.unwrap()));
+    }
+}
+
+#[test]
+fn test_flatten() {
+    let tests = [
+        "(flatten (some (some 1)))",
+        "(flatten (some none))",
+        "(flatten none)",
+        "(flatten (get a (some (tuple (a (some u2))))))",
+        "(flatten (some 1))",
+        "(flatten 1)",
+        ];
+
+    let expectations: &[Result<Value, Error>] = &[
+        Ok(Value::some(Value::Int(1)).unwrap()),
+        Ok(Value::none()),
+        Ok(Value::none()),
+        Ok(Value::some(Value::UInt(2)).unwrap()),
+        Err(CheckErrors::ExpectedOptionalValue(Value::Int(1)).into()),
+        Err(CheckErrors::ExpectedOptionalValue(Value::Int(1)).into()),
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).map(|x| x.unwrap()));
+    }
+}
+
+#[test]
+fn test_buff_to_uint() {
+    let tests = [
+        "(buff-to-uint-le 0x0102030405060708)",
+        "(buff-to-uint-be 0x0102030405060708)",
+        "(buff-to-uint-be 0xffffffffffffffffffffffffffffffff)",
+        "(buff-to-uint-le 0x0102030405060708090a0b0c0d0e0f1011121314)",
+        "(buff-to-uint-be 1)",
+        ];
+
+    let expectations: &[Result<Value, Error>] = &[
+        Ok(Value::UInt(0x0807060504030201)),
+        Ok(Value::UInt(0x0102030405060708)),
+        Ok(Value::UInt(u128::max_value())),
+        Err(CheckErrors::BufferTooLargeForInt(20).into()),
+        Err(CheckErrors::TypeValueError(BUFF_16, Value::Int(1)).into()),
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).map(|x| x.unwrap()));
+    }
+}
 
 #[test]
 fn test_hash_errors() {
@@ -643,6 +983,20 @@ fn test_lets() {
         .for_each(|(program, expectation)| assert_eq!(expectation.clone(), execute(program)));
 }
 
+#[test]
+fn test_let_tuple_destructuring() {
+    let tests = [
+        "(let (({ a: x, b: y } (tuple (a 1) (b 2)))) (+ x y))",
+        "(let ((pair { a: 1, b: 2 }) ({ b: y } pair)) y)"];
+
+    let expectations = [
+        Value::Int(3),
+        Value::Int(2)];
+
+    tests.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(expectation.clone(), execute(program)));
+}
+
 #[test]
 fn test_asserts() {
     let tests = [
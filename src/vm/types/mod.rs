@@ -2,6 +2,7 @@ pub mod serialization;
 pub mod signatures;
 
 use std::{fmt, cmp};
+use std::hash::{Hash, Hasher};
 use std::convert::{TryInto, TryFrom};
 use std::collections::BTreeMap;
 
@@ -13,7 +14,7 @@ use util::hash;
 pub use vm::types::signatures::{
     TupleTypeSignature, AssetIdentifier, FixedFunction, FunctionSignature,
     TypeSignature, FunctionType, ListTypeData, FunctionArg, parse_name_type_pairs,
-    BUFF_64, BUFF_32, BUFF_20, BufferLength
+    BUFF_65, BUFF_64, BUFF_33, BUFF_32, BUFF_20, BUFF_16, BUFF_1, BufferLength
 };
 
 pub const MAX_VALUE_SIZE: u32 = 1024 * 1024; // 1MB
@@ -21,8 +22,15 @@ pub const BOUND_VALUE_SERIALIZATION_BYTES: u32 = MAX_VALUE_SIZE * 2;
 pub const BOUND_VALUE_SERIALIZATION_HEX: u32 = BOUND_VALUE_SERIALIZATION_BYTES * 2;
 
 pub const MAX_TYPE_DEPTH: u8 = 32;
+// the maximum nesting of list types. the innermost entry type of a list always takes
+//   up one level of type depth, so this is the deepest list that fits in MAX_TYPE_DEPTH.
+pub const MAX_LIST_DIMENSION: u8 = MAX_TYPE_DEPTH - 1;
+// the maximum number of elements in a constructed list, whatever the size of its elements.
+pub const MAX_LIST_LENGTH: u32 = 65535;
 // this is the charged size for wrapped values, i.e., response or optionals
 pub const WRAPPER_VALUE_SIZE: u32 = 1;
+// the widest buffer which may be converted to a uint, i.e., the byte width of a u128
+pub const MAX_UINT_BUFFER_LEN: usize = 16;
 
 #[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct TupleData {
@@ -31,7 +39,7 @@ pub struct TupleData {
     pub data_map: BTreeMap<ClarityName, Value>
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BuffData {
     pub data: Vec<u8>,
 }
@@ -111,12 +119,12 @@ pub enum ContractIdentifier {
     Qualified(QualifiedContractIdentifier)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OptionalData {
     pub data: Option<Box<Value>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ResponseData {
     pub committed: bool,
     pub data: Box<Value>,
@@ -169,7 +177,7 @@ impl TraitIdentifier {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Value {
     Int(i128),
     UInt(u128),
@@ -236,6 +244,20 @@ impl PartialEq for TupleData {
     }
 }
 
+// like equality, hashing ignores the stored type signatures of lists and tuples, so that
+//   values which compare equal always hash equal.
+impl Hash for ListData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl Hash for TupleData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data_map.hash(state);
+    }
+}
+
 pub const NONE: Value = Value::Optional(OptionalData { data: None });
 
 impl Value {
@@ -696,4 +718,47 @@ mod test {
             vec![("a".into(), Value::Int(2))]).unwrap())),
                    "(tuple (a 2))");
     }
+
+    #[test]
+    fn test_reordered_tuples_hash_equal() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        fn hash_of<T: Hash>(item: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            item.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let type_a = TupleTypeSignature::try_from(vec![
+            ("owner".into(), TypeSignature::PrincipalType),
+            ("balance".into(), TypeSignature::UIntType)]).unwrap();
+        let type_b = TupleTypeSignature::try_from(vec![
+            ("balance".into(), TypeSignature::UIntType),
+            ("owner".into(), TypeSignature::PrincipalType)]).unwrap();
+        assert_eq!(type_a, type_b);
+        assert_eq!(hash_of(&type_a), hash_of(&type_b));
+        assert_eq!(hash_of(&TypeSignature::from(type_a.clone())), hash_of(&TypeSignature::from(type_b)));
+
+        let value_a = Value::from(TupleData::from_data(vec![
+            ("a".into(), Value::Int(1)), ("b".into(), Value::Bool(true))]).unwrap());
+        let value_b = Value::from(TupleData::from_data(vec![
+            ("b".into(), Value::Bool(true)), ("a".into(), Value::Int(1))]).unwrap());
+        assert_eq!(value_a, value_b);
+        assert_eq!(hash_of(&value_a), hash_of(&value_b));
+
+        // a list's stored type signature affects neither equality nor hashing
+        let narrow = Value::list_from(vec![Value::Int(5), Value::Int(2)]).unwrap();
+        let wide = Value::list_with_type(vec![Value::Int(5), Value::Int(2)],
+                                         ListTypeData::new_list(TypeSignature::IntType, 10).unwrap()).unwrap();
+        assert_eq!(narrow, wide);
+        assert_eq!(hash_of(&narrow), hash_of(&wide));
+
+        let mut cache = HashMap::new();
+        cache.insert(TypeSignature::from(type_a), "first");
+        cache.insert(TypeSignature::from(TupleTypeSignature::try_from(vec![
+            ("balance".into(), TypeSignature::UIntType),
+            ("owner".into(), TypeSignature::PrincipalType)]).unwrap()), "second");
+        assert_eq!(cache.len(), 1);
+    }
 }
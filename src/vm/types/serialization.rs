@@ -547,6 +547,39 @@ impl Value {
     }
 }
 
+impl TypeSignature {
+    /// The largest number of bytes `Value::serialize_write` may produce for
+    ///   a value of this type.
+    pub fn max_serialized_size(&self) -> u64 {
+        use super::TypeSignature::*;
+        // every value begins with its type prefix byte
+        let contents: u64 = match self {
+            NoType => 0,
+            BoolType => 0,
+            IntType | UIntType => 16,
+            BufferType(len) => 4 + u32::from(len) as u64,
+            // a contract principal is its issuer, followed by a length-prefixed name
+            PrincipalType | TraitReferenceType(_) => 21 + 1 + MAX_STRING_LEN as u64,
+            OptionalType(some_type) => some_type.max_serialized_size(),
+            ResponseType(response_types) => {
+                let (ok_type, err_type) = response_types.as_ref();
+                std::cmp::max(ok_type.max_serialized_size(), err_type.max_serialized_size())
+            },
+            ListType(list_type) => {
+                let entry_size = list_type.get_list_item_type().max_serialized_size();
+                4u64.saturating_add(entry_size.saturating_mul(list_type.get_max_len() as u64))
+            },
+            TupleType(tuple_type) => {
+                tuple_type.get_type_map().iter()
+                    .fold(4u64, |total, (name, field_type)| {
+                        total.saturating_add(1 + name.len() as u64 + field_type.max_serialized_size())
+                    })
+            }
+        };
+        contents.saturating_add(1)
+    }
+}
+
 impl ClaritySerializable for Value {
     fn serialize(&self) -> String {
         let mut byte_serialization = Vec::new();
@@ -728,6 +761,32 @@ mod tests {
         
     }
 
+    #[test]
+    fn test_max_serialized_size() {
+        // each of these is the largest value of its own type
+        let values = [
+            Value::Int(1),
+            Value::Bool(true),
+            Value::none(),
+            Value::some(Value::UInt(1)).unwrap(),
+            Value::okay(Value::buff_from(vec![1, 2, 3]).unwrap()).unwrap(),
+            Value::list_from(vec![Value::Int(1), Value::Int(2)]).unwrap(),
+            Value::from(TupleData::from_data(vec![
+                ("a".into(), Value::Int(1)),
+                ("bb".into(), Value::buff_from(vec![1, 2]).unwrap())]).unwrap())];
+
+        for value in values.iter() {
+            assert_eq!(value.serialize().len() as u64 / 2,
+                       TypeSignature::type_of(value).max_serialized_size());
+        }
+
+        // the shorter arm of a response, or a shorter list, is smaller than the bound
+        let response = Value::okay(Value::Bool(true)).unwrap();
+        assert!((response.serialize().len() as u64 / 2) <
+                TypeSignature::from("(response bool int)").max_serialized_size());
+        assert_eq!(TypeSignature::from("(list 3 int)").max_serialized_size(), 1 + 4 + 3 * 17);
+    }
+
     #[test]
     fn test_tuples() {
         let t_1 = Value::from(TupleData::from_data(vec![
@@ -6,7 +6,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use address::c32;
 use vm::costs::{cost_functions, CostOverflowingMath};
-use vm::types::{Value, MAX_VALUE_SIZE, MAX_TYPE_DEPTH, WRAPPER_VALUE_SIZE,
+use vm::types::{Value, MAX_VALUE_SIZE, MAX_TYPE_DEPTH, MAX_LIST_DIMENSION, MAX_LIST_LENGTH, WRAPPER_VALUE_SIZE,
                 QualifiedContractIdentifier, StandardPrincipalData, TraitIdentifier};
 use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName, ContractName, TraitDefinition};
 use vm::errors::{RuntimeErrorType, CheckErrors, IncomparableError, Error as VMError};
@@ -36,12 +36,12 @@ impl AssetIdentifier {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TupleTypeSignature {
     type_map: BTreeMap<ClarityName, TypeSignature>
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BufferLength (u32);
 
 // INVARIANTS enforced by the Type Signatures.
@@ -51,7 +51,7 @@ pub struct BufferLength (u32);
 //   2. The only methods which may be called on TypeSignatures that are too large
 //        (i.e., the only function that can be called by the constructor before
 //         it fails) is the `.size()` method, which may be used to check the size.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TypeSignature {
     NoType,
     IntType,
@@ -80,11 +80,15 @@ use self::TypeSignature::{
     TraitReferenceType
 };
 
+pub const BUFF_65: TypeSignature = BufferType(BufferLength(65));
 pub const BUFF_64: TypeSignature = BufferType(BufferLength(64));
+pub const BUFF_33: TypeSignature = BufferType(BufferLength(33));
 pub const BUFF_32: TypeSignature = BufferType(BufferLength(32));
 pub const BUFF_20: TypeSignature = BufferType(BufferLength(20));
+pub const BUFF_16: TypeSignature = BufferType(BufferLength(16));
+pub const BUFF_1: TypeSignature = BufferType(BufferLength(1));
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ListTypeData {
     max_len: u32,
     entry_type: Box<TypeSignature>
@@ -106,6 +110,9 @@ pub struct FixedFunction {
 pub enum FunctionType {
     Variadic(TypeSignature, TypeSignature),
     Fixed(FixedFunction),
+    // Functions with fixed required arguments, followed by trailing optional arguments
+    //   which may be omitted from the end of a call (and then take their defaults)
+    FixedWithOptional(FixedFunction, Vec<FunctionArg>),
     // Functions where the single input is a union type, e.g., Buffer or Int
     UnionArgs(Vec<TypeSignature>, TypeSignature),
     ArithmeticVariadic, ArithmeticBinary, ArithmeticComparison
@@ -268,6 +275,12 @@ impl TypeSignature {
     }
 
     pub fn admits_type(&self, other: &TypeSignature) -> bool {
+        self.admits_type_with(other, false)
+    }
+
+    /// Like `admits_type`, but if `tuple_width_subtyping` is set, a tuple type also admits
+    ///   tuples with fields in addition to its own, e.g., `(tuple (a int))` admits `(tuple (a int) (b bool))`.
+    pub fn admits_type_with(&self, other: &TypeSignature, tuple_width_subtyping: bool) -> bool {
         match self {
             ListType(ref my_list_type) => {
                 if let ListType(other_list_type) = other {
@@ -275,7 +288,7 @@ impl TypeSignature {
                         // if other is an empty list, a list type should always admit.
                         true
                     } else if my_list_type.max_len >= other_list_type.max_len {
-                        my_list_type.entry_type.admits_type(&*other_list_type.entry_type)
+                        my_list_type.entry_type.admits_type_with(&*other_list_type.entry_type, tuple_width_subtyping)
                     } else {
                         false
                     }
@@ -290,7 +303,7 @@ impl TypeSignature {
                     if other_inner_type.is_no_type() {
                         true
                     } else {
-                        my_inner_type.admits_type(other_inner_type)
+                        my_inner_type.admits_type_with(other_inner_type, tuple_width_subtyping)
                     }
                 } else {
                     false
@@ -304,12 +317,12 @@ impl TypeSignature {
                     //   if both OkType and ErrType admit => admit
                     //   otherwise fail.
                     if other_inner_type.0.is_no_type() {
-                        my_inner_type.1.admits_type(&other_inner_type.1)
+                        my_inner_type.1.admits_type_with(&other_inner_type.1, tuple_width_subtyping)
                     } else if other_inner_type.1.is_no_type() {
-                        my_inner_type.0.admits_type(&other_inner_type.0)
+                        my_inner_type.0.admits_type_with(&other_inner_type.0, tuple_width_subtyping)
                     } else {
-                        my_inner_type.1.admits_type(&other_inner_type.1)
-                            && my_inner_type.0.admits_type(&other_inner_type.0)
+                        my_inner_type.1.admits_type_with(&other_inner_type.1, tuple_width_subtyping)
+                            && my_inner_type.0.admits_type_with(&other_inner_type.0, tuple_width_subtyping)
                     }
                 } else {
                     false
@@ -324,7 +337,7 @@ impl TypeSignature {
             },
             TupleType(ref tuple_sig) => {
                 if let TupleType(ref other_tuple_sig) = other {
-                    tuple_sig.admits(other_tuple_sig)
+                    tuple_sig.admits_with(other_tuple_sig, tuple_width_subtyping)
                 } else {
                     false
                 }
@@ -390,13 +403,19 @@ impl TupleTypeSignature {
     }
 
     pub fn admits(&self, other: &TupleTypeSignature) -> bool {
-        if self.type_map.len() != other.type_map.len() {
+        self.admits_with(other, false)
+    }
+
+    /// Like `admits`, but if `tuple_width_subtyping` is set, `other` may have fields
+    ///   in addition to this tuple's.
+    pub fn admits_with(&self, other: &TupleTypeSignature, tuple_width_subtyping: bool) -> bool {
+        if !tuple_width_subtyping && self.type_map.len() != other.type_map.len() {
             return false
         }
 
         for (name, my_type_sig) in self.type_map.iter() {
             if let Some(other_type_sig) = other.type_map.get(name) {
-                if !my_type_sig.admits_type(other_type_sig) {
+                if !my_type_sig.admits_type_with(other_type_sig, tuple_width_subtyping) {
                     return false
                 }
             } else {
@@ -483,14 +502,31 @@ impl TypeSignature {
                                 .expect("FAIL: Max Clarity Value Size is no longer realizable in Buffer Type")))
     }
 
+    /// Returns the sequence type with its maximum length lowered to `new_max`, or `None` if this
+    ///   is not a sequence type, or `new_max` exceeds its current maximum length.
+    pub fn narrow_len(&self, new_max: u32) -> Option<TypeSignature> {
+        match self {
+            ListType(list_data) if new_max <= list_data.max_len => {
+                Some(ListType(ListTypeData {
+                    entry_type: list_data.entry_type.clone(),
+                    max_len: new_max
+                }))
+            },
+            BufferType(buff_len) if new_max <= u32::from(buff_len) => {
+                Some(BufferType(BufferLength(new_max)))
+            },
+            _ => None
+        }
+    }
+
     /// If one of the types is a NoType, return Ok(the other type), otherwise return least_supertype(a, b)
-    fn factor_out_no_type(a: &TypeSignature, b: &TypeSignature) -> Result<TypeSignature> {
+    fn factor_out_no_type(a: &TypeSignature, b: &TypeSignature, tuple_width_subtyping: bool) -> Result<TypeSignature> {
         if a.is_no_type() {
             Ok(b.clone())
         } else if b.is_no_type() {
             Ok(a.clone())
         } else {
-            Self::least_supertype(a, b)
+            Self::least_supertype_with(a, b, tuple_width_subtyping)
         }
     }
 
@@ -516,18 +552,30 @@ impl TypeSignature {
     ///      least_supertype(A, B) := if A != B, error, else A
     ///
     pub fn least_supertype(a: &TypeSignature, b: &TypeSignature) -> Result<TypeSignature> {
+        Self::least_supertype_with(a, b, false)
+    }
+
+    /// Like `least_supertype`, but if `tuple_width_subtyping` is set, the supertype of two
+    ///   tuples has only the fields common to both, and fails if they have no fields in common.
+    pub fn least_supertype_with(a: &TypeSignature, b: &TypeSignature, tuple_width_subtyping: bool) -> Result<TypeSignature> {
         match (a, b) {
             (TupleType(TupleTypeSignature{ type_map: types_a }), TupleType(TupleTypeSignature{ type_map: types_b })) => {
-                if types_a.len() != types_b.len() {
+                if !tuple_width_subtyping && types_a.len() != types_b.len() {
                     return Err(CheckErrors::TypeError(a.clone(), b.clone()))
                 }
                 let mut type_map_out = BTreeMap::new();
                 for (name, entry_a) in types_a.iter() {
-                    let entry_b = types_b.get(name)
-                        .ok_or(CheckErrors::TypeError(a.clone(), b.clone()))?;
-                    let entry_out = Self::least_supertype(entry_a, entry_b)?;
+                    let entry_b = match types_b.get(name) {
+                        Some(entry_b) => entry_b,
+                        None if tuple_width_subtyping => continue,
+                        None => return Err(CheckErrors::TypeError(a.clone(), b.clone()))
+                    };
+                    let entry_out = Self::least_supertype_with(entry_a, entry_b, tuple_width_subtyping)?;
                     type_map_out.insert(name.clone(), entry_out);
                 }
+                if type_map_out.is_empty() {
+                    return Err(CheckErrors::TypeError(a.clone(), b.clone()))
+                }
                 Ok(TupleTypeSignature::try_from(type_map_out).map(|x| x.into())
                    .expect("ERR: least_supertype attempted to construct a too-large supertype of two types"))
             },
@@ -538,19 +586,19 @@ impl TypeSignature {
                     } else if *len_b == 0 {
                         *(entry_a.clone())
                     } else {
-                        Self::least_supertype(entry_a, entry_b)?
+                        Self::least_supertype_with(entry_a, entry_b, tuple_width_subtyping)?
                     };
                 let max_len = cmp::max(len_a, len_b);
                 Ok(Self::list_of(entry_type, *max_len)
                    .expect("ERR: least_supertype attempted to construct a too-large supertype of two types"))
             },
             (ResponseType(resp_a), ResponseType(resp_b)) => {
-                let ok_type = Self::factor_out_no_type(&resp_a.0, &resp_b.0)?;
-                let err_type = Self::factor_out_no_type(&resp_a.1, &resp_b.1)?;
+                let ok_type = Self::factor_out_no_type(&resp_a.0, &resp_b.0, tuple_width_subtyping)?;
+                let err_type = Self::factor_out_no_type(&resp_a.1, &resp_b.1, tuple_width_subtyping)?;
                 Ok(Self::new_response(ok_type, err_type)?)
             },
             (OptionalType(some_a), OptionalType(some_b)) => {
-                let some_type = Self::factor_out_no_type(some_a, some_b)?;
+                let some_type = Self::factor_out_no_type(some_a, some_b, tuple_width_subtyping)?;
                 Ok(Self::new_option(some_type)?)
             },
             (BufferType(buff_a), BufferType(buff_b)) => {
@@ -561,6 +609,9 @@ impl TypeSignature {
                 }.clone();
                 Ok(BufferType(buff_len))
             },
+            // `NoType` is the bottom type: a diverging arm, the entries of `(list)`, or the
+            //   contents of `none`, all of which must unify with any type. It is therefore
+            //   not a checker failure here, even though `no_type()` also marks syntactic positions.
             (NoType, x) | (x, NoType) => {
                 Ok(x.clone())
             },
@@ -578,6 +629,15 @@ impl TypeSignature {
         ListTypeData::new_list(item_type, max_len).map(|x| x.into())
     }
 
+    /// Like `list_of`, for the type of a list that is constructed rather than declared:
+    ///   its length may not exceed `MAX_LIST_LENGTH`, even if its size would be allowed.
+    pub fn constructed_list_of(item_type: TypeSignature, max_len: u32) -> Result<TypeSignature> {
+        if max_len > MAX_LIST_LENGTH {
+            return Err(CheckErrors::ConstructedListTooLarge)
+        }
+        TypeSignature::list_of(item_type, max_len)
+    }
+
     pub fn empty_list() -> ListTypeData {
         ListTypeData {
             entry_type: Box::new(TypeSignature::NoType),
@@ -611,16 +671,49 @@ impl TypeSignature {
     }
 
     pub fn parent_list_type(children: &[TypeSignature]) -> std::result::Result<ListTypeData, CheckErrors> {
+        Self::parent_list_type_with(children, false)
+    }
+
+    /// Like `parent_list_type`, unifying the entries as `least_supertype_with`.
+    pub fn parent_list_type_with(children: &[TypeSignature], tuple_width_subtyping: bool) -> std::result::Result<ListTypeData, CheckErrors> {
+        if let Some(entry_type) = Self::list_entry_type(children, tuple_width_subtyping)? {
+            let len = u32::try_from(children.len())
+                .map_err(|_| CheckErrors::ValueTooLarge)?;
+            ListTypeData::new_list(entry_type, len)
+        } else {
+            Ok(TypeSignature::empty_list())
+        }
+    }
+
+    /// Like `parent_list_type_with`, for the type of a `list` expression in a contract, which may
+    ///   not exceed `MAX_LIST_DIMENSION` or `MAX_LIST_LENGTH`. These limits are only enforced
+    ///   during analysis: lists constructed at runtime, e.g. by `Value::list_from`, are unaffected.
+    pub fn constructed_parent_list_type(children: &[TypeSignature], tuple_width_subtyping: bool) -> std::result::Result<ListTypeData, CheckErrors> {
+        if let Some(entry_type) = Self::list_entry_type(children, tuple_width_subtyping)? {
+            let dimension = 1 + entry_type.list_dimension();
+            if dimension > MAX_LIST_DIMENSION {
+                return Err(CheckErrors::ListDimensionTooHigh(dimension))
+            }
+            let len = u32::try_from(children.len())
+                .map_err(|_| CheckErrors::ConstructedListTooLarge)?;
+            if len > MAX_LIST_LENGTH {
+                return Err(CheckErrors::ConstructedListTooLarge)
+            }
+            ListTypeData::new_list(entry_type, len)
+        } else {
+            Ok(TypeSignature::empty_list())
+        }
+    }
+
+    fn list_entry_type(children: &[TypeSignature], tuple_width_subtyping: bool) -> std::result::Result<Option<TypeSignature>, CheckErrors> {
         if let Some((first, rest)) = children.split_first() {
             let mut current_entry_type = first.clone();
             for next_entry in rest.iter() {
-                current_entry_type = Self::least_supertype(&current_entry_type, next_entry)?;
+                current_entry_type = Self::least_supertype_with(&current_entry_type, next_entry, tuple_width_subtyping)?;
             }
-            let len = u32::try_from(children.len())
-                .map_err(|_| CheckErrors::ValueTooLarge)?;
-            ListTypeData::new_list(current_entry_type, len)
+            Ok(Some(current_entry_type))
         } else {
-            Ok(TypeSignature::empty_list())
+            Ok(None)
         }
     }
 }
@@ -743,6 +836,9 @@ impl TypeSignature {
 
             // Extract function's name
             let fn_name = args[0].match_atom().ok_or(CheckErrors::DefineTraitBadSignature)?;
+            if trait_signature.contains_key(fn_name) {
+                return Err(CheckErrors::NameAlreadyUsed(fn_name.to_string()))
+            }
 
             // Extract function's arguments
             let fn_args_exprs = args[1].match_list().ok_or(CheckErrors::DefineTraitBadSignature)?;
@@ -777,6 +873,28 @@ impl TypeSignature {
 /// TypeSignature constructors will fail instead of constructing such a type.
 ///   because of this, the public interface to size is infallible.
 impl TypeSignature {
+    /// The number of list types nested in this type's list entries: `(list 2 (list 2 int))`
+    ///   has dimension 2, and any non-list type has dimension 0.
+    pub fn list_dimension(&self) -> u8 {
+        match self {
+            ListType(list_type) => 1 + list_type.get_list_item_type().list_dimension(),
+            _ => 0
+        }
+    }
+
+    /// Whether this type contains a sequence with a maximum length of zero, such as
+    ///   `(buff 0)` or `(tuple (items (list 0 int)))`, which can only ever hold an empty sequence.
+    pub fn contains_empty_sequence(&self) -> bool {
+        match self {
+            BufferType(len) => u32::from(len) == 0,
+            ListType(list_type) => list_type.get_max_len() == 0 || list_type.get_list_item_type().contains_empty_sequence(),
+            TupleType(tuple_type) => tuple_type.get_type_map().values().any(|t| t.contains_empty_sequence()),
+            OptionalType(inner_type) => inner_type.contains_empty_sequence(),
+            ResponseType(inner_types) => inner_types.0.contains_empty_sequence() || inner_types.1.contains_empty_sequence(),
+            _ => false
+        }
+    }
+
     pub fn depth(&self) -> u8 {
         // unlike inner_size, depth will never threaten to overflow,
         //  because a new type can only increase depth by 1.
@@ -795,6 +913,9 @@ impl TypeSignature {
         }
     }
 
+    /// The maximum size of a value of this type. Type construction fails with `ValueTooLarge`
+    ///   if this would exceed `MAX_VALUE_SIZE`, so this bounds every function argument,
+    ///   return type, and stored value.
     pub fn size(&self) -> u32 {
         self.inner_size()
             .expect("FAIL: .size() overflowed on too large of a type. construction should have failed!")
@@ -983,7 +1104,7 @@ impl fmt::Display for TupleTypeSignature {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "(tuple")?;
         for (field_name, field_type) in self.type_map.iter() {
-            write!(f, " ({} {})", &**field_name, field_type)?;
+            write!(f, " ({} {})", &**field_name, field_type.to_clarity_string())?;
         }
         write!(f, ")")
     }
@@ -995,24 +1116,33 @@ impl fmt::Display for AssetIdentifier {
     }
 }
 
-impl fmt::Display for TypeSignature {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl TypeSignature {
+    /// Writes the type as it would be written in a contract. Any type which does not
+    ///   contain a `NoType` can be parsed back from this string by `parse_type_repr`.
+    pub fn to_clarity_string(&self) -> String {
         match self {
-            NoType => write!(f, "UnknownType"),
-            IntType => write!(f, "int"),
-            UIntType => write!(f, "uint"),
-            BoolType => write!(f, "bool"),
-            BufferType(len) => write!(f, "(buff {})", len),
-            OptionalType(t) => write!(f, "(optional {})", t),
-            ResponseType(v) => write!(f, "(response {} {})", v.0, v.1),
-            TupleType(t) => write!(f, "{}", t),
-            PrincipalType => write!(f, "principal"),
-            ListType(list_type_data) => write!(f, "(list {} {})", list_type_data.max_len, list_type_data.entry_type),
-            TraitReferenceType(trait_alias) => write!(f, "<{}>", trait_alias.to_string()),
+            NoType => "UnknownType".to_string(),
+            IntType => "int".to_string(),
+            UIntType => "uint".to_string(),
+            BoolType => "bool".to_string(),
+            PrincipalType => "principal".to_string(),
+            BufferType(len) => format!("(buff {})", len),
+            OptionalType(t) => format!("(optional {})", t.to_clarity_string()),
+            ResponseType(v) => format!("(response {} {})", v.0.to_clarity_string(), v.1.to_clarity_string()),
+            TupleType(t) => t.to_string(),
+            ListType(list_type_data) => format!("(list {} {})", list_type_data.max_len,
+                                                list_type_data.entry_type.to_clarity_string()),
+            TraitReferenceType(trait_alias) => format!("<{}>", trait_alias.to_string()),
         }
     }
 }
 
+impl fmt::Display for TypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_clarity_string())
+    }
+}
+
 impl fmt::Display for BufferLength {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -1038,6 +1168,50 @@ mod test {
         TypeSignature::parse_type_repr(expr, &mut ()).unwrap_err()
     }
 
+    #[test]
+    fn test_parse_nested_type_repr() {
+        use vm::ast::parse;
+        let expr = &parse(&QualifiedContractIdentifier::transient(),
+                          "(optional (list 10 (tuple (a int) (b (response uint bool)))))").unwrap()[0];
+        let expected = TypeSignature::new_option(
+            TypeSignature::list_of(
+                TupleTypeSignature::try_from(vec![
+                    ("a".into(), IntType),
+                    ("b".into(), TypeSignature::new_response(UIntType, BoolType).unwrap())]).unwrap().into(),
+                10).unwrap()).unwrap();
+        assert_eq!(TypeSignature::parse_type_repr(expr, &mut ()).unwrap(), expected);
+
+        assert_eq!(fail_parse("(optional (list int))"), InvalidTypeDescription);
+        assert_eq!(fail_parse("(maybe int)"), InvalidTypeDescription);
+    }
+
+    #[test]
+    fn test_to_clarity_string() {
+        let types = ["int",
+                     "(optional (list 5 (buff 32)))",
+                     "(response (tuple (amount uint) (owners (list 2 principal))) int)",
+                     "(list 3 (optional (response bool (buff 1))))"];
+
+        for type_str in types.iter() {
+            let type_sig = TypeSignature::from(*type_str);
+            assert_eq!(&type_sig.to_clarity_string(), type_str);
+            assert_eq!(TypeSignature::from(type_sig.to_clarity_string().as_str()), type_sig);
+        }
+    }
+
+    #[test]
+    fn test_narrow_len() {
+        assert_eq!(TypeSignature::from("(buff 10)").narrow_len(3),
+                   Some(TypeSignature::from("(buff 3)")));
+        assert_eq!(TypeSignature::from("(buff 10)").narrow_len(10),
+                   Some(TypeSignature::from("(buff 10)")));
+        assert_eq!(TypeSignature::from("(buff 10)").narrow_len(20), None);
+        assert_eq!(TypeSignature::from("(list 5 int)").narrow_len(2),
+                   Some(TypeSignature::from("(list 2 int)")));
+        assert_eq!(TypeSignature::from("(list 5 int)").narrow_len(6), None);
+        assert_eq!(TypeSignature::IntType.narrow_len(1), None);
+    }
+
     #[test]
     fn type_of_list_of_buffs() {
         let value = execute("(list \"abc\" \"abcde\")").unwrap().unwrap();
@@ -1063,6 +1237,16 @@ mod test {
         assert_eq!(TupleTypeSignature::try_from(keys).unwrap_err(), ValueTooLarge);
     }
 
+    #[test]
+    fn tuple_of_buffers_size_boundary() {
+        // type_size = 2*2 + 2*(5+1) = 16
+        // size = 2*2 + 16 + (4+a+1) + (4+b+1) = 30 + a + b
+        let at_limit = TypeSignature::from("(tuple (a (buff 524273)) (b (buff 524273)))");
+        assert_eq!(at_limit.size(), MAX_VALUE_SIZE);
+
+        assert_eq!(fail_parse("(tuple (a (buff 524273)) (b (buff 524274)))"), ValueTooLarge);
+    }
+
     #[test]
     fn test_construction() {
         let bad_type_descriptions = [